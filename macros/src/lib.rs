@@ -0,0 +1,681 @@
+//! `#[derive(CommandArguments)]` for the `Arguments` of a command in `lu_discord_bot`
+//!
+//! ```ignore
+//! #[derive(CommandArguments)]
+//! #[arguments(command = DropCommand)]
+//! pub struct DropArguments {
+//!     /// An item in LEGO Universe.
+//!     #[option(required, autocomplete = "object")]
+//!     pub item: i32,
+//!     #[page]
+//!     pub page: usize,
+//!     #[custom_id(default = DEFAULT_PAGE_SIZE)]
+//!     pub size: usize,
+//! }
+//! ```
+//!
+//! - `#[arguments(command = ..)]` names the command whose custom ids these are, and
+//!   `version = ..` sets `ToCustomId::VERSION`
+//! - `#[option]` fields are slash command options named after the field, and their doc comment is
+//!   the description
+//!   - `required` ones must be given, `default = ..` ones start at it when left out and the others
+//!     must be an `Option`
+//!   - `autocomplete = "object"` suggests with `AutocompleteQueries::autocomplete_object`, and
+//!     `autocomplete = path::to::function` with any `fn(&str) -> Vec<AutocompleteChoice>`
+//!   - `min = ..` and `max = ..` bound numbers in Discord, from 0 up for whole numbers
+//!   - `longest = ..` replaces `OptionValue::longest`, ex: for a number that `max` bounds
+//! - `#[page]` fields start at `START_PAGE` from a slash command
+//! - `#[custom_id(default = ..)]` fields are only in custom ids and start at `default`
+//!
+//! It implements `ToCustomId`, both `TryFrom`s that `InteractionCommand::Arguments` needs and
+//! `CommandArguments`, whose `options()` and `autocomplete()` the command returns
+//!
+//! `#[derive(OptionChoice)]` makes a fieldless enum a static choice list for an `#[option]`:
+//!
+//! ```ignore
+//! #[derive(Clone, Copy, OptionChoice)]
+//! pub enum DropSort {
+//!     #[choice(label = "Highest chance first")]
+//!     Highest,
+//!     #[choice(label = "Lowest chance first")]
+//!     Lowest,
+//! }
+//! ```
+//!
+//! - Each variant is kept in custom ids as its name in snake_case, unless it sets `key = ".."`
+
+use heck::ToSnakeCase;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Error, Expr, Fields, GenericArgument, Ident,
+    LitInt, LitStr, Path, PathArguments, Type, Variant,
+};
+
+#[proc_macro_derive(CommandArguments, attributes(arguments, option, page, custom_id))]
+pub fn derive_command_arguments(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(OptionChoice, attributes(choice))]
+pub fn derive_option_choice(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_choice(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// What suggests values for an `#[option]`
+enum Autocomplete {
+    /// `AutocompleteQueries::autocomplete_{kind}`, ex: `"object"`
+    Query(Ident),
+    /// A `fn(&str) -> Vec<AutocompleteChoice>`
+    Function(Path),
+}
+
+/// Where a field comes from
+enum Source {
+    /// A slash command option
+    Option {
+        description: String,
+        required: bool,
+        /// What it is when the user leaves it out
+        default: Option<Box<Expr>>,
+        autocomplete: Option<Autocomplete>,
+        min: Option<Box<Expr>>,
+        max: Option<Box<Expr>>,
+    },
+    /// Only in custom ids, starting at this from a slash command
+    CustomId { default: Expr },
+}
+
+struct Field {
+    ident: Ident,
+    /// `T` of an `Option<T>`, or the type itself
+    value_type: Type,
+    optional: bool,
+    source: Source,
+    longest: Option<Expr>,
+}
+
+struct Arguments {
+    command: Path,
+    version: Option<LitInt>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let arguments = parse_arguments(&input)?;
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            input.span(),
+            "only structs can be command arguments",
+        ));
+    };
+    let fields = match &data.fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .map(parse_field)
+            .collect::<syn::Result<Vec<_>>>()?,
+        Fields::Unit => vec![],
+        Fields::Unnamed(fields) => {
+            return Err(Error::new(
+                fields.span(),
+                "command arguments need named fields",
+            ));
+        }
+    };
+
+    let krate = quote!(::lu_discord_bot);
+    let serenity = quote!(::serenity::all);
+    let command = &arguments.command;
+    let version = arguments
+        .version
+        .as_ref()
+        .map(|version| quote!(const VERSION: u32 = #version;));
+
+    let construct = |values: Vec<TokenStream2>| {
+        let idents = fields.iter().map(|field| &field.ident);
+        match &data.fields {
+            Fields::Unit => quote!(#name),
+            _ => quote!(#name { #(#idents: #values,)* }),
+        }
+    };
+
+    let parameters = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let key = ident.to_string();
+        match field.optional {
+            true => quote! {
+                if let Some(value) = &self.#ident {
+                    parameters.push(format!(
+                        "{}={}",
+                        #key,
+                        #krate::interaction_command::encode_value(&value.to_string())
+                    ));
+                }
+            },
+            false => quote! {
+                parameters.push(format!(
+                    "{}={}",
+                    #key,
+                    #krate::interaction_command::encode_value(&self.#ident.to_string())
+                ));
+            },
+        }
+    });
+
+    let longest = construct(
+        fields
+            .iter()
+            .map(|field| {
+                let value_type = &field.value_type;
+                let value = match &field.longest {
+                    Some(longest) => quote!(#longest),
+                    None => quote!(<#value_type as #krate::arguments::OptionValue>::longest()),
+                };
+                match field.optional {
+                    true => quote!(Some(#value)),
+                    false => value,
+                }
+            })
+            .collect(),
+    );
+
+    let from_custom_id = construct(
+        fields
+            .iter()
+            .map(|field| {
+                let key = field.ident.to_string();
+                match (&field.source, field.optional) {
+                    (_, true) => quote!(options.parse_optional(#key)?),
+                    // so an option added later does not make older custom ids outdated
+                    (
+                        Source::Option {
+                            default: Some(default),
+                            ..
+                        },
+                        _,
+                    ) => quote!(options.parse_optional(#key)?.unwrap_or_else(|| #default)),
+                    _ => quote!(options.parse(#key)?),
+                }
+            })
+            .collect(),
+    );
+
+    let from_slash_command = construct(
+        fields
+            .iter()
+            .map(|field| {
+                let key = field.ident.to_string();
+                let value_type = &field.value_type;
+                match &field.source {
+                    Source::Option { required: true, .. } => quote! {
+                        #krate::arguments::resolved::<#value_type>(options, #key)?.ok_or_else(|| {
+                            #krate::error::BotError::BadArgument(format!(
+                                "Expected required field: `{}`",
+                                #key
+                            ))
+                        })?
+                    },
+                    Source::Option {
+                        default: Some(default),
+                        ..
+                    } => quote! {
+                        #krate::arguments::resolved::<#value_type>(options, #key)?
+                            .unwrap_or_else(|| #default)
+                    },
+                    Source::Option {
+                        required: false, ..
+                    } => {
+                        quote!(#krate::arguments::resolved::<#value_type>(options, #key)?)
+                    }
+                    Source::CustomId { default } => quote!(#default),
+                }
+            })
+            .collect(),
+    );
+
+    let options = fields.iter().filter_map(|field| {
+        let Source::Option {
+            description,
+            required,
+            autocomplete,
+            min,
+            max,
+            ..
+        } = &field.source
+        else {
+            return None;
+        };
+        let key = field.ident.to_string();
+        let value_type = &field.value_type;
+        let autocomplete = autocomplete.is_some();
+        let bound = |bound: &Option<Box<Expr>>| match bound {
+            Some(bound) => quote!(Some((#bound) as f64)),
+            None => quote!(None),
+        };
+        let (min, max) = (bound(min), bound(max));
+        Some(quote! {
+            #krate::arguments::bounded::<#value_type>(
+                <#value_type as #krate::arguments::OptionValue>::add_choices(
+                    #serenity::CreateCommandOption::new(
+                        <#value_type as #krate::arguments::OptionValue>::KIND,
+                        #key,
+                        #description,
+                    )
+                    .required(#required)
+                    .set_autocomplete(#autocomplete),
+                ),
+                #min,
+                #max,
+            )
+        })
+    });
+
+    // serenity only takes whole number bounds from 0 up, so the others fail to compile
+    let bound_checks = fields.iter().flat_map(|field| {
+        let Source::Option { min, max, .. } = &field.source else {
+            return vec![];
+        };
+        let value_type = &field.value_type;
+        [("min", min), ("max", max)]
+            .into_iter()
+            .filter_map(|(name, bound)| {
+                let bound = bound.as_ref()?;
+                let message = format!(
+                    "`{name}` of `{}` must be at least 0, since Discord only bounds whole numbers from 0 up here",
+                    field.ident
+                );
+                Some(quote! {
+                    const _: () = assert!(
+                        !matches!(
+                            <#value_type as #krate::arguments::OptionValue>::KIND,
+                            #serenity::CommandOptionType::Integer
+                        ) || (#bound) as f64 >= 0.0,
+                        #message
+                    );
+                })
+            })
+            .collect()
+    });
+
+    let autocompletes = fields.iter().filter_map(|field| {
+        let Source::Option {
+            autocomplete: Some(kind),
+            ..
+        } = &field.source
+        else {
+            return None;
+        };
+        let key = field.ident.to_string();
+        Some(match kind {
+            Autocomplete::Query(kind) => {
+                let query = format_ident!("autocomplete_{}", kind);
+                quote! {
+                    #key => Some(#krate::queries::AutocompleteQueries::#query(
+                        &*#krate::data::current().cdclient,
+                        option.value,
+                    )),
+                }
+            }
+            Autocomplete::Function(function) => quote!(#key => Some(#function(option.value)),),
+        })
+    });
+
+    Ok(quote! {
+        #(#bound_checks)*
+
+        impl #krate::interaction_command::ToCustomId for #name {
+            const CMD: &'static str =
+                <#command as #krate::interaction_command::InteractionCommand>::NAME;
+            #version
+
+            fn parameters(&self) -> String {
+                #[allow(unused_mut)]
+                let mut parameters: Vec<String> = vec![];
+                #(#parameters)*
+                parameters.join("&")
+            }
+
+            fn longest() -> Self {
+                #longest
+            }
+        }
+
+        impl TryFrom<&#krate::interaction_command::CustomIdOptions> for #name {
+            type Error = #krate::error::BotError;
+
+            #[allow(unused_variables)]
+            fn try_from(
+                options: &#krate::interaction_command::CustomIdOptions,
+            ) -> Result<Self, Self::Error> {
+                Ok(#from_custom_id)
+            }
+        }
+
+        impl<'a> TryFrom<&'a [#serenity::ResolvedOption<'a>]> for #name {
+            type Error = #krate::error::BotError;
+
+            #[allow(unused_variables)]
+            fn try_from(options: &'a [#serenity::ResolvedOption<'a>]) -> Result<Self, Self::Error> {
+                Ok(#from_slash_command)
+            }
+        }
+
+        impl #krate::arguments::CommandArguments for #name {
+            fn options() -> Vec<#serenity::CreateCommandOption> {
+                vec![#(#options),*]
+            }
+
+            #[allow(unused_variables)]
+            fn autocomplete(
+                option: #serenity::AutocompleteOption<'_>,
+            ) -> Option<Vec<#serenity::AutocompleteChoice>> {
+                match option.name {
+                    #(#autocompletes)*
+                    _ => None,
+                }
+            }
+        }
+    })
+}
+
+fn parse_arguments(input: &DeriveInput) -> syn::Result<Arguments> {
+    let mut command = None;
+    let mut version = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("arguments"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("command") {
+                command = Some(meta.value()?.parse::<Path>()?);
+            } else if meta.path.is_ident("version") {
+                version = Some(meta.value()?.parse::<LitInt>()?);
+            } else {
+                return Err(meta.error("expected `command` or `version`"));
+            }
+            Ok(())
+        })?;
+    }
+    let command = command.ok_or_else(|| {
+        Error::new(
+            input.ident.span(),
+            "name the command with `#[arguments(command = ..)]`",
+        )
+    })?;
+    Ok(Arguments { command, version })
+}
+
+fn parse_field(field: &syn::Field) -> syn::Result<Field> {
+    let ident = field.ident.clone().expect("named fields have names");
+    let (value_type, optional) = match option_inner(&field.ty) {
+        Some(inner) => (inner.clone(), true),
+        None => (field.ty.clone(), false),
+    };
+
+    let mut source = None;
+    let mut longest = None;
+    for attr in &field.attrs {
+        if attr.path().is_ident("option") {
+            let (option, option_longest) = parse_option(attr, field, optional)?;
+            source = Some(option);
+            longest = option_longest;
+        } else if attr.path().is_ident("page") {
+            source = Some(Source::CustomId {
+                default: syn::parse_quote!(::lu_discord_bot::pager::START_PAGE),
+            });
+        } else if attr.path().is_ident("custom_id") {
+            let mut default = None;
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("default") {
+                    default = Some(meta.value()?.parse::<Expr>()?);
+                    Ok(())
+                } else if meta.path.is_ident("longest") {
+                    longest = Some(meta.value()?.parse::<Expr>()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `default` or `longest`"))
+                }
+            })?;
+            let default = default
+                .ok_or_else(|| Error::new(attr.span(), "expected `#[custom_id(default = ..)]`"))?;
+            source = Some(Source::CustomId { default });
+        }
+    }
+    let source = source.ok_or_else(|| {
+        Error::new(
+            ident.span(),
+            "give this field `#[option]`, `#[page]` or `#[custom_id(default = ..)]`",
+        )
+    })?;
+
+    Ok(Field {
+        ident,
+        value_type,
+        optional,
+        source,
+        longest,
+    })
+}
+
+/// The option and the `longest` it sets, if any
+fn parse_option(
+    attr: &Attribute,
+    field: &syn::Field,
+    optional: bool,
+) -> syn::Result<(Source, Option<Expr>)> {
+    let mut required = false;
+    let mut default = None;
+    let mut autocomplete = None;
+    let mut min = None;
+    let mut max = None;
+    let mut longest = None;
+    if !matches!(attr.meta, syn::Meta::Path(_)) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("required") {
+                required = true;
+            } else if meta.path.is_ident("default") {
+                default = Some(Box::new(meta.value()?.parse::<Expr>()?));
+            } else if meta.path.is_ident("autocomplete") {
+                let value = meta.value()?;
+                autocomplete = Some(match value.peek(LitStr) {
+                    true => {
+                        let kind = value.parse::<LitStr>()?;
+                        Autocomplete::Query(Ident::new(&kind.value(), kind.span()))
+                    }
+                    false => Autocomplete::Function(value.parse()?),
+                });
+            } else if meta.path.is_ident("min") {
+                min = Some(Box::new(meta.value()?.parse::<Expr>()?));
+            } else if meta.path.is_ident("max") {
+                max = Some(Box::new(meta.value()?.parse::<Expr>()?));
+            } else if meta.path.is_ident("longest") {
+                longest = Some(meta.value()?.parse::<Expr>()?);
+            } else {
+                return Err(meta.error(
+                    "expected `required`, `default`, `autocomplete`, `min`, `max` or `longest`",
+                ));
+            }
+            Ok(())
+        })?;
+    }
+    match (required, default.is_some(), optional) {
+        (false, false, true) | (true, false, false) | (false, true, false) => {}
+        (false, false, false) => {
+            return Err(Error::new(
+                field.ty.span(),
+                "an option must be `required`, have a `default` or be an `Option`",
+            ));
+        }
+        (_, _, true) => {
+            return Err(Error::new(
+                field.ty.span(),
+                "an `Option` is `None` when left out, so it is never `required` or `default`",
+            ));
+        }
+        (true, true, false) => {
+            return Err(Error::new(
+                attr.span(),
+                "a `required` option is never left out, so it has no `default`",
+            ));
+        }
+    }
+    let option = Source::Option {
+        description: description(field)?,
+        required,
+        default,
+        autocomplete,
+        min,
+        max,
+    };
+    Ok((option, longest))
+}
+
+/// The doc comment of a field, which Discord shows under the option
+fn description(field: &syn::Field) -> syn::Result<String> {
+    let lines: Vec<_> = field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(doc) => match &doc.value {
+                Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(line),
+                    ..
+                }) => Some(line.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    if lines.is_empty() {
+        return Err(Error::new(
+            field.span(),
+            "document this option, since its doc comment is its description in Discord",
+        ));
+    }
+    Ok(lines.join(" "))
+}
+
+/// `T` of an `Option<T>`
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.first()? {
+        GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+fn expand_choice(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new(input.span(), "only enums can be option choices"));
+    };
+    let variants = data
+        .variants
+        .iter()
+        .map(parse_variant)
+        .collect::<syn::Result<Vec<_>>>()?;
+    if variants.is_empty() {
+        return Err(Error::new(
+            input.span(),
+            "an option needs at least one choice",
+        ));
+    }
+
+    let krate = quote!(::lu_discord_bot);
+    let idents: Vec<_> = variants.iter().map(|(ident, _, _)| ident).collect();
+    let keys: Vec<_> = variants.iter().map(|(_, key, _)| key).collect();
+    let labels = variants.iter().map(|(_, _, label)| label);
+    let choices = keys
+        .iter()
+        .map(|key| format!("`{key}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Ok(quote! {
+        impl #krate::arguments::OptionChoice for #name {
+            const ALL: &'static [Self] = &[#(Self::#idents),*];
+
+            fn key(&self) -> &'static str {
+                match self {
+                    #(Self::#idents => #keys,)*
+                }
+            }
+
+            fn label(&self) -> &'static str {
+                match self {
+                    #(Self::#idents => #labels,)*
+                }
+            }
+        }
+
+        impl ::std::str::FromStr for #name {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    #(#keys => Ok(Self::#idents),)*
+                    _ => Err(format!("`{}` is not one of {}", s, #choices)),
+                }
+            }
+        }
+
+        impl ::std::fmt::Display for #name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(#krate::arguments::OptionChoice::key(self))
+            }
+        }
+    })
+}
+
+/// The variant, its key and its label
+fn parse_variant(variant: &Variant) -> syn::Result<(Ident, String, String)> {
+    if !matches!(variant.fields, Fields::Unit) {
+        return Err(Error::new(variant.span(), "a choice cannot hold anything"));
+    }
+    let mut key = None;
+    let mut label = None;
+    for attr in variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("choice"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("key") {
+                key = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("label") {
+                label = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else {
+                return Err(meta.error("expected `key` or `label`"));
+            }
+            Ok(())
+        })?;
+    }
+    let label = label.ok_or_else(|| {
+        Error::new(
+            variant.span(),
+            "give this choice the label Discord shows with `#[choice(label = \"..\")]`",
+        )
+    })?;
+    let key = key.unwrap_or_else(|| variant.ident.to_string().to_snake_case());
+    Ok((variant.ident.clone(), key, label))
+}
@@ -0,0 +1,299 @@
+use std::fmt::Display;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use once_cell::sync::OnceCell;
+use rusqlite::{params, Connection};
+use tracing::{error, info};
+
+use crate::error::{BotError, MsgResult};
+use crate::metrics::{InteractionKind, Outcome};
+use crate::CONFIG;
+
+/// What kind of content an option or custom id key looks up
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Lookup {
+    Object,
+    Mission,
+    Skill,
+}
+
+impl Lookup {
+    /// - Keys are the option names the commands use, ex: `item` in `/item drop item:7415`
+    pub fn of_key(key: &str) -> Option<Self> {
+        match key {
+            "item" | "brick" | "enemy" | "npc" | "package" | "smashable" | "vendor"
+            | "activity" | "group" | "table" => Some(Lookup::Object),
+            "mission" | "achievement" => Some(Lookup::Mission),
+            "skill" => Some(Lookup::Skill),
+            _ => None,
+        }
+    }
+
+    fn key(&self) -> &'static str {
+        match self {
+            Lookup::Object => "object",
+            Lookup::Mission => "mission",
+            Lookup::Skill => "skill",
+        }
+    }
+}
+
+impl Display for Lookup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+/// One handled interaction, as stored in the `interactions` table
+struct Event {
+    at: u64,
+    kind: InteractionKind,
+    /// The slash command, or [`crate::interaction_command::CustomIdOptions::cmd`] of a component
+    command: String,
+    lookup: Option<(Lookup, i32)>,
+    guild_id: Option<u64>,
+    outcome: &'static str,
+}
+
+/// Events are written in batches, so autocomplete does not mean one write per keystroke
+const FLUSH_AT: usize = 64;
+const FLUSH_EVERY_SECS: u64 = 30;
+/// Events kept while the database can't be written, so a broken disk can't fill memory
+const MAX_PENDING: usize = 10_000;
+
+/// The `interactions` table and the events not yet written to it
+pub struct AnalyticsStore {
+    conn: Mutex<Connection>,
+    pending: Mutex<(Vec<Event>, Instant)>,
+}
+
+static STORE: OnceCell<AnalyticsStore> = OnceCell::new();
+
+impl AnalyticsStore {
+    /// - `:memory:` keeps it in memory, ex: for tests
+    pub fn open(path: &Path) -> MsgResult<Self> {
+        let conn = Connection::open(path)
+            .map_err(|e| BotError::internal(format!("Could not open `{}`: {e}", path.display())))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS interactions (
+                at          INTEGER NOT NULL,
+                kind        TEXT    NOT NULL,
+                command     TEXT    NOT NULL,
+                lookup_kind TEXT,
+                lookup_id   INTEGER,
+                guild_id    INTEGER,
+                outcome     TEXT    NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS interactions_at ON interactions (at);",
+        )
+        .map_err(|e| {
+            BotError::internal(format!(
+                "Could not create tables in `{}`: {e}",
+                path.display()
+            ))
+        })?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+            pending: Mutex::new((vec![], Instant::now())),
+        })
+    }
+
+    /// Queues an interaction, and writes the queue once it is [`FLUSH_AT`] long or
+    /// [`FLUSH_EVERY_SECS`] old
+    pub fn record(
+        &self,
+        kind: InteractionKind,
+        command: &str,
+        lookup: Option<(Lookup, i32)>,
+        guild_id: Option<u64>,
+        outcome: &Outcome,
+    ) {
+        let due = {
+            let mut pending = self.pending.lock().unwrap();
+            pending.0.push(Event {
+                at: now_secs(),
+                kind,
+                command: command.to_string(),
+                lookup,
+                guild_id,
+                outcome: outcome.label(),
+            });
+            pending.0.len() >= FLUSH_AT || pending.1.elapsed().as_secs() >= FLUSH_EVERY_SECS
+        };
+        if due {
+            if let Err(e) = self.flush() {
+                error!("Could not record interactions: {e}");
+            }
+        }
+    }
+
+    /// Writes the queued events
+    /// - If that fails they are queued again, ahead of newer ones, for the next flush
+    pub fn flush(&self) -> MsgResult<()> {
+        let events = {
+            let mut pending = self.pending.lock().unwrap();
+            pending.1 = Instant::now();
+            std::mem::take(&mut pending.0)
+        };
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        let result = self.write(&events);
+        if result.is_err() {
+            let mut pending = self.pending.lock().unwrap();
+            let newer = std::mem::replace(&mut pending.0, events);
+            pending.0.extend(newer);
+            // the oldest go first
+            let over = pending.0.len().saturating_sub(MAX_PENDING);
+            pending.0.drain(..over);
+        }
+        result
+    }
+
+    /// Events queued and not yet written
+    pub fn pending(&self) -> usize {
+        self.pending.lock().unwrap().0.len()
+    }
+
+    fn write(&self, events: &[Event]) -> MsgResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(BotError::internal)?;
+        {
+            let mut stmt = tx
+                .prepare_cached(
+                    "INSERT INTO interactions
+                        (at, kind, command, lookup_kind, lookup_id, guild_id, outcome)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                )
+                .map_err(BotError::internal)?;
+            for event in events {
+                stmt.execute(params![
+                    event.at as i64,
+                    event.kind.to_string(),
+                    event.command,
+                    event.lookup.map(|(lookup, _)| lookup.key()),
+                    event.lookup.map(|(_, id)| id),
+                    event.guild_id.map(|id| id as i64),
+                    event.outcome,
+                ])
+                .map_err(BotError::internal)?;
+            }
+        }
+        tx.commit().map_err(BotError::internal)
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_secs())
+        .unwrap_or(0)
+}
+
+/// Opens the analytics database if `analytics_path` is set, so a bad path is reported at startup
+pub fn init() -> MsgResult<()> {
+    // TOML has no null, so `analytics_path = ""` is how the file turns it off
+    let path = CONFIG
+        .analytics_path
+        .as_ref()
+        .filter(|path| !path.as_os_str().is_empty());
+    let Some(path) = path else {
+        info!("`analytics_path` is not set, so interactions are not recorded");
+        return Ok(());
+    };
+    STORE.get_or_try_init(|| AnalyticsStore::open(path))?;
+    Ok(())
+}
+
+/// Queues an interaction to be written (does nothing unless [`init`] opened the database)
+pub fn record(
+    kind: InteractionKind,
+    command: &str,
+    lookup: Option<(Lookup, i32)>,
+    guild_id: Option<u64>,
+    outcome: &Outcome,
+) {
+    if let Some(store) = STORE.get() {
+        store.record(kind, command, lookup, guild_id, outcome);
+    }
+}
+
+/// Writes what is queued, ex: before shutting down (does nothing unless [`init`] opened the
+/// database)
+pub fn flush() -> MsgResult<()> {
+    STORE.get().map_or(Ok(()), AnalyticsStore::flush)
+}
+
+fn store() -> MsgResult<&'static AnalyticsStore> {
+    STORE.get().ok_or_else(|| {
+        BotError::NotFound(String::from(
+            "Interactions are not recorded, since `analytics_path` is not set!",
+        ))
+    })
+}
+
+impl AnalyticsStore {
+    /// The ids of one kind looked up most since `since` (unix seconds), with how often
+    /// - Only counts slash commands and components that succeeded, since autocomplete is typing
+    pub fn top_lookups(
+        &self,
+        lookup: Lookup,
+        since: u64,
+        limit: usize,
+    ) -> MsgResult<Vec<(i32, u64)>> {
+        // so the report includes what was just handled
+        self.flush()?;
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare_cached(
+                "SELECT lookup_id, COUNT(*) AS uses FROM interactions
+                 WHERE lookup_kind = ?1 AND at >= ?2 AND kind != 'autocomplete' AND outcome = 'ok'
+                 GROUP BY lookup_id ORDER BY uses DESC LIMIT ?3",
+            )
+            .map_err(BotError::internal)?;
+        let rows = stmt
+            .query_map(params![lookup.key(), since as i64, limit as i64], |row| {
+                Ok((row.get::<_, i32>(0)?, row.get::<_, i64>(1)? as u64))
+            })
+            .map_err(BotError::internal)?;
+        rows.collect::<Result<_, _>>().map_err(BotError::internal)
+    }
+
+    /// The commands used most since `since` (unix seconds), with how often
+    /// - Autocomplete is left out, since it fires on every keystroke
+    pub fn busiest_commands(&self, since: u64, limit: usize) -> MsgResult<Vec<(String, u64)>> {
+        self.flush()?;
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare_cached(
+                "SELECT command, COUNT(*) AS uses FROM interactions
+                 WHERE at >= ?1 AND kind != 'autocomplete'
+                 GROUP BY command ORDER BY uses DESC LIMIT ?2",
+            )
+            .map_err(BotError::internal)?;
+        let rows = stmt
+            .query_map(params![since as i64, limit as i64], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as u64))
+            })
+            .map_err(BotError::internal)?;
+        rows.collect::<Result<_, _>>().map_err(BotError::internal)
+    }
+}
+
+/// [`AnalyticsStore::top_lookups`] of the database at `analytics_path`
+pub fn top_lookups(lookup: Lookup, since: u64, limit: usize) -> MsgResult<Vec<(i32, u64)>> {
+    store()?.top_lookups(lookup, since, limit)
+}
+
+/// [`AnalyticsStore::busiest_commands`] of the database at `analytics_path`
+pub fn busiest_commands(since: u64, limit: usize) -> MsgResult<Vec<(String, u64)>> {
+    store()?.busiest_commands(since, limit)
+}
+
+/// Unix seconds `secs` ago
+pub fn since(secs: u64) -> u64 {
+    now_secs().saturating_sub(secs)
+}
@@ -0,0 +1,360 @@
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::{task, time};
+use tracing::{error, info, warn};
+
+use crate::error::{BotError, MsgResult};
+use crate::ids::{CdClientObjectsId, LUExplorer, LootMatrixObjectChances};
+use crate::interaction_command::decode_value;
+use crate::pager::{Pager, START_PAGE};
+use crate::scope::{self, Cancellation, InteractionScope};
+use crate::{data, CONFIG};
+
+pub const DEFAULT_PER_PAGE: usize = 25;
+pub const MAX_PER_PAGE: usize = 100;
+/// How long a connection gets to send its request, so an idle one is not kept open forever
+pub const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The lists `/item drop`, `/item unpack`, `/item reward`, `/item buy` and `/item earn` show, at `/objects/{id}/{endpoint}`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endpoint {
+    Drops,
+    Unpacks,
+    Rewards,
+    Vendors,
+    Achievements,
+}
+
+impl Endpoint {
+    pub const ALL: [Endpoint; 5] = [
+        Endpoint::Drops,
+        Endpoint::Unpacks,
+        Endpoint::Rewards,
+        Endpoint::Vendors,
+        Endpoint::Achievements,
+    ];
+
+    pub fn path(&self) -> &'static str {
+        match self {
+            Endpoint::Drops => "drops",
+            Endpoint::Unpacks => "unpacks",
+            Endpoint::Rewards => "rewards",
+            Endpoint::Vendors => "vendors",
+            Endpoint::Achievements => "achievements",
+        }
+    }
+
+    fn results(&self, object: CdClientObjectsId) -> MsgResult<Vec<Value>> {
+        Ok(match self {
+            Endpoint::Drops => chances(object.smashables_chances()?),
+            Endpoint::Unpacks => chances(object.packages_chances()?),
+            Endpoint::Rewards => chances(object.activities_chances()?),
+            Endpoint::Vendors => object
+                .vendors()?
+                .into_iter()
+                .map(|vendor| json!(ObjectRef::of(vendor)))
+                .collect(),
+            Endpoint::Achievements => object
+                .achievements()?
+                .into_iter()
+                .map(|achievement| {
+                    json!(ObjectRef {
+                        id: achievement.id(),
+                        name: achievement.req_name(),
+                        url: achievement.explorer_url(),
+                    })
+                })
+                .collect(),
+        })
+    }
+}
+
+#[derive(Serialize)]
+pub struct ObjectRef {
+    pub id: i32,
+    pub name: String,
+    pub url: String,
+}
+
+impl ObjectRef {
+    fn of(object: CdClientObjectsId) -> Self {
+        ObjectRef {
+            id: object.0,
+            name: object.req_name(),
+            url: object.explorer_url(),
+        }
+    }
+}
+
+/// One loot matrix and the objects it comes from, ex: the smashables that drop an item
+#[derive(Serialize)]
+struct Chance {
+    loot_matrix_index: i32,
+    /// From 0 to 1
+    chance: f64,
+    sources: Vec<ObjectRef>,
+}
+
+fn chances(entries: Vec<LootMatrixObjectChances>) -> Vec<Value> {
+    entries
+        .into_iter()
+        .map(|entry| {
+            json!(Chance {
+                loot_matrix_index: entry.lmi.0,
+                chance: entry.chance,
+                sources: entry.sources.into_iter().map(ObjectRef::of).collect(),
+            })
+        })
+        .collect()
+}
+
+#[derive(Serialize)]
+pub struct Page {
+    object: ObjectRef,
+    page: usize,
+    per_page: usize,
+    pages: usize,
+    total: usize,
+    results: Vec<Value>,
+}
+
+impl Page {
+    /// One page of what `object` has, or an error when `page` is past the last one
+    pub fn new(
+        object: ObjectRef,
+        results: Vec<Value>,
+        page: usize,
+        per_page: usize,
+    ) -> MsgResult<Self> {
+        let pager = Pager::new(results, page, per_page);
+        if !pager.has_page(page) {
+            Err(format!(
+                "`page` must be between 1 and {}",
+                pager.page_count()
+            ))?
+        }
+        Ok(Page {
+            object,
+            page: pager.current(),
+            per_page: pager.page_size(),
+            pages: pager.page_count(),
+            total: pager.total(),
+            results: pager
+                .this_page()
+                .into_iter()
+                .map(|(_, entry)| entry)
+                .collect(),
+        })
+    }
+}
+
+/// `GET /objects/{id}/{endpoint}?page=1&per_page=25&locale=en_US`
+#[derive(Debug)]
+pub struct Request {
+    pub object: i32,
+    pub endpoint: Endpoint,
+    pub page: usize,
+    pub per_page: usize,
+    pub locale: Option<String>,
+}
+
+impl Request {
+    /// - Anything but `/objects/{id}/{endpoint}` is not found, while a bad id or parameter is a bad request
+    pub fn parse(line: &str) -> MsgResult<Self> {
+        let mut words = line.split_whitespace();
+        let (Some("GET"), Some(target)) = (words.next(), words.next()) else {
+            Err(String::from("Only `GET` requests are served"))?
+        };
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let segments: Vec<_> = path.trim_matches('/').split('/').collect();
+        let [objects, id, endpoint] = segments.as_slice() else {
+            Err(BotError::NotFound(usage()))?
+        };
+        let endpoint = Endpoint::ALL
+            .into_iter()
+            .find(|candidate| candidate.path() == *endpoint);
+        let (true, Some(endpoint)) = (*objects == "objects", endpoint) else {
+            Err(BotError::NotFound(usage()))?
+        };
+        let object = id
+            .parse()
+            .map_err(|_| format!("`{id}` is not an object id"))?;
+
+        // ex: `locale=de%5FDE`, where a `+` is a space like in a form
+        let decode = |text: &str| {
+            decode_value(&text.replace('+', " "))
+                .ok_or_else(|| format!("`{text}` is not percent-encoded"))
+        };
+        let mut params = BTreeMap::new();
+        for param in query.split('&').filter(|param| !param.is_empty()) {
+            let (key, value) = param.split_once('=').unwrap_or((param, ""));
+            params.insert(decode(key)?, decode(value)?);
+        }
+        let mut number = |key: &str, default: usize| -> MsgResult<usize> {
+            match params.remove(key) {
+                Some(value) => Ok(value
+                    .parse()
+                    .map_err(|_| format!("`{key}` must be a whole number, not `{value}`"))?),
+                None => Ok(default),
+            }
+        };
+        let page = number("page", START_PAGE)?;
+        let per_page = number("per_page", DEFAULT_PER_PAGE)?;
+        let locale = params.remove("locale");
+        if let Some(key) = params.keys().next() {
+            Err(format!(
+                "Unknown parameter `{key}`, use `page`, `per_page` or `locale`"
+            ))?
+        }
+        if !(1..=MAX_PER_PAGE).contains(&per_page) {
+            Err(format!("`per_page` must be between 1 and {MAX_PER_PAGE}"))?
+        }
+
+        Ok(Request {
+            object,
+            endpoint,
+            page,
+            per_page,
+            locale,
+        })
+    }
+
+    /// - Runs inside a scope, so names come out in [`Self::locale`]
+    fn run(&self) -> MsgResult<Page> {
+        let object = CdClientObjectsId(self.object);
+        object.try_fetch()?;
+        let results = self.endpoint.results(object)?;
+        Page::new(ObjectRef::of(object), results, self.page, self.per_page)
+    }
+}
+
+fn usage() -> String {
+    let endpoints = Endpoint::ALL.map(|endpoint| endpoint.path()).join("|");
+    format!("Try `/objects/{{id}}/{{{endpoint}}}`", endpoint = endpoints)
+}
+
+/// Serves the lists behind the object commands as JSON at `GET /objects/{id}/{endpoint}`
+/// - Read only, so it is a bare HTTP/1.1 responder like [`crate::prometheus`]
+/// - Try it with `curl 'http://127.0.0.1:9186/objects/7415/drops?page=2'`
+pub async fn serve(addr: SocketAddr) {
+    let listener = match TcpListener::bind(addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            warn!("Could not serve the API at {addr}: {e}");
+            return;
+        }
+    };
+    info!("Serving the API at http://{addr}/objects/{{id}}/drops");
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(respond(stream));
+            }
+            Err(e) => warn!("Could not accept an API connection: {e}"),
+        }
+    }
+}
+
+/// The status and body served for a request line, ex: `GET /objects/7415/drops HTTP/1.1`
+pub async fn response(line: &str) -> (&'static str, Value) {
+    match Request::parse(line) {
+        Ok(request) => handle(request).await,
+        Err(error) => (status(&error), error_body(&error)),
+    }
+}
+
+/// The status an error is served with
+fn status(error: &BotError) -> &'static str {
+    match error {
+        BotError::NotFound(_) | BotError::MissingComponent(_) => "404 Not Found",
+        BotError::BadArgument(_) => "400 Bad Request",
+        BotError::DataInconsistency(_) | BotError::Internal { .. } => "500 Internal Server Error",
+    }
+}
+
+/// Like an error embed: the kind and message, except that an internal error only shows its id
+fn error_body(error: &BotError) -> Value {
+    match error {
+        BotError::Internal { id, .. } => {
+            json!({ "error": error.kind(), "id": id.to_string() })
+        }
+        other => json!({ "error": other.kind(), "message": other.message() }),
+    }
+}
+
+/// Resolves the locale and runs the request on the blocking pool, like an interaction handler
+async fn handle(request: Request) -> (&'static str, Value) {
+    let data = data::current();
+    let locale = match &request.locale {
+        Some(locale) => match data.match_locale(locale) {
+            Some(locale) => locale,
+            None => {
+                let error = BotError::BadArgument(format!(
+                    "`{locale}` is not a locale, try one of: {}",
+                    data.locale_names().join(", ")
+                ));
+                return (status(&error), error_body(&error));
+            }
+        },
+        None => CONFIG.locale.clone(),
+    };
+    drop(data);
+
+    let cancellation = Cancellation::default();
+    let scope = InteractionScope::offline(locale).with_cancellation(cancellation.clone());
+    let handler = task::spawn_blocking(move || scope::with_scope(scope, || request.run()).0);
+    let timeout = Duration::from_secs(CONFIG.handler_timeout_secs);
+    let result = match time::timeout(timeout, handler).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(why)) => Err(BotError::internal(format!("Handler panicked: {why}"))),
+        Err(_) => {
+            cancellation.cancel();
+            let body = json!({
+                "error": "timed_out",
+                "message": format!("This took longer than {} seconds", timeout.as_secs()),
+            });
+            return ("503 Service Unavailable", body);
+        }
+    };
+    match result {
+        Ok(page) => ("200 OK", json!(page)),
+        Err(error) => {
+            if let BotError::Internal { id, message } = &error {
+                error!(correlation_id = %id, error = message, "API request failed");
+            }
+            (status(&error), error_body(&error))
+        }
+    }
+}
+
+async fn respond(mut stream: TcpStream) {
+    let mut request = [0; 1024];
+    let Ok(Ok(len)) = time::timeout(READ_TIMEOUT, stream.read(&mut request)).await else {
+        return;
+    };
+    let request = String::from_utf8_lossy(&request[..len]);
+    let line = request.lines().next().unwrap_or_default().to_string();
+
+    let start = Instant::now();
+    let (status, body) = response(&line).await;
+    info!(
+        request = line,
+        status,
+        latency_ms = start.elapsed().as_millis() as u64,
+        "API request handled"
+    );
+
+    let body = body.to_string();
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nAccess-Control-Allow-Origin: *\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    _ = stream.write_all(response.as_bytes()).await;
+    _ = stream.shutdown().await;
+}
@@ -0,0 +1,224 @@
+/*
+* - `#[derive(CommandArguments)]` writes the `Arguments` of a command from one struct: its
+*   [`ToCustomId`], both `TryFrom`s and its slash command options (see `lu_discord_bot_macros`)
+* - The command then only returns [`CommandArguments::options`] and
+*   [`CommandArguments::autocomplete`]
+* - Fields can be numbers, `bool`, text, users or an enum with `#[derive(OptionChoice)]`
+*   (see [`OptionValue`]), and slash commands and custom ids parse them the same way
+*/
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serenity::all::{
+    AutocompleteChoice, AutocompleteOption, CommandOptionType, CreateCommandOption, ResolvedOption,
+    ResolvedValue, UserId,
+};
+
+use crate::error::{BotError, MsgResult};
+use crate::interaction_command::{CustomIdOptions, ToCustomId};
+
+pub use lu_discord_bot_macros::{CommandArguments, OptionChoice};
+
+/// What `#[derive(CommandArguments)]` implements besides [`ToCustomId`] and the `TryFrom`s
+pub trait CommandArguments:
+    ToCustomId
+    + for<'a> TryFrom<&'a CustomIdOptions, Error = BotError>
+    + for<'a> TryFrom<&'a [ResolvedOption<'a>], Error = BotError>
+{
+    /// The slash command options of the `#[option]` fields, in order
+    fn options() -> Vec<CreateCommandOption>;
+
+    /// Suggestions for the `#[option(autocomplete = "..")]` field being typed in
+    fn autocomplete(option: AutocompleteOption<'_>) -> Option<Vec<AutocompleteChoice>>;
+}
+
+/// A type that an `#[option]` field can have
+/// - It is written to and read from custom ids with [`Display`] and [`FromStr`]
+pub trait OptionValue: FromStr + Display {
+    const KIND: CommandOptionType;
+    /// What the user was asked for, ex: "a whole number"
+    const EXPECTED: &'static str;
+
+    fn from_resolved(value: &ResolvedValue) -> Option<Self>;
+
+    /// The value with the longest custom id (see [`ToCustomId::longest`])
+    fn longest() -> Self;
+
+    /// The static choices Discord offers instead of free input
+    fn add_choices(option: CreateCommandOption) -> CreateCommandOption {
+        option
+    }
+}
+
+/// A fieldless enum that an `#[option]` offers as static choices, see `#[derive(OptionChoice)]`
+pub trait OptionChoice: Copy + FromStr + Display + 'static {
+    const ALL: &'static [Self];
+
+    /// The value of the choice in Discord and in custom ids
+    fn key(&self) -> &'static str;
+
+    /// What Discord shows for the choice
+    fn label(&self) -> &'static str;
+}
+
+impl<T: OptionChoice> OptionValue for T {
+    const KIND: CommandOptionType = CommandOptionType::String;
+    const EXPECTED: &'static str = "one of the choices";
+
+    fn from_resolved(value: &ResolvedValue) -> Option<Self> {
+        match value {
+            ResolvedValue::String(value) => value.parse().ok(),
+            _ => None,
+        }
+    }
+
+    fn longest() -> Self {
+        *T::ALL
+            .iter()
+            .max_by_key(|choice| choice.key().len())
+            .expect("`#[derive(OptionChoice)]` needs a choice")
+    }
+
+    fn add_choices(option: CreateCommandOption) -> CreateCommandOption {
+        T::ALL.iter().fold(option, |option, choice| {
+            option.add_string_choice(choice.label(), choice.key())
+        })
+    }
+}
+
+macro_rules! whole_number {
+    ($($ty:ty: $longest:expr),*) => {
+        $(
+            impl OptionValue for $ty {
+                const KIND: CommandOptionType = CommandOptionType::Integer;
+                const EXPECTED: &'static str = "a whole number";
+
+                fn from_resolved(value: &ResolvedValue) -> Option<Self> {
+                    match value {
+                        ResolvedValue::Integer(value) => (*value).try_into().ok(),
+                        _ => None,
+                    }
+                }
+
+                fn longest() -> Self {
+                    $longest
+                }
+            }
+        )*
+    };
+}
+
+whole_number!(i32: i32::MIN, i64: i64::MIN, u32: u32::MAX, u64: u64::MAX, usize: usize::MAX);
+
+/// - Any number has a long custom id, so bound the field with `max` and set its `longest`
+impl OptionValue for f64 {
+    const KIND: CommandOptionType = CommandOptionType::Number;
+    const EXPECTED: &'static str = "a number";
+
+    fn from_resolved(value: &ResolvedValue) -> Option<Self> {
+        match value {
+            ResolvedValue::Number(value) => Some(*value),
+            ResolvedValue::Integer(value) => Some(*value as f64),
+            _ => None,
+        }
+    }
+
+    fn longest() -> Self {
+        f64::MIN
+    }
+}
+
+impl OptionValue for bool {
+    const KIND: CommandOptionType = CommandOptionType::Boolean;
+    const EXPECTED: &'static str = "true or false";
+
+    fn from_resolved(value: &ResolvedValue) -> Option<Self> {
+        match value {
+            ResolvedValue::Boolean(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn longest() -> Self {
+        false
+    }
+}
+
+impl OptionValue for UserId {
+    const KIND: CommandOptionType = CommandOptionType::User;
+    const EXPECTED: &'static str = "a user";
+
+    fn from_resolved(value: &ResolvedValue) -> Option<Self> {
+        match value {
+            ResolvedValue::User(user, _) => Some(user.id),
+            _ => None,
+        }
+    }
+
+    fn longest() -> Self {
+        UserId::new(u64::MAX)
+    }
+}
+
+/// - Text is percent-encoded in custom ids where it holds `%`, `&`, `=` or `~`, so set the field's
+///   `longest` with room for that
+impl OptionValue for String {
+    const KIND: CommandOptionType = CommandOptionType::String;
+    const EXPECTED: &'static str = "text";
+
+    fn from_resolved(value: &ResolvedValue) -> Option<Self> {
+        match value {
+            ResolvedValue::String(value) => Some(value.to_string()),
+            _ => None,
+        }
+    }
+
+    fn longest() -> Self {
+        String::new()
+    }
+}
+
+/// Lets Discord only accept numbers from `min` to `max`
+/// - Discord has no bounds for other kinds of options, so they are left as they are
+/// - serenity only takes whole numbers from 0 up, which `#[derive(CommandArguments)]` checks when
+///   it compiles
+pub fn bounded<T: OptionValue>(
+    option: CreateCommandOption,
+    min: Option<f64>,
+    max: Option<f64>,
+) -> CreateCommandOption {
+    match T::KIND {
+        CommandOptionType::Integer => {
+            let option = match min {
+                Some(min) => option.min_int_value(min as u64),
+                None => option,
+            };
+            match max {
+                Some(max) => option.max_int_value(max as u64),
+                None => option,
+            }
+        }
+        CommandOptionType::Number => {
+            let option = match min {
+                Some(min) => option.min_number_value(min),
+                None => option,
+            };
+            match max {
+                Some(max) => option.max_number_value(max),
+                None => option,
+            }
+        }
+        _ => option,
+    }
+}
+
+/// The option `name` of a slash command, or `None` when the user left it out
+pub fn resolved<T: OptionValue>(options: &[ResolvedOption], name: &str) -> MsgResult<Option<T>> {
+    let Some(option) = options.iter().find(|option| option.name == name) else {
+        return Ok(None);
+    };
+    T::from_resolved(&option.value)
+        .map(Some)
+        .ok_or_else(|| BotError::BadArgument(format!("Expected `{name}` to be {}", T::EXPECTED)))
+}
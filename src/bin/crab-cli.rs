@@ -0,0 +1,305 @@
+//! Runs a command against the data files and prints what the bot would send, without Discord
+//!
+//! ```sh
+//! crab-cli drop 7415 --page 2
+//! crab-cli --locale de_DE --format json mission --mission 1727
+//! crab-cli --custom-id 'u/drop.1:item~1vb&page=2&size=5'
+//! ```
+
+use std::collections::BTreeMap;
+use std::env;
+use std::process::ExitCode;
+
+use lu_discord_bot::bot_config::ENV_PREFIX;
+use lu_discord_bot::commands::COMMANDS;
+use lu_discord_bot::error::BotError;
+use lu_discord_bot::interaction_command::{CustomIdInteractionType, CustomIdOptions};
+use lu_discord_bot::pager::{DEFAULT_PAGE_SIZE, START_PAGE};
+use lu_discord_bot::registry::RegisteredCommand;
+use lu_discord_bot::render::{
+    DiscordRenderer, JsonRenderer, MarkdownRenderer, Renderer, TextRenderer,
+};
+use lu_discord_bot::response::Response;
+use lu_discord_bot::scope::{self, InteractionScope};
+use lu_discord_bot::{data, CONFIG};
+use serde_json::{json, Value};
+
+const USAGE: &str = "\
+Usage:
+    crab-cli [FLAGS] <command> [VALUE]... [--<key> <value>]...
+    crab-cli [FLAGS] --custom-id <custom id>
+    crab-cli --list
+
+Values are given to the options of the command in order, ex: `drop 7415` is `drop --item 7415`.
+Keys are those of the command's custom ids, so `--page` works on every paged command
+and `--size` (5, 10 or 15) on those with a page size menu.
+
+Flags:
+    --cdclient-path <path>    cdclient.sqlite to read (default: `cdclient_path` of the config)
+    --locale-path <path>      locale.xml to read (default: `locale_path` of the config)
+    --locale <locale>         Locale to show, ex: `de_DE` (default: `locale` of the config)
+    --format <format>         `text` (default), `markdown`, `json` for the response itself,
+                              or `discord` for the embed as Discord receives it
+    --list                    List every command and its options
+    -h, --help                Print this message";
+
+/// What to run, parsed from the command line
+enum Target {
+    List,
+    CustomId(String),
+    Command {
+        name: String,
+        values: Vec<String>,
+        keys: BTreeMap<String, String>,
+    },
+}
+
+struct Cli {
+    target: Target,
+    locale: Option<String>,
+    format: Format,
+}
+
+impl Cli {
+    /// - Sets the `CRAB_FORCE_*` path overrides, so call it before [`CONFIG`] is used
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut locale = None;
+        let mut format = Format::Text;
+        let mut custom_id = None;
+        let mut list = false;
+        let mut name = None;
+        let mut values = vec![];
+        let mut keys = BTreeMap::new();
+
+        while let Some(arg) = args.next() {
+            let Some(flag) = arg.strip_prefix("--") else {
+                match arg.as_str() {
+                    "-h" => return Ok(None),
+                    _ if name.is_none() => name = Some(arg),
+                    _ => values.push(arg),
+                }
+                continue;
+            };
+            let (flag, inline) = match flag.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (flag.to_string(), None),
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("`--{flag}` needs a value"))
+            };
+            match flag.as_str() {
+                "help" => return Ok(None),
+                "format" if name.is_none() => format = Format::parse(&value()?)?,
+                "list" => list = true,
+                "locale" if name.is_none() => locale = Some(value()?),
+                "custom-id" if name.is_none() => custom_id = Some(value()?),
+                "cdclient-path" if name.is_none() => {
+                    env::set_var(format!("{ENV_PREFIX}CDCLIENT_PATH"), value()?)
+                }
+                "locale-path" if name.is_none() => {
+                    env::set_var(format!("{ENV_PREFIX}LOCALE_PATH"), value()?)
+                }
+                _ if name.is_some() => {
+                    let value = value()?;
+                    keys.insert(flag, value);
+                }
+                _ => Err(format!("Unknown flag `--{flag}`"))?,
+            }
+        }
+
+        let target = match (list, custom_id, name) {
+            (true, _, _) => Target::List,
+            (false, Some(custom_id), None) => Target::CustomId(custom_id),
+            (false, Some(_), Some(name)) => Err(format!(
+                "Give either `--custom-id` or a command, not both (got `{name}`)"
+            ))?,
+            (false, None, Some(name)) => Target::Command { name, values, keys },
+            (false, None, None) => return Ok(None),
+        };
+        Ok(Some(Cli {
+            target,
+            locale,
+            format,
+        }))
+    }
+}
+
+/// The names and descriptions of a command's slash command options, in order
+fn command_options(command: &RegisteredCommand) -> Vec<(String, String)> {
+    let registered = serde_json::to_value((command.register)()).unwrap_or_default();
+    let Some(options) = registered["options"].as_array() else {
+        return vec![];
+    };
+    options
+        .iter()
+        .map(|option| {
+            let text = |key: &str| option[key].as_str().unwrap_or_default().to_string();
+            (text("name"), text("description"))
+        })
+        .collect()
+}
+
+/// Builds the custom id the bot would have sent for these values and keys
+/// - Paged commands start at [`START_PAGE`] and [`DEFAULT_PAGE_SIZE`] like their slash command
+///   does, so `page` and `size` are optional
+fn custom_id_options(
+    command: &RegisteredCommand,
+    values: Vec<String>,
+    mut keys: BTreeMap<String, String>,
+) -> Result<CustomIdOptions, String> {
+    let options = command_options(command);
+    if values.len() > options.len() {
+        Err(format!(
+            "`{}` takes at most {} value(s), but got {}",
+            command.name,
+            options.len(),
+            values.len()
+        ))?
+    }
+    for ((name, _), value) in options.into_iter().zip(values) {
+        if keys.insert(name.clone(), value).is_some() {
+            Err(format!(
+                "`{name}` is given both as a value and as `--{name}`"
+            ))?
+        }
+    }
+    keys.entry(String::from("page"))
+        .or_insert_with(|| START_PAGE.to_string());
+    keys.entry(String::from("size"))
+        .or_insert_with(|| DEFAULT_PAGE_SIZE.to_string());
+    Ok(CustomIdOptions {
+        interaction: CustomIdInteractionType::Reply,
+        cmd: command.name.to_string(),
+        version: command.version,
+        map: keys,
+    })
+}
+
+fn list_commands() {
+    for command in COMMANDS.iter() {
+        let registered = serde_json::to_value((command.register)()).unwrap_or_default();
+        println!(
+            "{}: {}",
+            command.name,
+            registered["description"].as_str().unwrap_or_default()
+        );
+        for (name, description) in command_options(command) {
+            println!("    --{name}: {description}");
+        }
+    }
+}
+
+/// How the response is printed, see [`lu_discord_bot::render`]
+#[derive(Clone, Copy)]
+enum Format {
+    Text,
+    Markdown,
+    Json,
+    /// The embed and components as Discord receives them
+    Discord,
+}
+
+impl Format {
+    fn parse(format: &str) -> Result<Self, String> {
+        Ok(match format {
+            "text" => Format::Text,
+            "markdown" => Format::Markdown,
+            "json" => Format::Json,
+            "discord" => Format::Discord,
+            other => Err(format!(
+                "`{other}` is not one of `text`, `markdown`, `json` or `discord`"
+            ))?,
+        })
+    }
+
+    fn render(&self, response: &Response) -> String {
+        let pretty = |value: Value| serde_json::to_string_pretty(&value).unwrap_or_default();
+        match self {
+            Format::Text => TextRenderer.render(response),
+            Format::Markdown => MarkdownRenderer.render(response),
+            Format::Json => pretty(JsonRenderer.render(response)),
+            Format::Discord => {
+                let (embed, components) = DiscordRenderer.render(response);
+                pretty(json!({ "embeds": [embed], "components": components }))
+            }
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<bool, String> {
+    let options = match cli.target {
+        Target::List => {
+            list_commands();
+            return Ok(true);
+        }
+        Target::CustomId(custom_id) => {
+            let options = CustomIdOptions::from_custom_id(&custom_id).map_err(|e| e.to_string())?;
+            // the bot replies that such a message is outdated instead of running it
+            COMMANDS
+                .check_custom_id(&options)
+                .map_err(|e| format!("`{custom_id}` is outdated: {e}"))?;
+            options
+        }
+        Target::Command { name, values, keys } => {
+            let command = COMMANDS
+                .get(&name)
+                .ok_or_else(|| format!("`{name}` is not a command, see `crab-cli --list`"))?;
+            custom_id_options(command, values, keys)?
+        }
+    };
+    let command = COMMANDS
+        .get(&options.cmd)
+        .ok_or_else(|| format!("`{}` is not a command, see `crab-cli --list`", options.cmd))?;
+
+    let data = data::current();
+    let locale = match cli.locale {
+        Some(locale) => data.match_locale(&locale).ok_or_else(|| {
+            format!(
+                "`{locale}` is not in `{}`, try one of: {}",
+                CONFIG.locale_path.display(),
+                data.locale_names().join(", ")
+            )
+        })?,
+        None => CONFIG.locale.clone(),
+    };
+    drop(data);
+
+    let (result, _) = scope::with_scope(InteractionScope::offline(locale), || {
+        (command.run)(&options)
+    });
+    let ok = result.is_ok();
+    let response = result.unwrap_or_else(|error| {
+        if let BotError::Internal { .. } = error {
+            eprintln!("{error}");
+        }
+        CONFIG.error_embed(&error)
+    });
+    println!("{}", cli.format.render(&response));
+    Ok(ok)
+}
+
+fn main() -> ExitCode {
+    let cli = match Cli::parse(env::args().skip(1)) {
+        Ok(Some(cli)) => cli,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("{e}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match run(cli) {
+        Ok(true) => ExitCode::SUCCESS,
+        // the error embed was printed like any other response
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
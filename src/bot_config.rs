@@ -1,5 +1,30 @@
-use serenity::all::{CreateActionRow, CreateEmbed, CreateEmbedAuthor, CreateEmbedFooter};
+use std::env;
+use std::fs;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
 
+use serde::Deserialize;
+
+use crate::error::BotError;
+use crate::ratelimit::RateLimits;
+use crate::registration::RegistrationMode;
+use crate::response::{Author, Footer, Response};
+
+/// Environment variable holding the path of the config file
+pub const CONFIG_PATH_VAR: &str = "CRAB_FORCE_CONFIG";
+/// Config file that is read when [`CONFIG_PATH_VAR`] is not set (it is optional)
+pub const DEFAULT_CONFIG_PATH: &str = "config.toml";
+/// Environment variable holding the bot token, which only the bot itself needs
+pub const TOKEN_VAR: &str = "DISCORD_TOKEN";
+/// Every field can be overridden with `CRAB_FORCE_{FIELD}`, ex: `CRAB_FORCE_LOCALE=de_DE`
+pub const ENV_PREFIX: &str = "CRAB_FORCE_";
+/// Leaves time to defer before Discord's 3 second deadline
+pub const MAX_DEFER_AFTER_MS: u64 = 2500;
+/// Interaction tokens expire after 15 minutes
+pub const MAX_HANDLER_TIMEOUT_SECS: u64 = 900;
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct BotConfig {
     pub locale: String,
     pub explorer_url: String,
@@ -7,9 +32,46 @@ pub struct BotConfig {
     pub author_url: Option<String>,
     pub author_name: Option<String>,
     pub author_icon: Option<String>,
+    /// Hex color, ex: `#1a87e8`
     pub color: Option<String>,
     pub footer_message: Option<String>,
     pub footer_icon: Option<String>,
+    pub cdclient_path: PathBuf,
+    pub locale_path: PathBuf,
+    /// SQLite file holding per-guild settings, created if it does not exist
+    pub settings_path: PathBuf,
+    /// SQLite file every interaction is recorded to for `/stats`; empty to record nothing
+    pub analytics_path: Option<PathBuf>,
+    /// How commands are registered (see [`RegistrationMode`])
+    pub registration: RegistrationMode,
+    /// Guilds to register commands in, unless [`Self::registration`] is `global`
+    pub guild_ids: Vec<u64>,
+    /// Users allowed to run owner-only commands like `/reload`
+    pub owner_ids: Vec<u64>,
+    /// Reload the data files when they change on disk
+    pub watch_data_files: bool,
+    pub watch_interval_secs: u64,
+    /// Log JSON lines instead of human-readable ones
+    pub log_json: bool,
+    /// Where to serve Prometheus metrics, ex: `127.0.0.1:9185` (needs `--features prometheus`)
+    pub metrics_addr: Option<SocketAddr>,
+    /// Where to serve the object queries as JSON, ex: `127.0.0.1:9186` (needs `--features api`)
+    pub api_addr: Option<SocketAddr>,
+    /// Reply with "thinking…" when a command takes longer than this, then edit the result in
+    pub defer_after_ms: u64,
+    /// Cancel a command that takes longer than this
+    pub handler_timeout_secs: u64,
+    /// Token buckets per user, guild and command (see [`RateLimits`])
+    pub rate_limits: RateLimits,
+    /// Ask Discord for the content of messages, so servers can turn on inline lookups
+    /// - Enable the Message Content intent of the bot in the Discord developer portal first
+    pub message_content_intent: bool,
+    /// Reply to `[[Item Name]]` in chat, which each server turns on with `/settings`
+    #[serde(skip)]
+    pub inline_lookups: bool,
+    /// The channels [`Self::inline_lookups`] are in, or every channel when empty
+    #[serde(skip)]
+    pub inline_channels: Vec<u64>,
 }
 
 fn join_paths(front: &str, back: &str) -> String {
@@ -18,7 +80,41 @@ fn join_paths(front: &str, back: &str) -> String {
     format!("{}/{}", front, back)
 }
 
+/// Parses `#1a87e8` or `1a87e8`
+fn parse_hex_color(color: &str) -> Option<(u8, u8, u8)> {
+    let hex = color.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |idx: usize| u8::from_str_radix(&hex[idx..idx + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+fn parse_ids(field: &str, value: &str, errors: &mut Vec<String>) -> Vec<u64> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .filter_map(|id| {
+            id.parse()
+                .map_err(|_| errors.push(format!("`{field}`: `{id}` is not a valid id")))
+                .ok()
+        })
+        .collect()
+}
+
+fn is_http_url(url: &str) -> bool {
+    url.starts_with("https://") || url.starts_with("http://")
+}
+
 impl BotConfig {
+    /// Whether `[[Item Name]]` in `channel_id` is replied to (see [`Self::inline_lookups`])
+    pub fn looks_up_inline_in(&self, channel_id: u64) -> bool {
+        self.message_content_intent
+            && self.inline_lookups
+            && (self.inline_channels.is_empty() || self.inline_channels.contains(&channel_id))
+    }
+
     pub fn explorer_uri(&self, path: impl AsRef<str>) -> String {
         join_paths(&self.explorer_url, path.as_ref())
     }
@@ -29,33 +125,281 @@ impl BotConfig {
             .to_lowercase()
             .replace(".dds", ".png")
     }
+
+    pub fn embed_color(&self) -> Option<(u8, u8, u8)> {
+        parse_hex_color(self.color.as_ref()?)
+    }
+
+    pub fn is_owner(&self, user_id: u64) -> bool {
+        self.owner_ids.contains(&user_id)
+    }
 }
 
 impl BotConfig {
-    pub fn default_embed(&self) -> CreateEmbed {
-        let author = self.author_name.as_ref().map(|name| {
-            let mut author = CreateEmbedAuthor::new(name);
-            if let Some(ref author_icon) = self.author_icon {
-                author = author.icon_url(author_icon);
+    /// - Reads the config file at [`CONFIG_PATH_VAR`] (or [`DEFAULT_CONFIG_PATH`] if it exists)
+    /// - Applies the `CRAB_FORCE_*` environment overrides
+    /// - Validates the result and returns every problem found, not just the first one
+    pub fn load() -> Result<Self, Vec<String>> {
+        let (config, errors) = Self::read();
+        if errors.is_empty() {
+            Ok(config)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// [`Self::load`] for the bot, which also needs its token (see [`Self::validate_bot`])
+    /// - Returns the config with the token, or the problems of both at once
+    pub fn load_bot() -> Result<(Self, String), Vec<String>> {
+        let (config, mut errors) = Self::read();
+        let token = config.validate_bot(&mut errors);
+        match token {
+            Some(token) if errors.is_empty() => Ok((config, token)),
+            _ => Err(errors),
+        }
+    }
+
+    /// The config with every problem found, not just the first one
+    fn read() -> (Self, Vec<String>) {
+        let mut errors = vec![];
+
+        let mut config = match env::var(CONFIG_PATH_VAR) {
+            Ok(path) => Self::from_file(Path::new(&path)).unwrap_or_else(|e| {
+                errors.push(e);
+                Self::default()
+            }),
+            Err(_) if Path::new(DEFAULT_CONFIG_PATH).exists() => {
+                Self::from_file(Path::new(DEFAULT_CONFIG_PATH)).unwrap_or_else(|e| {
+                    errors.push(e);
+                    Self::default()
+                })
             }
-            if let Some(ref author_url) = self.author_url {
-                author = author.url(author_url);
+            Err(_) => Self::default(),
+        };
+
+        config.apply_env_overrides(&mut errors);
+        config.validate(&mut errors);
+        (config, errors)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read config file `{}`: {e}", path.display()))?;
+        toml::from_str(&contents)
+            .map_err(|e| format!("Could not parse config file `{}`: {e}", path.display()))
+    }
+
+    fn apply_env_overrides(&mut self, errors: &mut Vec<String>) {
+        let var = |name: &str| env::var(format!("{ENV_PREFIX}{name}")).ok();
+        // an empty value unsets an optional field
+        let optional = |name: &str| var(name).map(|value| (!value.is_empty()).then_some(value));
+
+        if let Some(value) = var("LOCALE") {
+            self.locale = value;
+        }
+        if let Some(value) = var("EXPLORER_URL") {
+            self.explorer_url = value;
+        }
+        if let Some(value) = var("EXPLORER_RES_URL") {
+            self.explorer_res_url = value;
+        }
+        if let Some(value) = optional("AUTHOR_URL") {
+            self.author_url = value;
+        }
+        if let Some(value) = optional("AUTHOR_NAME") {
+            self.author_name = value;
+        }
+        if let Some(value) = optional("AUTHOR_ICON") {
+            self.author_icon = value;
+        }
+        if let Some(value) = optional("COLOR") {
+            self.color = value;
+        }
+        if let Some(value) = optional("FOOTER_MESSAGE") {
+            self.footer_message = value;
+        }
+        if let Some(value) = optional("FOOTER_ICON") {
+            self.footer_icon = value;
+        }
+        if let Some(value) = var("CDCLIENT_PATH") {
+            self.cdclient_path = PathBuf::from(value);
+        }
+        if let Some(value) = var("LOCALE_PATH") {
+            self.locale_path = PathBuf::from(value);
+        }
+
+        if let Some(value) = var("SETTINGS_PATH") {
+            self.settings_path = PathBuf::from(value);
+        }
+        if let Some(value) = optional("ANALYTICS_PATH") {
+            self.analytics_path = value.map(PathBuf::from);
+        }
+        if let Some(value) = var("REGISTRATION") {
+            match RegistrationMode::parse(&value) {
+                Some(mode) => self.registration = mode,
+                None => errors.push(format!(
+                    "`registration`: `{value}` is not one of `guilds`, `global` or `dev`"
+                )),
             }
-            author
-        });
-        let footer = self.footer_message.as_ref().map(|msg| {
-            let mut footer = CreateEmbedFooter::new(msg);
-            if let Some(ref icon_url) = self.footer_icon {
-                footer = footer.icon_url(icon_url);
+        }
+        // `GUILD_ID` is what the bot originally read, so it is still honored
+        let guild_ids = var("GUILD_IDS").or_else(|| env::var("GUILD_ID").ok());
+        if let Some(value) = guild_ids {
+            self.guild_ids = parse_ids("guild_ids", &value, errors);
+        }
+        if let Some(value) = var("OWNER_IDS") {
+            self.owner_ids = parse_ids("owner_ids", &value, errors);
+        }
+
+        if let Some(value) = var("WATCH_DATA_FILES") {
+            match value.parse() {
+                Ok(watch) => self.watch_data_files = watch,
+                Err(_) => errors.push(format!("`watch_data_files`: `{value}` is not a bool")),
             }
-            footer
+        }
+        if let Some(value) = var("LOG_JSON") {
+            match value.parse() {
+                Ok(json) => self.log_json = json,
+                Err(_) => errors.push(format!("`log_json`: `{value}` is not a bool")),
+            }
+        }
+        if let Some(value) = optional("METRICS_ADDR") {
+            match value.map(|addr| addr.parse()).transpose() {
+                Ok(addr) => self.metrics_addr = addr,
+                Err(_) => errors.push(String::from(
+                    "`metrics_addr` must be an address like `127.0.0.1:9185`",
+                )),
+            }
+        }
+        if let Some(value) = optional("API_ADDR") {
+            match value.map(|addr| addr.parse()).transpose() {
+                Ok(addr) => self.api_addr = addr,
+                Err(_) => errors.push(String::from(
+                    "`api_addr` must be an address like `127.0.0.1:9186`",
+                )),
+            }
+        }
+        if let Some(value) = var("WATCH_INTERVAL_SECS") {
+            match value.parse() {
+                Ok(secs) => self.watch_interval_secs = secs,
+                Err(_) => errors.push(format!("`watch_interval_secs`: `{value}` is not a number")),
+            }
+        }
+        if let Some(value) = var("DEFER_AFTER_MS") {
+            match value.parse() {
+                Ok(ms) => self.defer_after_ms = ms,
+                Err(_) => errors.push(format!("`defer_after_ms`: `{value}` is not a number")),
+            }
+        }
+        if let Some(value) = var("RATE_LIMITS_ENABLED") {
+            match value.parse() {
+                Ok(enabled) => self.rate_limits.enabled = enabled,
+                Err(_) => errors.push(format!("`rate_limits.enabled`: `{value}` is not a bool")),
+            }
+        }
+        if let Some(value) = var("MESSAGE_CONTENT_INTENT") {
+            match value.parse() {
+                Ok(intent) => self.message_content_intent = intent,
+                Err(_) => errors.push(format!("`message_content_intent`: `{value}` is not a bool")),
+            }
+        }
+        if let Some(value) = var("HANDLER_TIMEOUT_SECS") {
+            match value.parse() {
+                Ok(secs) => self.handler_timeout_secs = secs,
+                Err(_) => errors.push(format!("`handler_timeout_secs`: `{value}` is not a number")),
+            }
+        }
+    }
+
+    /// What only the bot needs: its token in [`TOKEN_VAR`] and guilds to register commands in
+    /// - Returns the token if it is set
+    pub fn validate_bot(&self, errors: &mut Vec<String>) -> Option<String> {
+        if self.registration.needs_guild_ids() && self.guild_ids.is_empty() {
+            errors.push(String::from(
+                "`guild_ids`: at least one guild id is required unless `registration` is `global`",
+            ));
+        }
+        let token = env::var(TOKEN_VAR)
+            .ok()
+            .filter(|token| !token.trim().is_empty());
+        if token.is_none() {
+            errors.push(format!("`{TOKEN_VAR}` must be set in the environment"));
+        }
+        token
+    }
+
+    fn validate(&self, errors: &mut Vec<String>) {
+        if self.locale.trim().is_empty() {
+            errors.push(String::from("`locale` must not be empty"));
+        }
+
+        let urls = [
+            ("explorer_url", Some(&self.explorer_url)),
+            ("explorer_res_url", Some(&self.explorer_res_url)),
+            ("author_url", self.author_url.as_ref()),
+            ("author_icon", self.author_icon.as_ref()),
+            ("footer_icon", self.footer_icon.as_ref()),
+        ];
+        for (field, url) in urls {
+            if let Some(url) = url.filter(|url| !is_http_url(url)) {
+                errors.push(format!("`{field}`: `{url}` is not an http(s) url"));
+            }
+        }
+
+        if let Some(color) = self.color.as_ref() {
+            if parse_hex_color(color).is_none() {
+                errors.push(format!(
+                    "`color`: `{color}` is not a hex color like `#1a87e8`"
+                ));
+            }
+        }
+
+        let files = [
+            ("cdclient_path", &self.cdclient_path),
+            ("locale_path", &self.locale_path),
+        ];
+        for (field, path) in files {
+            if !path.is_file() {
+                errors.push(format!("`{field}`: `{}` is not a file", path.display()));
+            }
+        }
+
+        if self.watch_data_files && self.watch_interval_secs == 0 {
+            errors.push(String::from("`watch_interval_secs` must be at least 1"));
+        }
+
+        // Discord drops interactions that are not answered within 3 seconds
+        if self.defer_after_ms > MAX_DEFER_AFTER_MS {
+            errors.push(format!(
+                "`defer_after_ms` must be at most {MAX_DEFER_AFTER_MS}"
+            ));
+        }
+        // and a deferred response can only be edited for 15 minutes
+        if !(1..=MAX_HANDLER_TIMEOUT_SECS).contains(&self.handler_timeout_secs) {
+            errors.push(format!(
+                "`handler_timeout_secs` must be between 1 and {MAX_HANDLER_TIMEOUT_SECS}"
+            ));
+        }
+
+        self.rate_limits.validate(errors);
+    }
+}
+
+impl BotConfig {
+    pub fn default_embed(&self) -> Response {
+        let author = self.author_name.as_ref().map(|name| Author {
+            name: name.clone(),
+            url: self.author_url.clone(),
+            icon_url: self.author_icon.clone(),
+        });
+        let footer = self.footer_message.as_ref().map(|msg| Footer {
+            text: msg.clone(),
+            icon_url: self.footer_icon.clone(),
         });
-        let mut embed = CreateEmbed::new();
-        // embed = embed.color((0x42, 0xb9, 0xf5));
-        embed = embed.color((0x1a, 0x87, 0xe8));
-        // if let Some(color) = self.color {
-        //     embed = embed.color(color);
-        // }
+        let mut embed = Response::new();
+        if let Some(color) = self.embed_color() {
+            embed = embed.color(color);
+        }
         if let Some(author) = author {
             embed = embed.author(author);
         }
@@ -65,18 +409,32 @@ impl BotConfig {
         embed
     }
 
-    pub fn error_embed(
-        &self,
-        msg: impl Into<String>,
-    ) -> (CreateEmbed, Option<Vec<CreateActionRow>>) {
-        let embed = self
-            .default_embed()
-            .title("Error")
-            .description(msg)
-            .color((0xff, 0x00, 0x00));
-        // let response = CreateInteractionResponseMessage::new().embed(embed);
-        // response
-        (embed, None)
+    /// How the user sees an error; each kind gets its own title and hint (see [`BotError`])
+    pub fn error_embed(&self, error: &BotError) -> Response {
+        match error {
+            BotError::NotFound(msg) => self.failure_embed("Not Found", msg),
+            BotError::MissingComponent(msg) => self.failure_embed(
+                "Not Applicable",
+                format!("{msg}\nThis command does not apply to it."),
+            ),
+            BotError::BadArgument(msg) => self.failure_embed("Invalid Input", msg),
+            BotError::DataInconsistency(msg) => self.failure_embed(
+                "Incomplete Data",
+                format!("{msg}\nThe game data refers to something it does not contain."),
+            ),
+            BotError::Internal { id, .. } => self.failure_embed(
+                "Something Went Wrong",
+                format!("This is a bug. If it keeps happening, report it with the id `{id}`."),
+            ),
+        }
+    }
+
+    /// A red embed, for failures that are not a [`BotError`], ex: a timeout
+    pub fn failure_embed(&self, title: &str, description: impl Into<String>) -> Response {
+        self.default_embed()
+            .title(title)
+            .description(description)
+            .color((0xff, 0x00, 0x00))
     }
 }
 
@@ -90,10 +448,28 @@ impl Default for BotConfig {
             author_name: Some(String::from("Crab Force")),
             // author_icon: Some(String::from("https://explorer.lu/lu-res/textures/ui/inventory/models/amb_crab.png")),
             author_icon: Some(String::from("https://cdn.discordapp.com/avatars/1340084890342785055/4c83403b3a82920365a5007c1aa580ec.webp")),
-            color: None, //(0x42, 0xb9, 0xf5),
+            color: Some(String::from("#1a87e8")), // #42b9f5
             footer_message: Some(String::from("LEGO® is a trademark of the LEGO Group which does not sponsor, authorize, or endorse this bot. The data and assets are presented purely for informational purposes.")),
             footer_icon: None,
             // footer_icon: Some(String::from("https://cdn.discordapp.com/attachments/813618981247516715/1339979649328877627/170px-LEGO_logo.png?ex=67b0b0cc&is=67af5f4c&hm=7e9d7b9258682dae296a525bc2fb46a7835a3b9ebefbe5cc192519c32cd66402&format=webp&quality=lossless")),
+            cdclient_path: PathBuf::from("cdclient.sqlite"),
+            locale_path: PathBuf::from("locale.xml"),
+            settings_path: PathBuf::from("settings.sqlite"),
+            analytics_path: Some(PathBuf::from("analytics.sqlite")),
+            registration: RegistrationMode::Guilds,
+            guild_ids: vec![],
+            owner_ids: vec![],
+            watch_data_files: false,
+            watch_interval_secs: 30,
+            log_json: false,
+            metrics_addr: None,
+            api_addr: None,
+            defer_after_ms: 1500,
+            handler_timeout_secs: 30,
+            rate_limits: RateLimits::default(),
+            message_content_intent: false,
+            inline_lookups: false,
+            inline_channels: vec![],
         }
     }
 }
@@ -1,59 +1,30 @@
+use crate::arguments::CommandArguments;
 use crate::cdclient::MissionPreReqType;
 use crate::custom::OptionBuilder;
-use crate::interaction_command::{CommandResult, CustomIdOptions, InteractionCommand, ToCustomId};
-use crate::queries::{AchievementQueries, AutocompleteQueries, LocaleQueries, ObjectQueries};
-use crate::{int_option, CD_CLIENT, CONFIG, LOCALE_XML};
+use crate::interaction_command::{CommandResult, InteractionCommand};
+use crate::queries::{AchievementQueries, ObjectQueries};
+use crate::Api;
 use heck::ToTitleCase;
-use serenity::all::{AutocompleteChoice, CommandOptionType, CreateCommandOption, ResolvedOption};
+use serenity::all::CreateCommandOption;
 
 pub struct AchievementCommand;
+impl Api for AchievementCommand {}
 
+#[derive(CommandArguments)]
+#[arguments(command = AchievementCommand)]
 pub struct AchievementArguments {
+    /// An achievement in LEGO Universe.
+    #[option(required, autocomplete = "achievement")]
     pub achievement: i32,
 }
 
-impl ToCustomId for AchievementArguments {
-    const CMD: &'static str = AchievementCommand::NAME;
-
-    fn parameters(&self) -> String {
-        let AchievementArguments { achievement } = self;
-        format!("achievement={achievement}")
-    }
-}
-
-impl TryFrom<&CustomIdOptions> for AchievementArguments {
-    type Error = String;
-
-    fn try_from(options: &CustomIdOptions) -> Result<Self, Self::Error> {
-        Ok(AchievementArguments {
-            achievement: options.parse("achievement")?,
-        })
-    }
-}
-
-impl<'a> TryFrom<&'a [ResolvedOption<'a>]> for AchievementArguments {
-    type Error = String;
-
-    fn try_from(options: &'a [ResolvedOption<'a>]) -> Result<Self, Self::Error> {
-        Ok(AchievementArguments {
-            achievement: int_option!(options, "achievement"),
-        })
-    }
-}
-
 impl InteractionCommand for AchievementCommand {
     const NAME: &'static str = "achievement";
 
     const DESCRIPTION: &'static str = "View the stats of an achievement!";
 
     fn options() -> Option<Vec<CreateCommandOption>> {
-        Some(vec![CreateCommandOption::new(
-            CommandOptionType::Integer,
-            "achievement",
-            "An achievement in LEGO Universe.",
-        )
-        .required(true)
-        .set_autocomplete(true)])
+        Some(AchievementArguments::options())
     }
 
     type Arguments = AchievementArguments;
@@ -61,34 +32,35 @@ impl InteractionCommand for AchievementCommand {
     fn handle_autocomplete(
         autocomplete_option: serenity::model::prelude::AutocompleteOption<'_>,
     ) -> Option<Vec<serenity::all::AutocompleteChoice>> {
-        let input = autocomplete_option.value;
-        Some(CD_CLIENT.autocomplete_achievement(input))
+        AchievementArguments::autocomplete(autocomplete_option)
     }
 
     fn run(arguments: Self::Arguments) -> CommandResult {
+        let cdclient = AchievementCommand.cdclient();
         let AchievementArguments { achievement: id } = arguments;
 
-        let mission = CD_CLIENT.get_achievement(id)?;
-        let explorer_url = CD_CLIENT.achievement_explorer_url(id);
-        let name = CD_CLIENT.req_achievement_name(id);
-        let full_path = CD_CLIENT.full_achievement_path(id);
+        let mission = cdclient.get_achievement(id)?;
+        let explorer_url = cdclient.achievement_explorer_url(id);
+        let name = cdclient.req_achievement_name(id);
+        let full_path = cdclient.full_achievement_path(id);
 
         let giver = mission.offer_object_id;
         let target = mission.target_object_id;
         let is_mission = giver.is_some_and(|id| id != -1) && target.is_some_and(|id| id != -1);
         let type_label = if is_mission { "Mission" } else { "Achievement" };
 
-        let mut embed = CONFIG
+        let mut embed = AchievementCommand
+            .config()
             .default_embed()
             .title(format!("{full_path} [{id}]"))
             .url(explorer_url)
             .field(type_label, full_path, false);
 
-        let objective = CD_CLIENT
+        let objective = cdclient
             .locale()
             .mission_text
             .get(&id)
-            .map(|txt| txt.in_progress.as_ref())
+            .map(|txt| txt.in_progress.clone())
             .flatten();
 
         if let Some(objective) = objective {
@@ -107,13 +79,13 @@ impl InteractionCommand for AchievementCommand {
                         MissionPreReqType::OneOf(ids) => {
                             let names = ids
                                 .into_iter()
-                                .map(|id| CD_CLIENT.achievement_hyperlinked_name(*id))
+                                .map(|id| cdclient.achievement_hyperlinked_name(*id))
                                 .collect::<Vec<_>>()
                                 .join(", ");
                             format!("**{num}.** *One of the following:* {names}")
                         }
                         MissionPreReqType::Required(id) => {
-                            let name = CD_CLIENT.achievement_hyperlinked_name(*id);
+                            let name = cdclient.achievement_hyperlinked_name(*id);
                             format!("**{num}.** {name}")
                         }
                     }
@@ -126,8 +98,8 @@ impl InteractionCommand for AchievementCommand {
         // giver
         if is_mission {
             // checked earlier and made sure it was not -1 too
-            let giver_name = CD_CLIENT.object_hyperlinked_name(giver.unwrap());
-            let target_name = CD_CLIENT.object_hyperlinked_name(target.unwrap());
+            let giver_name = cdclient.object_hyperlinked_name(giver.unwrap());
+            let target_name = cdclient.object_hyperlinked_name(target.unwrap());
             embed = embed.fields([
                 ("Accept From", giver_name, true),
                 ("Return To", target_name, true),
@@ -135,13 +107,13 @@ impl InteractionCommand for AchievementCommand {
         }
 
         // rewards
-        if let Some(rewards) = CD_CLIENT.get_achievement_rewards(id) {
+        if let Some(rewards) = cdclient.get_achievement_rewards(id) {
             let choose_one = mission.is_choice_reward.is_some_and(|is| is);
             let mut value = rewards
                 .into_iter()
                 .enumerate()
                 .map(|(idx, r)| {
-                    let name = CD_CLIENT.object_hyperlinked_name(r.item_id);
+                    let name = cdclient.object_hyperlinked_name(r.item_id);
                     let count = r.count;
                     let num = idx + 1;
                     if choose_one {
@@ -171,7 +143,7 @@ impl InteractionCommand for AchievementCommand {
         .map(|(idx, emote)| {
             let num = idx + 1;
             let emote = emote.unwrap();
-            let name = CD_CLIENT
+            let name = cdclient
                 .emotes
                 .at_key(&emote)
                 .map(|e| e.animation_name.as_ref().map(|name| name.to_title_case()))
@@ -239,10 +211,10 @@ impl InteractionCommand for AchievementCommand {
             );
         }
 
-        if let Some(url) = CD_CLIENT.achievement_icon_url(id) {
+        if let Some(url) = cdclient.achievement_icon_url(id) {
             embed = embed.thumbnail(url);
         }
 
-        Ok((embed, None))
+        Ok(embed)
     }
 }
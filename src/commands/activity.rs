@@ -1,67 +1,36 @@
+use crate::arguments::CommandArguments;
 use crate::ids::CdClientObjectsId;
-use crate::interaction_command::{CommandResult, CustomIdOptions, InteractionCommand, ToCustomId};
-use crate::queries::{AutocompleteQueries, ObjectQueries};
-use crate::{int_option, CD_CLIENT, CONFIG, LOCALE_XML};
-use serenity::all::{
-    AutocompleteChoice, CommandOptionType, CreateCommandOption, CreateSelectMenuOption,
-    ResolvedOption,
-};
+use crate::interaction_command::{CommandResult, InteractionCommand, ToCustomId};
+use crate::queries::ObjectQueries;
+use crate::response::SelectOption;
+use crate::Api;
+use serenity::all::CreateCommandOption;
 
 pub struct ActivityCommand;
+impl Api for ActivityCommand {}
 
+#[derive(CommandArguments)]
+#[arguments(command = ActivityCommand)]
 pub struct ActivityArguments {
+    /// An activity in LEGO Universe.
+    #[option(required, autocomplete = "object")]
     pub activity: i32,
 }
 
-impl ToCustomId for ActivityArguments {
-    const CMD: &'static str = ActivityCommand::NAME;
-
-    fn parameters(&self) -> String {
-        let ActivityArguments { activity } = self;
-        format!("activity={activity}")
-    }
-}
-
-impl TryFrom<&CustomIdOptions> for ActivityArguments {
-    type Error = String;
-
-    fn try_from(options: &CustomIdOptions) -> Result<Self, Self::Error> {
-        Ok(ActivityArguments {
-            activity: options.parse("activity")?,
-        })
-    }
-}
-
-impl<'a> TryFrom<&'a [ResolvedOption<'a>]> for ActivityArguments {
-    type Error = String;
-
-    fn try_from(options: &'a [ResolvedOption<'a>]) -> Result<Self, Self::Error> {
-        Ok(ActivityArguments {
-            activity: int_option!(options, "activity"),
-        })
-    }
-}
-
-impl Into<CreateSelectMenuOption> for ActivityArguments {
-    fn into(self) -> CreateSelectMenuOption {
+impl Into<SelectOption> for ActivityArguments {
+    fn into(self) -> SelectOption {
         let it = CdClientObjectsId(self.activity);
-        CreateSelectMenuOption::new(it.name_id(), self.to_custom_id(true))
+        SelectOption::new(it.name_id(), self.to_custom_id(true))
     }
 }
 
 impl InteractionCommand for ActivityCommand {
     const NAME: &'static str = "activity";
 
-    const DESCRIPTION: &'static str = "View a activity in LEGO Universe!";
+    const DESCRIPTION: &'static str = "View an activity in LEGO Universe!";
 
     fn options() -> Option<Vec<CreateCommandOption>> {
-        Some(vec![CreateCommandOption::new(
-            CommandOptionType::Integer,
-            "activity",
-            "A activity in LEGO Universe.",
-        )
-        .required(true)
-        .set_autocomplete(true)])
+        Some(ActivityArguments::options())
     }
 
     type Arguments = ActivityArguments;
@@ -69,26 +38,27 @@ impl InteractionCommand for ActivityCommand {
     fn handle_autocomplete(
         autocomplete_option: serenity::model::prelude::AutocompleteOption<'_>,
     ) -> Option<Vec<serenity::all::AutocompleteChoice>> {
-        let input = autocomplete_option.value;
-        Some(CD_CLIENT.autocomplete_object(input))
+        ActivityArguments::autocomplete(autocomplete_option)
     }
 
     fn run(arguments: Self::Arguments) -> CommandResult {
+        let cdclient = ActivityCommand.cdclient();
         let ActivityArguments { activity: id } = arguments;
 
-        let explorer_url = CD_CLIENT.object_explorer_url(id);
-        let name = CD_CLIENT.req_object_name(id);
-        let item_component = CD_CLIENT.object_item_component(id)?;
+        let explorer_url = cdclient.object_explorer_url(id);
+        let name = cdclient.req_object_name(id);
+        let item_component = cdclient.object_item_component(id)?;
 
-        let mut embed = CONFIG
+        let mut embed = ActivityCommand
+            .config()
             .default_embed()
             .title(format!("{} [{}]", name, id))
             .url(explorer_url);
 
-        if let Some(icon_url) = CD_CLIENT.object_icon_url(id) {
+        if let Some(icon_url) = cdclient.object_icon_url(id) {
             embed = embed.thumbnail(icon_url);
         }
 
-        Ok((embed, None))
+        Ok(embed)
     }
 }
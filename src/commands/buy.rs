@@ -1,45 +1,25 @@
-use crate::interaction_command::{CommandResult, CustomIdOptions, InteractionCommand, ToCustomId};
-use crate::pager::START_PAGE;
-use crate::queries::{AutocompleteQueries, ObjectQueries};
-use crate::{int_option, CD_CLIENT, CONFIG, LOCALE_XML};
-use serenity::all::{AutocompleteChoice, CommandOptionType, CreateCommandOption, ResolvedOption};
+use crate::arguments::CommandArguments;
+use crate::commands::vendor::VendorArguments;
+use crate::error::BotError;
+use crate::interaction_command::{CommandResult, InteractionCommand};
+use crate::pager::{Pager, DEFAULT_PAGE_SIZE};
+use crate::queries::ObjectQueries;
+use crate::Api;
+use serenity::all::CreateCommandOption;
 
 pub struct BuyCommand;
+impl Api for BuyCommand {}
 
+#[derive(CommandArguments)]
+#[arguments(command = BuyCommand)]
 pub struct BuyArguments {
+    /// An item in LEGO Universe.
+    #[option(required, autocomplete = "object")]
     pub item: i32,
+    #[page]
     pub page: usize,
-}
-
-impl ToCustomId for BuyArguments {
-    const CMD: &'static str = BuyCommand::NAME;
-
-    fn parameters(&self) -> String {
-        let BuyArguments { item, page } = self;
-        vec![format!("item={item}"), format!("page={page}")].join("&")
-    }
-}
-
-impl TryFrom<&CustomIdOptions> for BuyArguments {
-    type Error = String;
-
-    fn try_from(options: &CustomIdOptions) -> Result<Self, Self::Error> {
-        Ok(BuyArguments {
-            item: options.parse("item")?,
-            page: options.parse("page")?,
-        })
-    }
-}
-
-impl<'a> TryFrom<&'a [ResolvedOption<'a>]> for BuyArguments {
-    type Error = String;
-
-    fn try_from(options: &'a [ResolvedOption<'a>]) -> Result<Self, Self::Error> {
-        Ok(BuyArguments {
-            item: int_option!(options, "item"),
-            page: START_PAGE,
-        })
-    }
+    #[custom_id(default = DEFAULT_PAGE_SIZE)]
+    pub size: usize,
 }
 
 impl InteractionCommand for BuyCommand {
@@ -48,13 +28,7 @@ impl InteractionCommand for BuyCommand {
     const DESCRIPTION: &'static str = "View all vendors that sell an item!";
 
     fn options() -> Option<Vec<CreateCommandOption>> {
-        Some(vec![CreateCommandOption::new(
-            CommandOptionType::Integer,
-            "item",
-            "An item in LEGO Universe.",
-        )
-        .required(true)
-        .set_autocomplete(true)])
+        Some(BuyArguments::options())
     }
 
     type Arguments = BuyArguments;
@@ -62,19 +36,20 @@ impl InteractionCommand for BuyCommand {
     fn handle_autocomplete(
         autocomplete_option: serenity::model::prelude::AutocompleteOption<'_>,
     ) -> Option<Vec<serenity::all::AutocompleteChoice>> {
-        let input = autocomplete_option.value;
-        Some(CD_CLIENT.autocomplete_object(input))
+        BuyArguments::autocomplete(autocomplete_option)
     }
 
     fn run(arguments: Self::Arguments) -> CommandResult {
+        let cdclient = BuyCommand.cdclient();
         let BuyArguments {
             item: item_id,
             page,
+            size,
         } = arguments;
 
-        let explorer_url = CD_CLIENT.object_explorer_url(item_id);
-        let name = CD_CLIENT.req_object_name(item_id);
-        let item_component = CD_CLIENT.object_item_component(item_id)?;
+        let explorer_url = cdclient.object_explorer_url(item_id);
+        let name = cdclient.req_object_name(item_id);
+        let item_component = cdclient.object_item_component(item_id)?;
 
         let coin_cost = item_component
             .base_value
@@ -82,44 +57,45 @@ impl InteractionCommand for BuyCommand {
 
         let commendation_cost = item_component.commendation_cost.map(|cost| {
             let lot = item_component.commendation_lot.ok_or_else(|| {
-                format!("Commendation Cost Provided but Commendation Object is not")
+                BotError::DataInconsistency(String::from(
+                    "Commendation Cost Provided but Commendation Object is not",
+                ))
             });
             lot.map(|lot| {
-                let name = CD_CLIENT.object_hyperlinked_name(lot);
+                let name = cdclient.object_hyperlinked_name(lot);
                 format!("**{cost}** {name}")
             })
         });
 
         let alt_cost = item_component.alt_currency_cost.map(|cost| {
             let lot = item_component.currency_lot.ok_or_else(|| {
-                format!("Alternate Currency Cost Provided but Alternate Currency Object is not")
+                BotError::DataInconsistency(String::from(
+                    "Alternate Currency Cost Provided but Alternate Currency Object is not",
+                ))
             });
             lot.map(|lot| {
-                let name = CD_CLIENT.object_hyperlinked_name(lot);
+                let name = cdclient.object_hyperlinked_name(lot);
                 format!("**{cost}** {name}")
             })
         });
 
-        let mut embed = CONFIG
+        let mut embed = BuyCommand
+            .config()
             .default_embed()
             .title(format!("{} [{}]", name, item_id))
             .url(explorer_url);
 
-        let vendors = CD_CLIENT
+        let vendors = cdclient
             .object_vendor_ids(item_id)
             .unwrap_or_else(|_| vec![]);
-        let description = if vendors.len() == 0 {
-            format!("This item is not sold")
+        let pager = Pager::new(vendors, page, size);
+        let description = if pager.total() == 0 {
+            String::from("This item is not sold")
         } else {
-            let vendors = vendors
-                .into_iter()
-                .enumerate()
-                .map(|(idx, vendor_id)| {
-                    let name = CD_CLIENT.object_hyperlinked_name(vendor_id);
-                    format!("{}. {name}", idx + 1)
-                })
-                .collect::<Vec<_>>()
-                .join("\n");
+            let vendors = pager.lines(|num, vendor_id| {
+                let name = cdclient.object_hyperlinked_name(vendor_id);
+                format!("{num}. {name}")
+            });
             format!("**Vendors**\n{vendors}")
         };
         embed = embed.description(description);
@@ -136,10 +112,27 @@ impl InteractionCommand for BuyCommand {
             embed = embed.field("Alternate Currency", alt_cost?, true);
         }
 
-        if let Some(icon_url) = CD_CLIENT.object_icon_url(item_id) {
+        if let Some(icon_url) = cdclient.object_icon_url(item_id) {
             embed = embed.thumbnail(icon_url);
         }
 
-        Ok((embed, None))
+        let mut components = vec![];
+        if pager.total() > 1 {
+            components.extend(
+                pager.select_menu(BuyCommand::NAME, |vendor| VendorArguments { vendor }.into()),
+            );
+        }
+        components.extend(pager.row(|page| BuyArguments {
+            item: item_id,
+            page,
+            size,
+        }));
+        components.extend(pager.size_menu(|page, size| BuyArguments {
+            item: item_id,
+            page,
+            size,
+        }));
+
+        Ok(embed.components(components))
     }
 }
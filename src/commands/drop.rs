@@ -1,54 +1,59 @@
+use crate::arguments::{CommandArguments, OptionChoice};
 use crate::commands::buy::BuyArguments;
 use crate::commands::earn::EarnArguments;
 use crate::commands::reward::RewardArguments;
 use crate::commands::smash::{SmashArguments, SmashCommand};
 use crate::commands::unpack::UnpackArguments;
 use crate::ids::CdClientObjectsId;
-use crate::interaction_command::{CommandResult, CustomIdOptions, InteractionCommand, ToCustomId};
-use crate::pager::{Pager, START_PAGE};
-use crate::queries::{AutocompleteQueries, ObjectQueries};
-use crate::{int_option, CD_CLIENT, CONFIG, LOCALE_XML};
+use crate::interaction_command::{CommandResult, InteractionCommand, ToCustomId};
+use crate::pager::{Pager, DEFAULT_PAGE_SIZE, START_PAGE};
+use crate::queries::ObjectQueries;
+use crate::response::{ActionRow, SelectMenu, SelectOption};
+use crate::Api;
 use itertools::Itertools;
-use serenity::all::{
-    AutocompleteChoice, ButtonStyle, CommandOptionType, CreateActionRow, CreateCommandOption,
-    CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption, ResolvedOption,
-};
+use serenity::all::CreateCommandOption;
 
 pub struct DropCommand;
+impl Api for DropCommand {}
+
+/// The order smashables are listed in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, OptionChoice)]
+pub enum DropSort {
+    #[default]
+    #[choice(label = "Highest chance first")]
+    Highest,
+    #[choice(label = "Lowest chance first")]
+    Lowest,
+}
 
+#[derive(CommandArguments)]
+#[arguments(command = DropCommand)]
 pub struct DropArguments {
+    /// An item in LEGO Universe.
+    #[option(required, autocomplete = "object")]
     pub item: i32,
+    /// Only show chances of at least this percent (default: 0).
+    #[option(default = 0.0, min = 0, max = 100, longest = 99.999)]
+    pub min_chance: f64,
+    /// The order to list smashables in (default: highest chance first).
+    #[option(default = DropSort::default())]
+    pub sort: DropSort,
+    #[page]
     pub page: usize,
+    #[custom_id(default = DEFAULT_PAGE_SIZE)]
+    pub size: usize,
 }
 
-impl ToCustomId for DropArguments {
-    const CMD: &'static str = DropCommand::NAME;
-
-    fn parameters(&self) -> String {
-        let DropArguments { item, page } = self;
-        vec![format!("item={item}"), format!("page={page}")].join("&")
-    }
-}
-
-impl TryFrom<&CustomIdOptions> for DropArguments {
-    type Error = String;
-
-    fn try_from(options: &CustomIdOptions) -> Result<Self, Self::Error> {
-        Ok(DropArguments {
-            item: options.parse("item")?,
-            page: options.parse("page")?,
-        })
-    }
-}
-
-impl<'a> TryFrom<&'a [ResolvedOption<'a>]> for DropArguments {
-    type Error = String;
-
-    fn try_from(options: &'a [ResolvedOption<'a>]) -> Result<Self, Self::Error> {
-        Ok(DropArguments {
-            item: int_option!(options, "item"),
-            page: START_PAGE,
-        })
+impl DropArguments {
+    /// `/item drop` of an item with every smashable, ex: for the Drop button of other commands
+    pub fn new(item: i32, page: usize, size: usize) -> Self {
+        Self {
+            item,
+            min_chance: 0.0,
+            sort: DropSort::default(),
+            page,
+            size,
+        }
     }
 }
 
@@ -58,26 +63,40 @@ impl InteractionCommand for DropCommand {
     const DESCRIPTION: &'static str = "View all smashables that drop an item!";
 
     fn options() -> Option<Vec<CreateCommandOption>> {
-        Some(vec![CreateCommandOption::new(
-            CommandOptionType::Integer,
-            "item",
-            "An item in LEGO Universe.",
-        )
-        .required(true)
-        .set_autocomplete(true)])
+        Some(DropArguments::options())
     }
 
     type Arguments = DropArguments;
 
+    fn examples() -> Vec<Self::Arguments> {
+        vec![
+            DropArguments::new(7415, START_PAGE, DEFAULT_PAGE_SIZE),
+            DropArguments {
+                min_chance: 10.0,
+                sort: DropSort::Lowest,
+                ..DropArguments::new(7415, START_PAGE, DEFAULT_PAGE_SIZE)
+            },
+        ]
+    }
+
     fn handle_autocomplete(
         autocomplete_option: serenity::model::prelude::AutocompleteOption<'_>,
     ) -> Option<Vec<serenity::all::AutocompleteChoice>> {
-        let input = autocomplete_option.value;
-        Some(CD_CLIENT.autocomplete_object(input))
+        DropArguments::autocomplete(autocomplete_option)
     }
 
     fn run(arguments: Self::Arguments) -> CommandResult {
-        let DropArguments { item: id, page } = arguments;
+        let cdclient = DropCommand.cdclient();
+        let DropArguments {
+            item: id,
+            min_chance,
+            sort,
+            page,
+            size,
+        } = arguments;
+        // chances are shown with 5 decimals, but 3 are plenty to filter by and keep custom ids short
+        // (see `longest`)
+        let min_chance = (min_chance * 1000.0).round() / 1000.0;
 
         let object = CdClientObjectsId(id);
         let name = object.req_name();
@@ -86,17 +105,30 @@ impl InteractionCommand for DropCommand {
         // Create Embed //
         // ------------ //
 
-        let mut embed = CONFIG
+        let mut embed = DropCommand
+            .config()
             .default_embed()
             .title(object.name_id())
             .url(object.explorer_url());
 
-        if let Some(icon_url) = CD_CLIENT.object_icon_url(id) {
+        if let Some(icon_url) = cdclient.object_icon_url(id) {
             embed = embed.thumbnail(icon_url);
         }
 
-        let entries = object.smashables_chances()?;
-        let pager = Pager::new(entries, page, 5);
+        let mut entries: Vec<_> = object
+            .smashables_chances()?
+            .into_iter()
+            .filter(|entry| entry.chance * 100.0 >= min_chance)
+            .collect();
+        if entries.is_empty() && min_chance > 0.0 {
+            Err(format!(
+                "{name} never drops with at least a {min_chance}% chance"
+            ))?;
+        }
+        if sort == DropSort::Lowest {
+            entries.reverse();
+        }
+        let pager = Pager::new(entries, page, size);
 
         for (num, entry) in pager.this_page() {
             let field_name = format!("{}. {:.5}% for {}", num, entry.chance * 100.0, &name);
@@ -121,13 +153,13 @@ impl InteractionCommand for DropCommand {
 
         let page = START_PAGE;
         let item = id;
-        let earn_button = EarnArguments { item, page }.to_update_button("Earn");
-        let drop_button = DropArguments { item, page }.to_self_button("Drop");
-        let unpack_button = UnpackArguments { item, page }.to_update_button("Unpack");
-        let reward_button = RewardArguments { item, page }.to_update_button("Reward");
-        let buy_button = BuyArguments { item, page }.to_update_button("Buy");
+        let earn_button = EarnArguments { item, page, size }.to_update_button("Earn");
+        let drop_button = DropArguments::new(item, page, size).to_self_button("Drop");
+        let unpack_button = UnpackArguments { item, page, size }.to_update_button("Unpack");
+        let reward_button = RewardArguments { item, page, size }.to_update_button("Reward");
+        let buy_button = BuyArguments { item, page, size }.to_update_button("Buy");
 
-        components.push(CreateActionRow::Buttons(vec![
+        components.push(ActionRow::Buttons(vec![
             earn_button,
             drop_button,
             unpack_button,
@@ -139,7 +171,7 @@ impl InteractionCommand for DropCommand {
         // Referenced Objects Row //
         // ---------------------- //
 
-        let options: Vec<CreateSelectMenuOption> = pager
+        let options: Vec<SelectOption> = pager
             .this_page()
             .into_iter()
             .flat_map(|(_, entry)| {
@@ -154,9 +186,9 @@ impl InteractionCommand for DropCommand {
             .collect();
 
         if options.len() > 1 {
-            components.push(CreateActionRow::SelectMenu(CreateSelectMenu::new(
+            components.push(ActionRow::SelectMenu(SelectMenu::new(
                 DropCommand::NAME,
-                CreateSelectMenuKind::String { options },
+                options,
             )));
         }
 
@@ -164,27 +196,21 @@ impl InteractionCommand for DropCommand {
         // Pagination Row //
         // -------------- //
 
-        let prev_page_button = DropArguments {
+        components.extend(pager.row(|page| DropArguments {
             item: id,
-            page: pager.prev(),
-        }
-        .to_update_button(format!("Page {}", pager.prev()))
-        .disabled(pager.is_first_page());
-
-        let next_page_button = DropArguments {
+            min_chance,
+            sort,
+            page,
+            size,
+        }));
+        components.extend(pager.size_menu(|page, size| DropArguments {
             item: id,
-            page: pager.next(),
-        }
-        .to_update_button(format!("Page {}", pager.next()))
-        .disabled(pager.is_last_page());
-
-        if pager.has_multiple_pages() {
-            components.push(CreateActionRow::Buttons(vec![
-                prev_page_button,
-                next_page_button,
-            ]));
-        }
+            min_chance,
+            sort,
+            page,
+            size,
+        }));
 
-        Ok((embed, Some(components)))
+        Ok(embed.components(components))
     }
 }
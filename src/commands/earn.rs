@@ -1,12 +1,11 @@
+use crate::arguments::CommandArguments;
 use crate::ids::CdClientObjectsId;
-use crate::interaction_command::{CommandResult, CustomIdOptions, InteractionCommand, ToCustomId};
-use crate::pager::{Pager, START_PAGE};
-use crate::queries::{AutocompleteQueries, ObjectQueries};
-use crate::{int_option, CD_CLIENT, CONFIG, LOCALE_XML};
-use serenity::all::{
-    AutocompleteChoice, CommandOptionType, CreateActionRow, CreateCommandOption, CreateSelectMenu,
-    CreateSelectMenuKind, CreateSelectMenuOption, ResolvedOption,
-};
+use crate::interaction_command::{CommandResult, InteractionCommand, ToCustomId};
+use crate::pager::{Pager, DEFAULT_PAGE_SIZE, START_PAGE};
+use crate::queries::ObjectQueries;
+use crate::response::{ActionRow, SelectMenu, SelectOption};
+use crate::Api;
+use serenity::all::CreateCommandOption;
 
 use super::buy::BuyArguments;
 use super::drop::DropArguments;
@@ -14,41 +13,18 @@ use super::reward::RewardArguments;
 use super::unpack::UnpackArguments;
 
 pub struct EarnCommand;
+impl Api for EarnCommand {}
 
+#[derive(CommandArguments)]
+#[arguments(command = EarnCommand)]
 pub struct EarnArguments {
+    /// An item in LEGO Universe.
+    #[option(required, autocomplete = "object")]
     pub item: i32,
+    #[page]
     pub page: usize,
-}
-
-impl ToCustomId for EarnArguments {
-    const CMD: &'static str = EarnCommand::NAME;
-
-    fn parameters(&self) -> String {
-        let EarnArguments { item, page } = self;
-        vec![format!("item={item}"), format!("page={page}")].join("&")
-    }
-}
-
-impl TryFrom<&CustomIdOptions> for EarnArguments {
-    type Error = String;
-
-    fn try_from(options: &CustomIdOptions) -> Result<Self, Self::Error> {
-        Ok(EarnArguments {
-            item: options.parse("item")?,
-            page: options.parse("page")?,
-        })
-    }
-}
-
-impl<'a> TryFrom<&'a [ResolvedOption<'a>]> for EarnArguments {
-    type Error = String;
-
-    fn try_from(options: &'a [ResolvedOption<'a>]) -> Result<Self, Self::Error> {
-        Ok(EarnArguments {
-            item: int_option!(options, "item"),
-            page: START_PAGE,
-        })
-    }
+    #[custom_id(default = DEFAULT_PAGE_SIZE)]
+    pub size: usize,
 }
 
 impl InteractionCommand for EarnCommand {
@@ -57,13 +33,7 @@ impl InteractionCommand for EarnCommand {
     const DESCRIPTION: &'static str = "View all missions that reward an item!";
 
     fn options() -> Option<Vec<CreateCommandOption>> {
-        Some(vec![CreateCommandOption::new(
-            CommandOptionType::Integer,
-            "item",
-            "An item in LEGO Universe.",
-        )
-        .required(true)
-        .set_autocomplete(true)])
+        Some(EarnArguments::options())
     }
 
     type Arguments = EarnArguments;
@@ -71,12 +41,16 @@ impl InteractionCommand for EarnCommand {
     fn handle_autocomplete(
         autocomplete_option: serenity::model::prelude::AutocompleteOption<'_>,
     ) -> Option<Vec<serenity::all::AutocompleteChoice>> {
-        let input = autocomplete_option.value;
-        Some(CD_CLIENT.autocomplete_object(input))
+        EarnArguments::autocomplete(autocomplete_option)
     }
 
     fn run(arguments: Self::Arguments) -> CommandResult {
-        let EarnArguments { item: id, page } = arguments;
+        let cdclient = EarnCommand.cdclient();
+        let EarnArguments {
+            item: id,
+            page,
+            size,
+        } = arguments;
 
         // ------------ //
         // Create Embed //
@@ -85,17 +59,18 @@ impl InteractionCommand for EarnCommand {
         let object = CdClientObjectsId(id);
         let name = object.req_name();
 
-        let mut embed = CONFIG
+        let mut embed = EarnCommand
+            .config()
             .default_embed()
             .title(object.name_id())
             .url(object.explorer_url());
 
-        if let Some(icon_url) = CD_CLIENT.object_icon_url(id) {
+        if let Some(icon_url) = cdclient.object_icon_url(id) {
             embed = embed.thumbnail(icon_url);
         }
 
         let entries = object.achievements()?;
-        let pager = Pager::new(entries, page, 5);
+        let pager = Pager::new(entries, page, size);
 
         // ---------- //
         // Components //
@@ -109,13 +84,13 @@ impl InteractionCommand for EarnCommand {
 
         let page = START_PAGE;
         let item = id;
-        let earn_button = EarnArguments { item, page }.to_self_button("Earn");
-        let drop_button = DropArguments { item, page }.to_update_button("Drop");
-        let unpack_button = UnpackArguments { item, page }.to_update_button("Unpack");
-        let reward_button = RewardArguments { item, page }.to_update_button("Reward");
-        let buy_button = BuyArguments { item, page }.to_update_button("Buy");
+        let earn_button = EarnArguments { item, page, size }.to_self_button("Earn");
+        let drop_button = DropArguments::new(item, page, size).to_update_button("Drop");
+        let unpack_button = UnpackArguments { item, page, size }.to_update_button("Unpack");
+        let reward_button = RewardArguments { item, page, size }.to_update_button("Reward");
+        let buy_button = BuyArguments { item, page, size }.to_update_button("Buy");
 
-        components.push(CreateActionRow::Buttons(vec![
+        components.push(ActionRow::Buttons(vec![
             earn_button,
             drop_button,
             unpack_button,
@@ -127,12 +102,12 @@ impl InteractionCommand for EarnCommand {
         // Referenced Objects Row //
         // ---------------------- //
 
-        let options: Vec<CreateSelectMenuOption> = vec![];
+        let options: Vec<SelectOption> = vec![];
 
         if options.len() > 1 {
-            components.push(CreateActionRow::SelectMenu(CreateSelectMenu::new(
+            components.push(ActionRow::SelectMenu(SelectMenu::new(
                 EarnCommand::NAME,
-                CreateSelectMenuKind::String { options },
+                options,
             )));
         }
 
@@ -140,27 +115,17 @@ impl InteractionCommand for EarnCommand {
         // Pagination Row //
         // -------------- //
 
-        let prev_page_button = EarnArguments {
+        components.extend(pager.row(|page| EarnArguments {
             item: id,
-            page: pager.prev(),
-        }
-        .to_update_button(format!("Page {}", pager.prev()))
-        .disabled(pager.is_first_page());
-
-        let next_page_button = EarnArguments {
+            page,
+            size,
+        }));
+        components.extend(pager.size_menu(|page, size| EarnArguments {
             item: id,
-            page: pager.next(),
-        }
-        .to_update_button(format!("Page {}", pager.next()))
-        .disabled(pager.is_last_page());
-
-        if pager.has_multiple_pages() {
-            components.push(CreateActionRow::Buttons(vec![
-                prev_page_button,
-                next_page_button,
-            ]));
-        }
+            page,
+            size,
+        }));
 
-        Ok((embed, Some(components)))
+        Ok(embed.components(components))
     }
 }
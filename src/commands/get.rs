@@ -1,56 +1,27 @@
-use crate::interaction_command::{CommandResult, CustomIdOptions, InteractionCommand, ToCustomId};
-use crate::queries::{AutocompleteQueries, ObjectQueries};
-use crate::{int_option, CD_CLIENT, CONFIG, LOCALE_XML};
-use serenity::all::{AutocompleteChoice, CommandOptionType, CreateCommandOption, ResolvedOption};
+use crate::arguments::CommandArguments;
+use crate::interaction_command::{CommandResult, InteractionCommand};
+use crate::queries::ObjectQueries;
+use crate::Api;
+use serenity::all::CreateCommandOption;
 
 pub struct GetCommand;
+impl Api for GetCommand {}
 
+#[derive(CommandArguments)]
+#[arguments(command = GetCommand)]
 pub struct GetArguments {
+    /// An item in LEGO Universe.
+    #[option(required, autocomplete = "object")]
     item: i32,
 }
 
-impl ToCustomId for GetArguments {
-    const CMD: &'static str = GetCommand::NAME;
-
-    fn parameters(&self) -> String {
-        let GetArguments { item } = self;
-        format!("item={item}")
-    }
-}
-
-impl TryFrom<&CustomIdOptions> for GetArguments {
-    type Error = String;
-
-    fn try_from(options: &CustomIdOptions) -> Result<Self, Self::Error> {
-        Ok(GetArguments {
-            item: options.parse("item")?,
-        })
-    }
-}
-
-impl<'a> TryFrom<&'a [ResolvedOption<'a>]> for GetArguments {
-    type Error = String;
-
-    fn try_from(options: &'a [ResolvedOption<'a>]) -> Result<Self, Self::Error> {
-        Ok(GetArguments {
-            item: int_option!(options, "item"),
-        })
-    }
-}
-
 impl InteractionCommand for GetCommand {
     const NAME: &'static str = "get";
 
     const DESCRIPTION: &'static str = "View how to get an item!";
 
     fn options() -> Option<Vec<CreateCommandOption>> {
-        Some(vec![CreateCommandOption::new(
-            CommandOptionType::Integer,
-            "item",
-            "An item in LEGO Universe.",
-        )
-        .required(true)
-        .set_autocomplete(true)])
+        Some(GetArguments::options())
     }
 
     type Arguments = GetArguments;
@@ -58,26 +29,27 @@ impl InteractionCommand for GetCommand {
     fn handle_autocomplete(
         autocomplete_option: serenity::model::prelude::AutocompleteOption<'_>,
     ) -> Option<Vec<serenity::all::AutocompleteChoice>> {
-        let input = autocomplete_option.value;
-        Some(CD_CLIENT.autocomplete_object(input))
+        GetArguments::autocomplete(autocomplete_option)
     }
 
     fn run(arguments: Self::Arguments) -> CommandResult {
+        let cdclient = GetCommand.cdclient();
         let GetArguments { item: id } = arguments;
 
-        let explorer_url = CD_CLIENT.object_explorer_url(id);
-        let name = CD_CLIENT.req_object_name(id);
-        let item_component = CD_CLIENT.object_item_component(id)?;
+        let explorer_url = cdclient.object_explorer_url(id);
+        let name = cdclient.req_object_name(id);
+        let item_component = cdclient.object_item_component(id)?;
 
-        let mut embed = CONFIG
+        let mut embed = GetCommand
+            .config()
             .default_embed()
             .title(format!("{} [{}]", name, id))
             .url(explorer_url);
 
-        if let Some(icon_url) = CD_CLIENT.object_icon_url(id) {
+        if let Some(icon_url) = cdclient.object_icon_url(id) {
             embed = embed.thumbnail(icon_url);
         }
 
-        Ok((embed, None))
+        Ok(embed)
     }
 }
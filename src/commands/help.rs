@@ -0,0 +1,261 @@
+use serde_json::Value;
+use serenity::all::{AutocompleteChoice, CreateCommandOption};
+
+use crate::arguments::CommandArguments;
+use crate::commands::COMMANDS;
+use crate::error::BotError;
+use crate::interaction_command::{CommandResult, CustomIdOptions, InteractionCommand, ToCustomId};
+use crate::pager::{Pager, START_PAGE};
+use crate::registry::RegisteredCommand;
+use crate::response::{ActionRow, Button, ButtonAction, SelectOption};
+use crate::Api;
+
+pub struct HelpCommand;
+impl Api for HelpCommand {}
+
+/// Few enough for the select menu
+const COMMANDS_PER_PAGE: usize = 15;
+
+/// Discord shows at most 5 buttons in a row
+const EXAMPLES: usize = 5;
+
+#[derive(CommandArguments)]
+#[arguments(command = HelpCommand)]
+pub struct HelpArguments {
+    /// A command of the bot, ex: /item drop.
+    // kept as `InteractionCommand::NAME`, which Discord allows up to 32 characters of
+    #[option(autocomplete = autocomplete_command, longest = "x".repeat(32))]
+    pub command: Option<String>,
+    #[page]
+    pub page: usize,
+}
+
+/// What `/help` shows of a command, from what it registers
+#[derive(Clone)]
+struct CommandHelp {
+    /// How it is run without the `/`, ex: `item drop`
+    invocation: String,
+    command: RegisteredCommand,
+    description: String,
+    /// Its slash command options as Discord receives them
+    options: Vec<Value>,
+}
+
+impl CommandHelp {
+    fn description(&self) -> String {
+        match self.command.owner_only {
+            true => format!("{} (owners only)", self.description),
+            false => self.description.clone(),
+        }
+    }
+
+    fn to_select_option(&self, page: usize) -> SelectOption {
+        let arguments = HelpArguments {
+            command: Some(self.command.name.to_string()),
+            page,
+        };
+        SelectOption::new(
+            format!("/{}", self.invocation),
+            arguments.to_custom_id(false),
+        )
+        .description(self.description())
+    }
+
+    /// - Options are left out of the label when they are what the command starts at anyway
+    fn example(&self, custom_id: String) -> (String, String) {
+        let Ok(options) = CustomIdOptions::from_custom_id(&custom_id) else {
+            return (format!("/{}", self.invocation), custom_id);
+        };
+        let given = self
+            .options
+            .iter()
+            .filter_map(|option| option["name"].as_str())
+            .filter_map(|name| {
+                let value = options.map.get(name)?;
+                let mut without = CustomIdOptions::from_custom_id(&custom_id).ok()?;
+                without.map.remove(name);
+                let is_default =
+                    (self.command.custom_id)(&without).is_ok_and(|without| without == custom_id);
+                (!is_default).then(|| format!("{name}:{value}"))
+            });
+        let label = std::iter::once(format!("/{}", self.invocation))
+            .chain(given)
+            .collect::<Vec<_>>()
+            .join(" ");
+        (label, custom_id)
+    }
+
+    fn examples(&self) -> Vec<(String, String)> {
+        (self.command.examples)()
+            .into_iter()
+            .map(|custom_id| self.example(custom_id))
+            .collect()
+    }
+
+    fn options(&self) -> String {
+        let lines: Vec<_> = self
+            .options
+            .iter()
+            .map(|option| {
+                let name = option["name"].as_str().unwrap_or_default();
+                let description = option["description"].as_str().unwrap_or_default();
+                let mut line = match option["required"].as_bool().unwrap_or(false) {
+                    true => format!("`{name}` {description} (required)"),
+                    false => format!("`{name}` {description}"),
+                };
+                if let Some(choices) = option["choices"].as_array() {
+                    let choices: Vec<_> = choices
+                        .iter()
+                        .filter_map(|choice| choice["name"].as_str())
+                        .collect();
+                    line = format!("{line}: {}", choices.join(", "));
+                }
+                line
+            })
+            .collect();
+        match lines.is_empty() {
+            true => String::from("None"),
+            false => lines.join("\n"),
+        }
+    }
+}
+
+/// Every command in the order Discord lists them
+fn commands() -> Vec<CommandHelp> {
+    COMMANDS
+        .invocations()
+        .into_iter()
+        .map(|(invocation, command)| {
+            let registered = serde_json::to_value((command.register)()).unwrap_or_default();
+            CommandHelp {
+                invocation,
+                command,
+                description: registered["description"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                options: registered["options"]
+                    .as_array()
+                    .cloned()
+                    .unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+/// Finds a command by [`InteractionCommand::NAME`] or by how it is run, ex: `drop` or `/item drop`
+fn find(commands: &[CommandHelp], command: &str) -> Option<usize> {
+    let command = command.trim().trim_start_matches('/');
+    commands
+        .iter()
+        .position(|help| help.command.name == command || help.invocation == command)
+}
+
+/// The examples of a command as how they are typed in Discord and the custom ids that run them,
+/// ex: `/item drop item:7415`
+pub fn examples(command: &str) -> Vec<(String, String)> {
+    let commands = commands();
+    find(&commands, command)
+        .map(|idx| commands[idx].examples())
+        .unwrap_or_default()
+}
+
+fn autocomplete_command(input: &str) -> Vec<AutocompleteChoice> {
+    let input = input.trim().trim_start_matches('/').to_lowercase();
+    COMMANDS
+        .invocations()
+        .into_iter()
+        .filter(|(invocation, _)| invocation.contains(&input))
+        .take(25)
+        .map(|(invocation, command)| {
+            AutocompleteChoice::new(format!("/{invocation}"), command.name)
+        })
+        .collect()
+}
+
+impl InteractionCommand for HelpCommand {
+    const NAME: &'static str = "help";
+
+    const DESCRIPTION: &'static str = "View every command, what it does and examples to try!";
+
+    fn options() -> Option<Vec<CreateCommandOption>> {
+        Some(HelpArguments::options())
+    }
+
+    type Arguments = HelpArguments;
+
+    fn handle_autocomplete(
+        autocomplete_option: serenity::model::prelude::AutocompleteOption<'_>,
+    ) -> Option<Vec<serenity::all::AutocompleteChoice>> {
+        HelpArguments::autocomplete(autocomplete_option)
+    }
+
+    fn examples() -> Vec<Self::Arguments> {
+        vec![HelpArguments {
+            command: Some(String::from("drop")),
+            page: START_PAGE,
+        }]
+    }
+
+    fn run(arguments: Self::Arguments) -> CommandResult {
+        let HelpArguments { command, page } = arguments;
+        let commands = commands();
+        let embed = HelpCommand.config().default_embed();
+
+        let Some(command) = command else {
+            let pager = Pager::new(commands, page, COMMANDS_PER_PAGE);
+            let description =
+                pager.lines(|_, help| format!("`/{}` {}", help.invocation, help.description()));
+            let mut components = vec![];
+            components.extend(pager.select_menu(HelpCommand::NAME, |help| {
+                help.to_select_option(pager.current())
+            }));
+            components.extend(pager.row(|page| HelpArguments {
+                command: None,
+                page,
+            }));
+            return Ok(embed
+                .title("Commands")
+                .description(description)
+                .components(components));
+        };
+
+        let idx = find(&commands, &command).ok_or_else(|| {
+            BotError::NotFound(format!("`/{command}` is not a command, see `/help`"))
+        })?;
+        let help = commands[idx].clone();
+        // the page it is on, to pick other commands from and go back to
+        let page = idx / COMMANDS_PER_PAGE + START_PAGE;
+
+        let mut embed = embed
+            .title(format!("/{}", help.invocation))
+            .description(help.description())
+            .field("Options", help.options(), false);
+
+        let examples: Vec<_> = help.examples().into_iter().take(EXAMPLES).collect();
+        let mut components = vec![];
+        if !examples.is_empty() {
+            let lines: Vec<_> = examples
+                .iter()
+                .map(|(label, _)| format!("`{label}`"))
+                .collect();
+            embed = embed.field("Examples", lines.join("\n"), false);
+            let buttons = examples
+                .into_iter()
+                .map(|(label, custom_id)| Button::new(label, ButtonAction::Run(custom_id)))
+                .collect();
+            components.push(ActionRow::Buttons(buttons));
+        }
+        let pager = Pager::new(commands, page, COMMANDS_PER_PAGE);
+        components.extend(pager.select_menu(HelpCommand::NAME, |help| help.to_select_option(page)));
+        let back = HelpArguments {
+            command: None,
+            page,
+        };
+        components.push(ActionRow::Buttons(vec![
+            back.to_update_button("All Commands")
+        ]));
+
+        Ok(embed.components(components))
+    }
+}
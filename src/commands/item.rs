@@ -1,40 +1,26 @@
-use crate::interaction_command::{CommandResult, CustomIdOptions, InteractionCommand, ToCustomId};
-use crate::queries::{AutocompleteQueries, ObjectQueries};
-use crate::{int_option, CD_CLIENT, CONFIG, LOCALE_XML};
-use serenity::all::{AutocompleteChoice, CommandOptionType, CreateCommandOption, ResolvedOption};
+use crate::arguments::CommandArguments;
+use crate::ids::CdClientObjectsId;
+use crate::interaction_command::{CommandResult, InteractionCommand, ToCustomId};
+use crate::queries::ObjectQueries;
+use crate::response::SelectOption;
+use crate::Api;
+use serenity::all::CreateCommandOption;
 
 pub struct ItemCommand;
+impl Api for ItemCommand {}
 
+#[derive(CommandArguments)]
+#[arguments(command = ItemCommand)]
 pub struct ItemArguments {
-    item: i32,
+    /// An item in LEGO Universe.
+    #[option(required, autocomplete = "object")]
+    pub item: i32,
 }
 
-impl ToCustomId for ItemArguments {
-    const CMD: &'static str = ItemCommand::NAME;
-
-    fn parameters(&self) -> String {
-        let ItemArguments { item } = self;
-        format!("item={item}")
-    }
-}
-
-impl TryFrom<&CustomIdOptions> for ItemArguments {
-    type Error = String;
-
-    fn try_from(options: &CustomIdOptions) -> Result<Self, Self::Error> {
-        Ok(ItemArguments {
-            item: options.parse("item")?,
-        })
-    }
-}
-
-impl<'a> TryFrom<&'a [ResolvedOption<'a>]> for ItemArguments {
-    type Error = String;
-
-    fn try_from(options: &'a [ResolvedOption<'a>]) -> Result<Self, Self::Error> {
-        Ok(ItemArguments {
-            item: int_option!(options, "item"),
-        })
+impl From<ItemArguments> for SelectOption {
+    fn from(arguments: ItemArguments) -> Self {
+        let it = CdClientObjectsId(arguments.item);
+        SelectOption::new(it.name_id(), arguments.to_custom_id(true))
     }
 }
 
@@ -44,40 +30,39 @@ impl InteractionCommand for ItemCommand {
     const DESCRIPTION: &'static str = "View the stats of an item!";
 
     fn options() -> Option<Vec<CreateCommandOption>> {
-        Some(vec![CreateCommandOption::new(
-            CommandOptionType::Integer,
-            "item",
-            "An item in LEGO Universe.",
-        )
-        .required(true)
-        .set_autocomplete(true)])
+        Some(ItemArguments::options())
     }
 
     type Arguments = ItemArguments;
 
+    fn examples() -> Vec<Self::Arguments> {
+        vec![ItemArguments { item: 7415 }]
+    }
+
     fn handle_autocomplete(
         autocomplete_option: serenity::model::prelude::AutocompleteOption<'_>,
     ) -> Option<Vec<serenity::all::AutocompleteChoice>> {
-        let input = autocomplete_option.value;
-        Some(CD_CLIENT.autocomplete_object(input))
+        ItemArguments::autocomplete(autocomplete_option)
     }
 
     fn run(arguments: Self::Arguments) -> CommandResult {
+        let cdclient = ItemCommand.cdclient();
         let ItemArguments { item: id } = arguments;
 
-        let explorer_url = CD_CLIENT.object_explorer_url(id);
-        let name = CD_CLIENT.req_object_name(id);
-        let item_component = CD_CLIENT.object_item_component(id)?;
+        let explorer_url = cdclient.object_explorer_url(id);
+        let name = cdclient.req_object_name(id);
+        let item_component = cdclient.object_item_component(id)?;
 
-        let mut embed = CONFIG
+        let mut embed = ItemCommand
+            .config()
             .default_embed()
             .title(format!("{} [{}]", name, id))
             .url(explorer_url);
 
-        if let Some(icon_url) = CD_CLIENT.object_icon_url(id) {
+        if let Some(icon_url) = cdclient.object_icon_url(id) {
             embed = embed.thumbnail(icon_url);
         }
 
-        Ok((embed, None))
+        Ok(embed)
     }
 }
@@ -0,0 +1,159 @@
+use crate::data;
+use crate::error::BotError;
+use crate::interaction_command::{CommandResult, CustomIdOptions, InteractionCommand, ToCustomId};
+use crate::response::{ActionRow, ButtonStyle};
+use crate::settings;
+use crate::{scope, Api};
+use serenity::all::{
+    AutocompleteChoice, CommandOptionType, CreateCommandOption, ResolvedOption, ResolvedValue,
+};
+
+pub struct LanguageCommand;
+impl Api for LanguageCommand {}
+
+pub struct LanguageArguments {
+    pub locale: Option<String>,
+    pub reset: bool,
+}
+
+impl ToCustomId for LanguageArguments {
+    const CMD: &'static str = LanguageCommand::NAME;
+
+    fn parameters(&self) -> String {
+        let LanguageArguments { locale, reset } = self;
+        match locale {
+            Some(locale) => format!("locale={locale}&reset={reset}"),
+            None => format!("reset={reset}"),
+        }
+    }
+
+    fn longest() -> Self {
+        Self {
+            // locales in locale.xml are like `en_US`, this leaves room for longer ones
+            locale: Some("x".repeat(16)),
+            reset: false,
+        }
+    }
+}
+
+impl TryFrom<&CustomIdOptions> for LanguageArguments {
+    type Error = BotError;
+
+    fn try_from(options: &CustomIdOptions) -> Result<Self, Self::Error> {
+        Ok(LanguageArguments {
+            locale: options.get("locale").ok().map(String::from),
+            reset: options.parse("reset")?,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [ResolvedOption<'a>]> for LanguageArguments {
+    type Error = BotError;
+
+    fn try_from(options: &'a [ResolvedOption<'a>]) -> Result<Self, Self::Error> {
+        let locale = options.iter().find_map(|opt| match opt.value {
+            ResolvedValue::String(value) if opt.name == "locale" => Some(value.to_string()),
+            _ => None,
+        });
+        Ok(LanguageArguments {
+            locale,
+            reset: false,
+        })
+    }
+}
+
+impl InteractionCommand for LanguageCommand {
+    const NAME: &'static str = "language";
+
+    const DESCRIPTION: &'static str = "Choose the language names and descriptions are shown in!";
+
+    fn options() -> Option<Vec<CreateCommandOption>> {
+        Some(vec![CreateCommandOption::new(
+            CommandOptionType::String,
+            "locale",
+            "A locale from the game, ex: de_DE.",
+        )
+        .set_autocomplete(true)])
+    }
+
+    type Arguments = LanguageArguments;
+
+    fn handle_autocomplete(
+        autocomplete_option: serenity::model::prelude::AutocompleteOption<'_>,
+    ) -> Option<Vec<AutocompleteChoice>> {
+        let input = autocomplete_option.value.to_lowercase();
+        let choices = data::current()
+            .locale_names()
+            .into_iter()
+            .filter(|name| name.to_lowercase().contains(&input))
+            .take(25)
+            .map(|name| AutocompleteChoice::new(name.clone(), name))
+            .collect();
+        Some(choices)
+    }
+
+    fn run(arguments: Self::Arguments) -> CommandResult {
+        let LanguageArguments { locale, reset } = arguments;
+        let scope =
+            scope::current().ok_or_else(|| BotError::internal("No user to set a language for"))?;
+        let data = data::current();
+
+        let description = if reset {
+            settings::set_user_locale(scope.user_id, None)?;
+            String::from("Names and descriptions now follow your Discord language.")
+        } else if let Some(locale) = locale {
+            let locale = data.match_locale(&locale).ok_or_else(|| {
+                format!(
+                    "`{locale}` is not a locale, expected one of: {}",
+                    data.locale_names()
+                        .iter()
+                        .map(|name| format!("`{name}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })?;
+            settings::set_user_locale(scope.user_id, Some(locale.clone()))?;
+            format!("Names and descriptions are now shown in `{locale}`.")
+        } else {
+            format!("Names and descriptions are shown in `{}`.", scope.locale)
+        };
+
+        let preference = settings::user_locale(scope.user_id);
+
+        let embed = LanguageCommand
+            .config()
+            .default_embed()
+            .title("Language")
+            .description(description)
+            .field(
+                "Your Preference",
+                match preference {
+                    Some(ref locale) => format!("`{locale}`"),
+                    None => String::from("None (your Discord language is used)"),
+                },
+                false,
+            )
+            .field(
+                "Available",
+                data.locale_names()
+                    .iter()
+                    .map(|name| format!("`{name}`"))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                false,
+            );
+
+        let mut components = vec![];
+        if preference.is_some() {
+            let reset_button = LanguageArguments {
+                locale: None,
+                reset: true,
+            }
+            .to_update_button("Use Discord Language")
+            .style(ButtonStyle::Danger);
+            components.push(ActionRow::Buttons(vec![reset_button]));
+        }
+
+        Ok(embed.components(components))
+    }
+}
@@ -1,84 +1,70 @@
-use crate::interaction_command::{CommandResult, CustomIdOptions, InteractionCommand, ToCustomId};
-use crate::{int_option, CD_CLIENT, CONFIG};
-use serenity::all::{
-    CommandOptionType, CreateActionRow, CreateCommandOption, CreateEmbed, ResolvedOption,
-};
+use crate::arguments::CommandArguments;
+use crate::error::BotError;
+use crate::interaction_command::{CommandResult, InteractionCommand, ToCustomId};
+use crate::response::ActionRow;
+use crate::Api;
+use serenity::all::CreateCommandOption;
 
 pub struct LevelCommand;
+impl Api for LevelCommand {}
 
+#[derive(CommandArguments)]
+#[arguments(command = LevelCommand)]
 pub struct LevelArguments {
+    /// A level in LEGO Universe.
+    #[option(required)]
     level: i32,
 }
 
-impl ToCustomId for LevelArguments {
-    const CMD: &'static str = LevelCommand::NAME;
-
-    fn parameters(&self) -> String {
-        let LevelArguments { level } = self;
-        format!("level={level}")
-    }
-}
-
-impl TryFrom<&CustomIdOptions> for LevelArguments {
-    type Error = String;
-
-    fn try_from(options: &CustomIdOptions) -> Result<Self, Self::Error> {
-        Ok(LevelArguments {
-            level: options.parse("level")?,
-        })
-    }
-}
-
-impl<'a> TryFrom<&'a [ResolvedOption<'a>]> for LevelArguments {
-    type Error = String;
-
-    fn try_from(options: &'a [ResolvedOption<'a>]) -> Result<Self, Self::Error> {
-        Ok(LevelArguments {
-            level: int_option!(options, "level"),
-        })
-    }
-}
-
 impl InteractionCommand for LevelCommand {
     const NAME: &'static str = "level";
 
     const DESCRIPTION: &'static str = "View stats about a level in LEGO Universe!";
 
     fn options() -> Option<Vec<CreateCommandOption>> {
-        Some(vec![CreateCommandOption::new(
-            CommandOptionType::Integer,
-            "level",
-            "A level in LEGO Universe.",
-        )
-        .required(true)])
+        Some(LevelArguments::options())
     }
 
     type Arguments = LevelArguments;
 
+    fn examples() -> Vec<Self::Arguments> {
+        vec![LevelArguments { level: 10 }]
+    }
+
     fn run(arguments: Self::Arguments) -> CommandResult {
+        let cdclient = LevelCommand.cdclient();
         let LevelArguments { level } = arguments;
 
-        let progression = CD_CLIENT
+        let progression = cdclient
             .level_progression_lookup
             .at_key(&level)
-            .ok_or_else(|| format!("Level `{level}` does not exist!"))?;
+            .ok_or_else(|| BotError::NotFound(format!("Level `{level}` does not exist!")))?;
 
         let total_experience = progression.required_uscore;
-        let experience_for_previous_level = CD_CLIENT
+        let experience_for_previous_level = cdclient
             .level_progression_lookup
             .at_key(&(level - 1))
             .map(|l| l.required_uscore)
             .unwrap_or(0);
         let experience_from_previous_level = total_experience - experience_for_previous_level;
 
-        let min_level = CD_CLIENT.level_progression_lookup.first().unwrap().id;
-        let max_level = CD_CLIENT.level_progression_lookup.last().unwrap().id;
+        let min_level = cdclient.level_progression_lookup.first().unwrap().id;
+        let max_level = cdclient.level_progression_lookup.last().unwrap().id;
 
-        let embed = CONFIG
+        let embed = LevelCommand
+            .config()
             .default_embed()
             .title(format!("Level {level}!"))
-            .url(CONFIG.explorer_uri("/misc/level-progression"))
-            .thumbnail(CONFIG.explorer_uri("/lu-res/ui/ingame/passport_i90.png"))
+            .url(
+                LevelCommand
+                    .config()
+                    .explorer_uri("/misc/level-progression"),
+            )
+            .thumbnail(
+                LevelCommand
+                    .config()
+                    .explorer_uri("/lu-res/ui/ingame/passport_i90.png"),
+            )
             .field("Requirements", format!("**For Level {level}:**"), true)
             .field(
                 format!("From Level {}", level - 1),
@@ -94,11 +80,11 @@ impl InteractionCommand for LevelCommand {
             .to_update_button(format!("Level {}", level + 1))
             .disabled(level + 1 > max_level);
 
-        let components = Some(vec![CreateActionRow::Buttons(vec![
+        let components = vec![ActionRow::Buttons(vec![
             prev_level_button,
             next_level_button,
-        ])]);
+        ])];
 
-        Ok((embed, components))
+        Ok(embed.components(components))
     }
 }
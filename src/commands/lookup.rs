@@ -0,0 +1,110 @@
+use crate::analytics::Lookup;
+use crate::commands::item::{ItemArguments, ItemCommand};
+use crate::commands::mission::{MissionArguments, MissionCommand};
+use crate::commands::skill::{SkillArguments, SkillCommand};
+use crate::error::BotError;
+use crate::ids::fmt_name_id;
+use crate::interaction_command::{CommandResult, InteractionCommand, MessageCommand, ToCustomId};
+use crate::queries::{AchievementQueries, ObjectQueries, SkillQueries};
+use crate::references::{self, ExplorerUrls};
+use crate::response::{ActionRow, SelectMenu, SelectOption};
+use crate::Api;
+
+pub struct LookupCommand;
+impl Api for LookupCommand {}
+
+impl LookupCommand {
+    /// The card of an item, or what every object has for the rest, ex: enemies
+    pub fn object_card(id: i32) -> CommandResult {
+        ItemCommand::run(ItemArguments { item: id }).or_else(|_| {
+            let cdclient = LookupCommand.cdclient();
+            let mut embed = LookupCommand
+                .config()
+                .default_embed()
+                .title(format!("{} [{}]", cdclient.req_object_name(id), id))
+                .url(cdclient.object_explorer_url(id));
+            if let Some(icon_url) = cdclient.object_icon_url(id) {
+                embed = embed.thumbnail(icon_url);
+            }
+            Ok(embed)
+        })
+    }
+
+    /// The card of what a message refers to, for this command and for inline lookups alike
+    pub fn card(lookup: Lookup, id: i32) -> CommandResult {
+        match lookup {
+            Lookup::Object => LookupCommand::object_card(id),
+            Lookup::Mission => MissionCommand::run(MissionArguments { mission: id }),
+            Lookup::Skill => SkillCommand::run(SkillArguments { skill: id }),
+        }
+    }
+
+    fn hyperlinked_name(lookup: Lookup, id: i32) -> String {
+        let cdclient = LookupCommand.cdclient();
+        match lookup {
+            Lookup::Object => cdclient.object_hyperlinked_name(id),
+            Lookup::Mission => cdclient.achievement_hyperlinked_name(id),
+            Lookup::Skill => cdclient.skill_hyperlinked_name(id),
+        }
+    }
+
+    /// Choosing it replies with its card
+    fn select_option(lookup: Lookup, id: i32) -> SelectOption {
+        let cdclient = LookupCommand.cdclient();
+        match lookup {
+            Lookup::Object => ItemArguments { item: id }.into(),
+            Lookup::Mission => SelectOption::new(
+                fmt_name_id(cdclient.req_achievement_name(id), id),
+                MissionArguments { mission: id }.to_custom_id(true),
+            ),
+            Lookup::Skill => SelectOption::new(
+                fmt_name_id(cdclient.req_skill_name(id), id),
+                SkillArguments { skill: id }.to_custom_id(true),
+            ),
+        }
+    }
+}
+
+impl MessageCommand for LookupCommand {
+    const NAME: &'static str = "lookup";
+
+    const LABEL: &'static str = "Look up in Crab Force";
+
+    fn run(text: &str) -> CommandResult {
+        let cdclient = LookupCommand.cdclient();
+        let found = references::scan(text, &ExplorerUrls::of(&cdclient));
+        let found = references::resolve(&cdclient, found);
+
+        match found.as_slice() {
+            [] => Err(BotError::NotFound(String::from(
+                "This message has no LOT numbers, explorer links or `[[Item Name]]`s of anything in LEGO Universe!",
+            ))),
+            [(lookup, id)] => LookupCommand::card(*lookup, *id),
+            _ => {
+                let lines: Vec<_> = found
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, (lookup, id))| {
+                        format!(
+                            "**{}.** {}",
+                            idx + 1,
+                            LookupCommand::hyperlinked_name(*lookup, *id)
+                        )
+                    })
+                    .collect();
+                let options = found
+                    .iter()
+                    .map(|(lookup, id)| LookupCommand::select_option(*lookup, *id))
+                    .collect();
+                let menu = SelectMenu::new(LookupCommand::NAME, options)
+                    .placeholder("View one of them");
+                Ok(LookupCommand
+                    .config()
+                    .default_embed()
+                    .title("Found in this Message")
+                    .description(lines.join("\n"))
+                    .row(ActionRow::SelectMenu(menu)))
+            }
+        }
+    }
+}
@@ -1,56 +1,27 @@
-use crate::interaction_command::{CommandResult, CustomIdOptions, InteractionCommand, ToCustomId};
-use crate::queries::{AutocompleteQueries, ObjectQueries};
-use crate::{int_option, CD_CLIENT, CONFIG, LOCALE_XML};
-use serenity::all::{AutocompleteChoice, CommandOptionType, CreateCommandOption, ResolvedOption};
+use crate::arguments::CommandArguments;
+use crate::interaction_command::{CommandResult, InteractionCommand};
+use crate::queries::ObjectQueries;
+use crate::Api;
+use serenity::all::CreateCommandOption;
 
 pub struct LootTableCommand;
+impl Api for LootTableCommand {}
 
+#[derive(CommandArguments)]
+#[arguments(command = LootTableCommand)]
 pub struct LootTableArguments {
+    /// A loot table in LEGO Universe.
+    #[option(required, autocomplete = "object")]
     table: i32,
 }
 
-impl ToCustomId for LootTableArguments {
-    const CMD: &'static str = LootTableCommand::NAME;
-
-    fn parameters(&self) -> String {
-        let LootTableArguments { table } = self;
-        format!("table={table}")
-    }
-}
-
-impl TryFrom<&CustomIdOptions> for LootTableArguments {
-    type Error = String;
-
-    fn try_from(options: &CustomIdOptions) -> Result<Self, Self::Error> {
-        Ok(LootTableArguments {
-            table: options.parse("table")?,
-        })
-    }
-}
-
-impl<'a> TryFrom<&'a [ResolvedOption<'a>]> for LootTableArguments {
-    type Error = String;
-
-    fn try_from(options: &'a [ResolvedOption<'a>]) -> Result<Self, Self::Error> {
-        Ok(LootTableArguments {
-            table: int_option!(options, "table"),
-        })
-    }
-}
-
 impl InteractionCommand for LootTableCommand {
     const NAME: &'static str = "table";
 
     const DESCRIPTION: &'static str = "View the stats of a loot table!";
 
     fn options() -> Option<Vec<CreateCommandOption>> {
-        Some(vec![CreateCommandOption::new(
-            CommandOptionType::Integer,
-            "table",
-            "A loot table in LEGO Universe.",
-        )
-        .required(true)
-        .set_autocomplete(true)])
+        Some(LootTableArguments::options())
     }
 
     type Arguments = LootTableArguments;
@@ -58,26 +29,27 @@ impl InteractionCommand for LootTableCommand {
     fn handle_autocomplete(
         autocomplete_option: serenity::model::prelude::AutocompleteOption<'_>,
     ) -> Option<Vec<serenity::all::AutocompleteChoice>> {
-        let input = autocomplete_option.value;
-        Some(CD_CLIENT.autocomplete_object(input))
+        LootTableArguments::autocomplete(autocomplete_option)
     }
 
     fn run(arguments: Self::Arguments) -> CommandResult {
+        let cdclient = LootTableCommand.cdclient();
         let LootTableArguments { table: id } = arguments;
 
-        let explorer_url = CD_CLIENT.object_explorer_url(id);
-        let name = CD_CLIENT.req_object_name(id);
-        let item_component = CD_CLIENT.object_item_component(id)?;
+        let explorer_url = cdclient.object_explorer_url(id);
+        let name = cdclient.req_object_name(id);
+        let item_component = cdclient.object_item_component(id)?;
 
-        let mut embed = CONFIG
+        let mut embed = LootTableCommand
+            .config()
             .default_embed()
             .title(format!("{} [{}]", name, id))
             .url(explorer_url);
 
-        if let Some(icon_url) = CD_CLIENT.object_icon_url(id) {
+        if let Some(icon_url) = cdclient.object_icon_url(id) {
             embed = embed.thumbnail(icon_url);
         }
 
-        Ok((embed, None))
+        Ok(embed)
     }
 }
@@ -7,17 +7,103 @@ pub mod drop;
 pub mod earn;
 pub mod enemy;
 pub mod get;
+pub mod help;
 pub mod item;
+pub mod language;
 pub mod level;
+pub mod lookup;
 pub mod loottable;
 pub mod mission;
 pub mod npc;
 pub mod package;
 pub mod preconditions;
+pub mod reload;
 pub mod reward;
+pub mod settings;
 pub mod skill;
 pub mod skillitems;
 pub mod skills;
 pub mod smash;
+pub mod stats;
+pub mod status;
 pub mod unpack;
 pub mod vendor;
+
+use once_cell::sync::Lazy;
+
+use crate::registry::{CommandGroup, CommandRegistry};
+
+use achievement::AchievementCommand;
+use activity::ActivityCommand;
+use brick::BrickCommand;
+use buy::BuyCommand;
+use cooldowngroup::CooldownGroupCommand;
+use drop::DropCommand;
+use earn::EarnCommand;
+use enemy::EnemyCommand;
+use get::GetCommand;
+use help::HelpCommand;
+use item::ItemCommand;
+use language::LanguageCommand;
+use level::LevelCommand;
+use lookup::LookupCommand;
+use loottable::LootTableCommand;
+use mission::MissionCommand;
+use npc::NpcCommand;
+use package::PackageCommand;
+use preconditions::PreconditionsCommand;
+use reload::ReloadCommand;
+use reward::RewardCommand;
+use settings::SettingsCommand;
+use skill::SkillCommand;
+use skillitems::SkillItemsCommand;
+use skills::SkillsCommand;
+use smash::SmashCommand;
+use stats::StatsCommand;
+use status::StatusCommand;
+use unpack::UnpackCommand;
+use vendor::VendorCommand;
+
+/// Declare each command once here, either top-level or as a subcommand of a group
+pub static COMMANDS: Lazy<CommandRegistry> = Lazy::new(|| {
+    CommandRegistry::new()
+        .with::<AchievementCommand>()
+        .with::<ActivityCommand>()
+        .with::<BrickCommand>()
+        .with::<EnemyCommand>()
+        .with::<HelpCommand>()
+        .with::<LanguageCommand>()
+        .with::<LevelCommand>()
+        .with::<LootTableCommand>()
+        .with::<MissionCommand>()
+        .with::<NpcCommand>()
+        .with::<PackageCommand>()
+        .with::<ReloadCommand>()
+        .with::<SettingsCommand>()
+        .with::<SmashCommand>()
+        .with::<StatsCommand>()
+        .with::<StatusCommand>()
+        .with::<VendorCommand>()
+        .group(
+            CommandGroup::new("item", "View an item and everywhere it comes from!")
+                .command::<ItemCommand>("info")
+                .command::<DropCommand>("drop")
+                .command::<UnpackCommand>("unpack")
+                .command::<RewardCommand>("reward")
+                .command::<BuyCommand>("buy")
+                .command::<EarnCommand>("earn")
+                .command::<GetCommand>("get")
+                .command::<SkillsCommand>("skills")
+                .command::<PreconditionsCommand>("preconditions"),
+        )
+        .group(
+            CommandGroup::new(
+                "skill",
+                "View a skill, the items that have it and its cooldown!",
+            )
+            .command::<SkillCommand>("info")
+            .command::<SkillItemsCommand>("items")
+            .command::<CooldownGroupCommand>("group"),
+        )
+        .with_message::<LookupCommand>()
+});
@@ -1,56 +1,27 @@
-use crate::interaction_command::{CommandResult, CustomIdOptions, InteractionCommand, ToCustomId};
-use crate::queries::{AutocompleteQueries, ObjectQueries};
-use crate::{int_option, CD_CLIENT, CONFIG, LOCALE_XML};
-use serenity::all::{AutocompleteChoice, CommandOptionType, CreateCommandOption, ResolvedOption};
+use crate::arguments::CommandArguments;
+use crate::interaction_command::{CommandResult, InteractionCommand};
+use crate::queries::ObjectQueries;
+use crate::Api;
+use serenity::all::CreateCommandOption;
 
 pub struct NpcCommand;
+impl Api for NpcCommand {}
 
+#[derive(CommandArguments)]
+#[arguments(command = NpcCommand)]
 pub struct NpcArguments {
+    /// An npc in LEGO Universe.
+    #[option(required, autocomplete = "object")]
     npc: i32,
 }
 
-impl ToCustomId for NpcArguments {
-    const CMD: &'static str = NpcCommand::NAME;
-
-    fn parameters(&self) -> String {
-        let NpcArguments { npc } = self;
-        format!("npc={npc}")
-    }
-}
-
-impl TryFrom<&CustomIdOptions> for NpcArguments {
-    type Error = String;
-
-    fn try_from(options: &CustomIdOptions) -> Result<Self, Self::Error> {
-        Ok(NpcArguments {
-            npc: options.parse("npc")?,
-        })
-    }
-}
-
-impl<'a> TryFrom<&'a [ResolvedOption<'a>]> for NpcArguments {
-    type Error = String;
-
-    fn try_from(options: &'a [ResolvedOption<'a>]) -> Result<Self, Self::Error> {
-        Ok(NpcArguments {
-            npc: int_option!(options, "npc"),
-        })
-    }
-}
-
 impl InteractionCommand for NpcCommand {
     const NAME: &'static str = "npc";
 
     const DESCRIPTION: &'static str = "View an npc in LEGO Universe!";
 
     fn options() -> Option<Vec<CreateCommandOption>> {
-        Some(vec![CreateCommandOption::new(
-            CommandOptionType::Integer,
-            "npc",
-            "An npc in LEGO Universe.",
-        )
-        .required(true)
-        .set_autocomplete(true)])
+        Some(NpcArguments::options())
     }
 
     type Arguments = NpcArguments;
@@ -58,26 +29,27 @@ impl InteractionCommand for NpcCommand {
     fn handle_autocomplete(
         autocomplete_option: serenity::model::prelude::AutocompleteOption<'_>,
     ) -> Option<Vec<serenity::all::AutocompleteChoice>> {
-        let input = autocomplete_option.value;
-        Some(CD_CLIENT.autocomplete_object(input))
+        NpcArguments::autocomplete(autocomplete_option)
     }
 
     fn run(arguments: Self::Arguments) -> CommandResult {
+        let cdclient = NpcCommand.cdclient();
         let NpcArguments { npc: id } = arguments;
 
-        let explorer_url = CD_CLIENT.object_explorer_url(id);
-        let name = CD_CLIENT.req_object_name(id);
-        let item_component = CD_CLIENT.object_item_component(id)?;
+        let explorer_url = cdclient.object_explorer_url(id);
+        let name = cdclient.req_object_name(id);
+        let item_component = cdclient.object_item_component(id)?;
 
-        let mut embed = CONFIG
+        let mut embed = NpcCommand
+            .config()
             .default_embed()
             .title(format!("{} [{}]", name, id))
             .url(explorer_url);
 
-        if let Some(icon_url) = CD_CLIENT.object_icon_url(id) {
+        if let Some(icon_url) = cdclient.object_icon_url(id) {
             embed = embed.thumbnail(icon_url);
         }
 
-        Ok((embed, None))
+        Ok(embed)
     }
 }
@@ -1,51 +1,26 @@
+use crate::arguments::CommandArguments;
 use crate::ids::CdClientObjectsId;
-use crate::interaction_command::{CommandResult, CustomIdOptions, InteractionCommand, ToCustomId};
-use crate::queries::{AutocompleteQueries, ObjectQueries};
-use crate::{int_option, CD_CLIENT, CONFIG, LOCALE_XML};
-use serenity::all::{
-    AutocompleteChoice, CommandOptionType, CreateCommandOption, CreateSelectMenuOption,
-    ResolvedOption,
-};
+use crate::interaction_command::{CommandResult, InteractionCommand, ToCustomId};
+use crate::queries::ObjectQueries;
+use crate::response::SelectOption;
+use crate::Api;
+use serenity::all::CreateCommandOption;
 
 pub struct PackageCommand;
+impl Api for PackageCommand {}
 
+#[derive(CommandArguments)]
+#[arguments(command = PackageCommand)]
 pub struct PackageArguments {
+    /// A package in LEGO Universe.
+    #[option(required, autocomplete = "object")]
     pub package: i32,
 }
 
-impl ToCustomId for PackageArguments {
-    const CMD: &'static str = PackageCommand::NAME;
-
-    fn parameters(&self) -> String {
-        let PackageArguments { package } = self;
-        format!("package={package}")
-    }
-}
-
-impl TryFrom<&CustomIdOptions> for PackageArguments {
-    type Error = String;
-
-    fn try_from(options: &CustomIdOptions) -> Result<Self, Self::Error> {
-        Ok(PackageArguments {
-            package: options.parse("package")?,
-        })
-    }
-}
-
-impl<'a> TryFrom<&'a [ResolvedOption<'a>]> for PackageArguments {
-    type Error = String;
-
-    fn try_from(options: &'a [ResolvedOption<'a>]) -> Result<Self, Self::Error> {
-        Ok(PackageArguments {
-            package: int_option!(options, "package"),
-        })
-    }
-}
-
-impl Into<CreateSelectMenuOption> for PackageArguments {
-    fn into(self) -> CreateSelectMenuOption {
+impl Into<SelectOption> for PackageArguments {
+    fn into(self) -> SelectOption {
         let it = CdClientObjectsId(self.package);
-        CreateSelectMenuOption::new(it.name_id(), self.to_custom_id(true))
+        SelectOption::new(it.name_id(), self.to_custom_id(true))
     }
 }
 
@@ -55,13 +30,7 @@ impl InteractionCommand for PackageCommand {
     const DESCRIPTION: &'static str = "View a package in LEGO Universe!";
 
     fn options() -> Option<Vec<CreateCommandOption>> {
-        Some(vec![CreateCommandOption::new(
-            CommandOptionType::Integer,
-            "package",
-            "An package in LEGO Universe.",
-        )
-        .required(true)
-        .set_autocomplete(true)])
+        Some(PackageArguments::options())
     }
 
     type Arguments = PackageArguments;
@@ -69,26 +38,27 @@ impl InteractionCommand for PackageCommand {
     fn handle_autocomplete(
         autocomplete_option: serenity::model::prelude::AutocompleteOption<'_>,
     ) -> Option<Vec<serenity::all::AutocompleteChoice>> {
-        let input = autocomplete_option.value;
-        Some(CD_CLIENT.autocomplete_object(input))
+        PackageArguments::autocomplete(autocomplete_option)
     }
 
     fn run(arguments: Self::Arguments) -> CommandResult {
+        let cdclient = PackageCommand.cdclient();
         let PackageArguments { package: id } = arguments;
 
-        let explorer_url = CD_CLIENT.object_explorer_url(id);
-        let name = CD_CLIENT.req_object_name(id);
-        let item_component = CD_CLIENT.object_item_component(id)?;
+        let explorer_url = cdclient.object_explorer_url(id);
+        let name = cdclient.req_object_name(id);
+        let item_component = cdclient.object_item_component(id)?;
 
-        let mut embed = CONFIG
+        let mut embed = PackageCommand
+            .config()
             .default_embed()
             .title(format!("{} [{}]", name, id))
             .url(explorer_url);
 
-        if let Some(icon_url) = CD_CLIENT.object_icon_url(id) {
+        if let Some(icon_url) = cdclient.object_icon_url(id) {
             embed = embed.thumbnail(icon_url);
         }
 
-        Ok((embed, None))
+        Ok(embed)
     }
 }
@@ -0,0 +1,81 @@
+use std::time::UNIX_EPOCH;
+
+use crate::commands::status::StatusArguments;
+use crate::data;
+use crate::error::{BotError, CorrelationId};
+use crate::interaction_command::{CommandResult, CustomIdOptions, InteractionCommand, ToCustomId};
+use crate::response::ActionRow;
+use crate::Api;
+use serenity::all::ResolvedOption;
+
+pub struct ReloadCommand;
+impl Api for ReloadCommand {}
+
+pub struct ReloadArguments;
+
+impl ToCustomId for ReloadArguments {
+    const CMD: &'static str = ReloadCommand::NAME;
+
+    fn parameters(&self) -> String {
+        String::new()
+    }
+
+    fn longest() -> Self {
+        Self
+    }
+}
+
+impl TryFrom<&CustomIdOptions> for ReloadArguments {
+    type Error = BotError;
+
+    fn try_from(_: &CustomIdOptions) -> Result<Self, Self::Error> {
+        Ok(ReloadArguments)
+    }
+}
+
+impl<'a> TryFrom<&'a [ResolvedOption<'a>]> for ReloadArguments {
+    type Error = BotError;
+
+    fn try_from(_: &'a [ResolvedOption<'a>]) -> Result<Self, Self::Error> {
+        Ok(ReloadArguments)
+    }
+}
+
+impl InteractionCommand for ReloadCommand {
+    const NAME: &'static str = "reload";
+
+    const DESCRIPTION: &'static str = "Reload cdclient.sqlite and locale.xml without restarting!";
+
+    const OWNER_ONLY: bool = true;
+
+    type Arguments = ReloadArguments;
+
+    fn run(_: Self::Arguments) -> CommandResult {
+        let loaded_at = data::current()
+            .loaded_at
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_secs())
+            .unwrap_or(0);
+
+        // loading takes a while, so the swap happens in the background
+        let id = CorrelationId::new();
+        data::spawn_reload(id).map_err(|e| BotError::BadArgument(format!("{e}!")))?;
+
+        let config = ReloadCommand.config();
+        let embed = config
+            .default_embed()
+            .title("Reloading")
+            .description(format!(
+                "Loading `{}` and `{}` in the background.\nCommands keep using the current data until both have loaded.\n`/status` shows how it went.",
+                config.cdclient_path.display(),
+                config.locale_path.display()
+            ))
+            .field("Current Data Loaded", format!("<t:{loaded_at}:R>"), true)
+            .field("Reload Id", format!("`{id}`"), true)
+            .row(ActionRow::Buttons(vec![
+                StatusArguments.to_update_button("Check Status")
+            ]));
+
+        Ok(embed)
+    }
+}
@@ -1,12 +1,11 @@
+use crate::arguments::CommandArguments;
 use crate::ids::CdClientObjectsId;
-use crate::interaction_command::{CommandResult, CustomIdOptions, InteractionCommand, ToCustomId};
-use crate::pager::{Pager, START_PAGE};
-use crate::queries::{AutocompleteQueries, ObjectQueries};
-use crate::{int_option, CD_CLIENT, CONFIG, LOCALE_XML};
-use serenity::all::{
-    AutocompleteChoice, CommandOptionType, CreateActionRow, CreateCommandOption, CreateSelectMenu,
-    CreateSelectMenuKind, CreateSelectMenuOption, ResolvedOption,
-};
+use crate::interaction_command::{CommandResult, InteractionCommand, ToCustomId};
+use crate::pager::{Pager, DEFAULT_PAGE_SIZE, START_PAGE};
+use crate::queries::ObjectQueries;
+use crate::response::{ActionRow, SelectMenu, SelectOption};
+use crate::Api;
+use serenity::all::CreateCommandOption;
 
 use super::activity::ActivityArguments;
 use super::buy::BuyArguments;
@@ -16,41 +15,18 @@ use super::package::PackageCommand;
 use super::unpack::UnpackArguments;
 
 pub struct RewardCommand;
+impl Api for RewardCommand {}
 
+#[derive(CommandArguments)]
+#[arguments(command = RewardCommand)]
 pub struct RewardArguments {
+    /// An item in LEGO Universe.
+    #[option(required, autocomplete = "object")]
     pub item: i32,
+    #[page]
     pub page: usize,
-}
-
-impl ToCustomId for RewardArguments {
-    const CMD: &'static str = RewardCommand::NAME;
-
-    fn parameters(&self) -> String {
-        let RewardArguments { item, page } = self;
-        vec![format!("item={item}"), format!("page={page}")].join("&")
-    }
-}
-
-impl TryFrom<&CustomIdOptions> for RewardArguments {
-    type Error = String;
-
-    fn try_from(options: &CustomIdOptions) -> Result<Self, Self::Error> {
-        Ok(RewardArguments {
-            item: options.parse("item")?,
-            page: options.parse("page")?,
-        })
-    }
-}
-
-impl<'a> TryFrom<&'a [ResolvedOption<'a>]> for RewardArguments {
-    type Error = String;
-
-    fn try_from(options: &'a [ResolvedOption<'a>]) -> Result<Self, Self::Error> {
-        Ok(RewardArguments {
-            item: int_option!(options, "item"),
-            page: START_PAGE,
-        })
-    }
+    #[custom_id(default = DEFAULT_PAGE_SIZE)]
+    pub size: usize,
 }
 
 impl InteractionCommand for RewardCommand {
@@ -59,13 +35,7 @@ impl InteractionCommand for RewardCommand {
     const DESCRIPTION: &'static str = "View all activities that drop an item!";
 
     fn options() -> Option<Vec<CreateCommandOption>> {
-        Some(vec![CreateCommandOption::new(
-            CommandOptionType::Integer,
-            "item",
-            "An item in LEGO Universe.",
-        )
-        .required(true)
-        .set_autocomplete(true)])
+        Some(RewardArguments::options())
     }
 
     type Arguments = RewardArguments;
@@ -73,12 +43,16 @@ impl InteractionCommand for RewardCommand {
     fn handle_autocomplete(
         autocomplete_option: serenity::model::prelude::AutocompleteOption<'_>,
     ) -> Option<Vec<serenity::all::AutocompleteChoice>> {
-        let input = autocomplete_option.value;
-        Some(CD_CLIENT.autocomplete_object(input))
+        RewardArguments::autocomplete(autocomplete_option)
     }
 
     fn run(arguments: Self::Arguments) -> CommandResult {
-        let RewardArguments { item: id, page } = arguments;
+        let cdclient = RewardCommand.cdclient();
+        let RewardArguments {
+            item: id,
+            page,
+            size,
+        } = arguments;
 
         let object = CdClientObjectsId(id);
         let name = object.req_name();
@@ -87,17 +61,18 @@ impl InteractionCommand for RewardCommand {
         // Create Embed //
         // ------------ //
 
-        let mut embed = CONFIG
+        let mut embed = RewardCommand
+            .config()
             .default_embed()
             .title(object.name_id())
             .url(object.explorer_url());
 
-        if let Some(icon_url) = CD_CLIENT.object_icon_url(id) {
+        if let Some(icon_url) = cdclient.object_icon_url(id) {
             embed = embed.thumbnail(icon_url);
         }
 
         let entries = object.activities_chances()?;
-        let pager = Pager::new(entries, page, 5);
+        let pager = Pager::new(entries, page, size);
 
         for (num, entry) in pager.this_page() {
             let field_name = format!("{}. {:.5}% for {}", num, entry.chance * 100.0, &name);
@@ -122,13 +97,13 @@ impl InteractionCommand for RewardCommand {
 
         let page = START_PAGE;
         let item = id;
-        let earn_button = EarnArguments { item, page }.to_update_button("Earn");
-        let drop_button = DropArguments { item, page }.to_update_button("Drop");
-        let unpack_button = UnpackArguments { item, page }.to_update_button("Unpack");
-        let reward_button = RewardArguments { item, page }.to_self_button("Reward");
-        let buy_button = BuyArguments { item, page }.to_update_button("Buy");
+        let earn_button = EarnArguments { item, page, size }.to_update_button("Earn");
+        let drop_button = DropArguments::new(item, page, size).to_update_button("Drop");
+        let unpack_button = UnpackArguments { item, page, size }.to_update_button("Unpack");
+        let reward_button = RewardArguments { item, page, size }.to_self_button("Reward");
+        let buy_button = BuyArguments { item, page, size }.to_update_button("Buy");
 
-        components.push(CreateActionRow::Buttons(vec![
+        components.push(ActionRow::Buttons(vec![
             earn_button,
             drop_button,
             unpack_button,
@@ -140,7 +115,7 @@ impl InteractionCommand for RewardCommand {
         // Referenced Objects Row //
         // ---------------------- //
 
-        let options: Vec<CreateSelectMenuOption> = pager
+        let options: Vec<SelectOption> = pager
             .this_page()
             .into_iter()
             .flat_map(|(_, entry)| {
@@ -153,10 +128,7 @@ impl InteractionCommand for RewardCommand {
             .collect();
 
         if options.len() > 1 {
-            let value = CreateActionRow::SelectMenu(CreateSelectMenu::new(
-                PackageCommand::NAME,
-                CreateSelectMenuKind::String { options },
-            ));
+            let value = ActionRow::SelectMenu(SelectMenu::new(PackageCommand::NAME, options));
             components.push(value);
         }
 
@@ -164,27 +136,17 @@ impl InteractionCommand for RewardCommand {
         // Pagination Row //
         // -------------- //
 
-        let prev_page_button = RewardArguments {
+        components.extend(pager.row(|page| RewardArguments {
             item: id,
-            page: pager.prev(),
-        }
-        .to_update_button(format!("Page {}", pager.prev()))
-        .disabled(pager.is_first_page());
-
-        let next_page_button = RewardArguments {
+            page,
+            size,
+        }));
+        components.extend(pager.size_menu(|page, size| RewardArguments {
             item: id,
-            page: pager.next(),
-        }
-        .to_update_button(format!("Page {}", pager.next()))
-        .disabled(pager.is_last_page());
-
-        if pager.has_multiple_pages() {
-            components.push(CreateActionRow::Buttons(vec![
-                prev_page_button,
-                next_page_button,
-            ]));
-        }
+            page,
+            size,
+        }));
 
-        Ok((embed, Some(components)))
+        Ok(embed.components(components))
     }
 }
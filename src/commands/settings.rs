@@ -0,0 +1,186 @@
+use crate::error::BotError;
+use crate::interaction_command::{CommandResult, CustomIdOptions, InteractionCommand, ToCustomId};
+use crate::response::{ActionRow, ButtonStyle};
+use crate::settings::{self, GuildSetting};
+use crate::{scope, Api, CONFIG};
+use serenity::all::{
+    CommandOptionType, CreateCommand, CreateCommandOption, Permissions, ResolvedOption,
+    ResolvedValue,
+};
+
+pub struct SettingsCommand;
+impl Api for SettingsCommand {}
+
+pub struct SettingsArguments {
+    pub setting: Option<GuildSetting>,
+    /// Only given from the slash command; custom ids are too short to hold it
+    pub value: Option<String>,
+    pub reset: bool,
+}
+
+impl ToCustomId for SettingsArguments {
+    const CMD: &'static str = SettingsCommand::NAME;
+
+    fn parameters(&self) -> String {
+        let SettingsArguments { setting, reset, .. } = self;
+        match setting {
+            Some(setting) => format!("setting={setting}&reset={reset}"),
+            None => format!("reset={reset}"),
+        }
+    }
+
+    fn longest() -> Self {
+        Self {
+            setting: GuildSetting::ALL
+                .into_iter()
+                .max_by_key(|setting| setting.to_string().len()),
+            value: None,
+            reset: false,
+        }
+    }
+}
+
+impl TryFrom<&CustomIdOptions> for SettingsArguments {
+    type Error = BotError;
+
+    fn try_from(options: &CustomIdOptions) -> Result<Self, Self::Error> {
+        Ok(SettingsArguments {
+            setting: options.parse("setting").ok(),
+            value: None,
+            reset: options.parse("reset")?,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [ResolvedOption<'a>]> for SettingsArguments {
+    type Error = BotError;
+
+    fn try_from(options: &'a [ResolvedOption<'a>]) -> Result<Self, Self::Error> {
+        let string = |name: &str| {
+            options.iter().find_map(|opt| match opt.value {
+                ResolvedValue::String(value) if opt.name == name => Some(value.to_string()),
+                _ => None,
+            })
+        };
+        Ok(SettingsArguments {
+            setting: string("setting").map(|s| s.parse()).transpose()?,
+            value: string("value"),
+            reset: false,
+        })
+    }
+}
+
+impl InteractionCommand for SettingsCommand {
+    const NAME: &'static str = "settings";
+
+    const DESCRIPTION: &'static str = "View or change the settings of this server!";
+
+    fn options() -> Option<Vec<CreateCommandOption>> {
+        let mut setting = CreateCommandOption::new(
+            CommandOptionType::String,
+            "setting",
+            "The setting to change.",
+        );
+        for option in GuildSetting::ALL {
+            setting = setting.add_string_choice(option.key(), option.key());
+        }
+        Some(vec![
+            setting,
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "value",
+                "The new value of the setting.",
+            ),
+        ])
+    }
+
+    /// Hidden from members without Manage Server, and unavailable in DMs
+    fn register() -> CreateCommand {
+        let mut cmd = CreateCommand::new(Self::NAME)
+            .description(Self::DESCRIPTION)
+            .default_member_permissions(Permissions::MANAGE_GUILD)
+            .dm_permission(false);
+        for option in Self::options().unwrap_or_default() {
+            cmd = cmd.add_option(option)
+        }
+        cmd
+    }
+
+    type Arguments = SettingsArguments;
+
+    fn run(arguments: Self::Arguments) -> CommandResult {
+        let SettingsArguments {
+            setting,
+            value,
+            reset,
+        } = arguments;
+
+        let scope =
+            scope::current().ok_or_else(|| BotError::internal("No interaction to configure"))?;
+        let guild_id = scope
+            .guild_id
+            .ok_or_else(|| String::from("Settings can only be changed in a server!"))?;
+        // component interactions are not covered by the command's default permissions
+        if !scope.can_manage_guild() && !CONFIG.is_owner(scope.user_id) {
+            Err("You need the `Manage Server` permission to change settings!")?
+        }
+
+        let description = match (setting, value) {
+            (Some(setting), _) if reset => {
+                settings::set_guild_setting(guild_id, setting, None)?;
+                format!("Reset `{setting}` to the default.")
+            }
+            (Some(setting), Some(value)) => {
+                settings::set_guild_setting(guild_id, setting, Some(value))?;
+                format!("Updated `{setting}`.")
+            }
+            (Some(setting), None) => Err(format!("Give a `value` to change `{setting}` to!"))?,
+            (None, Some(_)) => Err("Choose the `setting` to change!")?,
+            (None, None) => String::from("Use `/settings setting value` to change a setting."),
+        };
+
+        // the scope still holds the settings from before this change
+        let guild_settings = settings::guild_settings(guild_id);
+        let config = guild_settings.apply(&CONFIG);
+
+        let mut embed = config
+            .default_embed()
+            .title("Server Settings")
+            .description(description);
+
+        let mut reset_buttons = vec![];
+        for option in GuildSetting::ALL {
+            let value = match guild_settings.get(option) {
+                Some(value) => format!("`{value}`"),
+                None => match option.default_value(&CONFIG) {
+                    Some(value) => format!("`{value}` (default)"),
+                    None => String::from("None (default)"),
+                },
+            };
+            embed = embed.field(
+                option.key(),
+                format!("{}\n{value}", option.description()),
+                false,
+            );
+
+            if guild_settings.get(option).is_some() {
+                let button = SettingsArguments {
+                    setting: Some(option),
+                    value: None,
+                    reset: true,
+                }
+                .to_update_button(format!("Reset {option}"))
+                .style(ButtonStyle::Danger);
+                reset_buttons.push(button);
+            }
+        }
+
+        // Discord shows at most 5 buttons in a row
+        let components = reset_buttons
+            .chunks(5)
+            .map(|buttons| ActionRow::Buttons(buttons.to_vec()))
+            .collect();
+
+        Ok(embed.components(components))
+    }
+}
@@ -1,41 +1,18 @@
-use crate::interaction_command::{CommandResult, CustomIdOptions, InteractionCommand, ToCustomId};
-use crate::queries::{AutocompleteQueries, LocaleQueries, ObjectQueries, SkillQueries};
-use crate::{int_option, CD_CLIENT, CONFIG, LOCALE_XML};
-use serenity::all::{AutocompleteChoice, CommandOptionType, CreateCommandOption, ResolvedOption};
+use crate::arguments::CommandArguments;
+use crate::interaction_command::{CommandResult, InteractionCommand};
+use crate::queries::{ObjectQueries, SkillQueries};
+use crate::Api;
+use serenity::all::CreateCommandOption;
 
 pub struct SkillCommand;
+impl Api for SkillCommand {}
 
+#[derive(CommandArguments)]
+#[arguments(command = SkillCommand)]
 pub struct SkillArguments {
-    skill: i32,
-}
-
-impl ToCustomId for SkillArguments {
-    const CMD: &'static str = SkillCommand::NAME;
-
-    fn parameters(&self) -> String {
-        let SkillArguments { skill } = self;
-        format!("skill={skill}")
-    }
-}
-
-impl TryFrom<&CustomIdOptions> for SkillArguments {
-    type Error = String;
-
-    fn try_from(options: &CustomIdOptions) -> Result<Self, Self::Error> {
-        Ok(SkillArguments {
-            skill: options.parse("skill")?,
-        })
-    }
-}
-
-impl<'a> TryFrom<&'a [ResolvedOption<'a>]> for SkillArguments {
-    type Error = String;
-
-    fn try_from(options: &'a [ResolvedOption<'a>]) -> Result<Self, Self::Error> {
-        Ok(SkillArguments {
-            skill: int_option!(options, "skill"),
-        })
-    }
+    /// A skill in LEGO Universe.
+    #[option(required, autocomplete = "skill")]
+    pub skill: i32,
 }
 
 impl InteractionCommand for SkillCommand {
@@ -44,13 +21,7 @@ impl InteractionCommand for SkillCommand {
     const DESCRIPTION: &'static str = "View a skill in LEGO Universe!";
 
     fn options() -> Option<Vec<CreateCommandOption>> {
-        Some(vec![CreateCommandOption::new(
-            CommandOptionType::Integer,
-            "skill",
-            "A skill in LEGO Universe.",
-        )
-        .required(true)
-        .set_autocomplete(true)])
+        Some(SkillArguments::options())
     }
 
     type Arguments = SkillArguments;
@@ -58,18 +29,19 @@ impl InteractionCommand for SkillCommand {
     fn handle_autocomplete(
         autocomplete_option: serenity::model::prelude::AutocompleteOption<'_>,
     ) -> Option<Vec<serenity::all::AutocompleteChoice>> {
-        let input = autocomplete_option.value;
-        Some(CD_CLIENT.autocomplete_skill(input))
+        SkillArguments::autocomplete(autocomplete_option)
     }
 
     fn run(arguments: Self::Arguments) -> CommandResult {
+        let cdclient = SkillCommand.cdclient();
         let SkillArguments { skill: id } = arguments;
 
-        let skill = CD_CLIENT.get_skill(id)?;
-        let explorer_url = CD_CLIENT.skill_explorer_url(id);
-        let name = CD_CLIENT.req_skill_name(id);
+        let skill = cdclient.get_skill(id)?;
+        let explorer_url = cdclient.skill_explorer_url(id);
+        let name = cdclient.req_skill_name(id);
 
-        let mut embed = CONFIG
+        let mut embed = SkillCommand
+            .config()
             .default_embed()
             .title(format!("{} [{}]", name, id))
             .url(explorer_url);
@@ -77,7 +49,7 @@ impl InteractionCommand for SkillCommand {
         if let Some(cdg) = skill.cooldowngroup {
             embed = embed.field(
                 "Cooldown Group",
-                CD_CLIENT.cooldown_group_hyperlinked_name(cdg),
+                cdclient.cooldown_group_hyperlinked_name(cdg),
                 true,
             );
         }
@@ -92,7 +64,7 @@ impl InteractionCommand for SkillCommand {
             true,
         );
 
-        if let Some(locale) = CD_CLIENT.locale().skill_behavior.get(&id) {
+        if let Some(locale) = cdclient.locale().skill_behavior.get(&id) {
             if let Some(desc) = &locale.description_ui {
                 if let Some(segments) = &desc.segments {
                     for (header, value) in segments {
@@ -106,10 +78,10 @@ impl InteractionCommand for SkillCommand {
             }
         }
 
-        if let Some(icon_url) = CD_CLIENT.skill_icon_url(id) {
+        if let Some(icon_url) = cdclient.skill_icon_url(id) {
             embed = embed.thumbnail(icon_url);
         }
 
-        Ok((embed, None))
+        Ok(embed)
     }
 }
@@ -1,41 +1,24 @@
-use crate::interaction_command::{CommandResult, CustomIdOptions, InteractionCommand, ToCustomId};
-use crate::queries::{AutocompleteQueries, ObjectQueries, SkillQueries};
-use crate::{int_option, CD_CLIENT, CONFIG, LOCALE_XML};
-use serenity::all::{AutocompleteChoice, CommandOptionType, CreateCommandOption, ResolvedOption};
+use crate::arguments::CommandArguments;
+use crate::commands::item::ItemArguments;
+use crate::interaction_command::{CommandResult, InteractionCommand};
+use crate::pager::{Pager, DEFAULT_PAGE_SIZE};
+use crate::queries::{ObjectQueries, SkillQueries};
+use crate::Api;
+use serenity::all::CreateCommandOption;
 
 pub struct SkillItemsCommand;
+impl Api for SkillItemsCommand {}
 
+#[derive(CommandArguments)]
+#[arguments(command = SkillItemsCommand)]
 pub struct SkillItemsArguments {
-    skill: i32,
-}
-
-impl ToCustomId for SkillItemsArguments {
-    const CMD: &'static str = SkillItemsCommand::NAME;
-
-    fn parameters(&self) -> String {
-        let SkillItemsArguments { skill } = self;
-        format!("skill={skill}")
-    }
-}
-
-impl TryFrom<&CustomIdOptions> for SkillItemsArguments {
-    type Error = String;
-
-    fn try_from(options: &CustomIdOptions) -> Result<Self, Self::Error> {
-        Ok(SkillItemsArguments {
-            skill: options.parse("skill")?,
-        })
-    }
-}
-
-impl<'a> TryFrom<&'a [ResolvedOption<'a>]> for SkillItemsArguments {
-    type Error = String;
-
-    fn try_from(options: &'a [ResolvedOption<'a>]) -> Result<Self, Self::Error> {
-        Ok(SkillItemsArguments {
-            skill: int_option!(options, "skill"),
-        })
-    }
+    /// A skill in LEGO Universe.
+    #[option(required, autocomplete = "skill")]
+    pub skill: i32,
+    #[page]
+    pub page: usize,
+    #[custom_id(default = DEFAULT_PAGE_SIZE)]
+    pub size: usize,
 }
 
 impl InteractionCommand for SkillItemsCommand {
@@ -44,13 +27,7 @@ impl InteractionCommand for SkillItemsCommand {
     const DESCRIPTION: &'static str = "View all items that have a skill!";
 
     fn options() -> Option<Vec<CreateCommandOption>> {
-        Some(vec![CreateCommandOption::new(
-            CommandOptionType::Integer,
-            "skill",
-            "An skill in LEGO Universe.",
-        )
-        .required(true)
-        .set_autocomplete(true)])
+        Some(SkillItemsArguments::options())
     }
 
     type Arguments = SkillItemsArguments;
@@ -58,50 +35,70 @@ impl InteractionCommand for SkillItemsCommand {
     fn handle_autocomplete(
         autocomplete_option: serenity::model::prelude::AutocompleteOption<'_>,
     ) -> Option<Vec<serenity::all::AutocompleteChoice>> {
-        let input = autocomplete_option.value;
-        Some(CD_CLIENT.autocomplete_skill(input))
+        SkillItemsArguments::autocomplete(autocomplete_option)
     }
 
     fn run(arguments: Self::Arguments) -> CommandResult {
-        let SkillItemsArguments { skill: id } = arguments;
-
-        let skill = CD_CLIENT.get_skill(id)?;
-        let explorer_url = CD_CLIENT.skill_explorer_url(id);
-        let name = CD_CLIENT.req_skill_name(id);
-
-        let object_skills: Vec<_> = CD_CLIENT
+        let cdclient = SkillItemsCommand.cdclient();
+        let SkillItemsArguments {
+            skill: id,
+            page,
+            size,
+        } = arguments;
+
+        let skill = cdclient.get_skill(id)?;
+        let explorer_url = cdclient.skill_explorer_url(id);
+        let name = cdclient.req_skill_name(id);
+
+        let object_skills: Vec<_> = cdclient
             .object_skills
             .iter()
             .filter(|ob| ob.skill_id == id)
-            .enumerate()
-            .map(|(idx, ob)| {
-                let num = idx + 1;
-                let name = CD_CLIENT.object_hyperlinked_name(ob.object_template);
-                format!("**{num}.** {name}")
-            })
+            .map(|ob| ob.object_template)
             .collect();
+        let pager = Pager::new(object_skills, page, size);
 
-        let mut embed = CONFIG
+        let mut embed = SkillItemsCommand
+            .config()
             .default_embed()
             .title(format!("{} [{}]", name, id))
             .url(explorer_url);
 
-        if let Some(icon_url) = CD_CLIENT.skill_icon_url(id) {
+        if let Some(icon_url) = cdclient.skill_icon_url(id) {
             embed = embed.thumbnail(icon_url);
         }
 
-        match object_skills.len() {
+        let columns = pager.columns(2, |num, item| {
+            let name = cdclient.object_hyperlinked_name(item);
+            format!("**{num}.** {name}")
+        });
+        match pager.total() {
             0 => embed = embed.field("Items", "None", true),
-            1 => embed = embed.field("Item", &object_skills[0], true),
+            1 => embed = embed.field("Item", &columns[0], true),
             _ => {
-                let half = object_skills.len() / 2;
-                let col1 = object_skills[..=half].join("\n");
-                let col2 = object_skills[half + 1..].join("\n");
-                embed = embed.field("Items", col1, true);
-                embed = embed.field("Items", col2, true);
+                for column in columns {
+                    embed = embed.field("Items", column, true);
+                }
             }
         };
 
-        Ok((embed, None))
+        let mut components = vec![];
+        if pager.total() > 1 {
+            components.extend(pager.select_menu(SkillItemsCommand::NAME, |item| {
+                ItemArguments { item }.into()
+            }));
+        }
+        components.extend(pager.row(|page| SkillItemsArguments {
+            skill: id,
+            page,
+            size,
+        }));
+        components.extend(pager.size_menu(|page, size| SkillItemsArguments {
+            skill: id,
+            page,
+            size,
+        }));
+
+        Ok(embed.components(components))
     }
 }
@@ -0,0 +1,130 @@
+use crate::analytics::{self, Lookup};
+use crate::arguments::{CommandArguments, OptionChoice};
+use crate::commands::COMMANDS;
+use crate::error::BotError;
+use crate::ids::{CdClientObjectsId, CdClientSkillBehaviorId, LUExplorer};
+use crate::interaction_command::{CommandResult, InteractionCommand, ToCustomId};
+use crate::queries::AchievementQueries;
+use crate::response::ActionRow;
+use crate::Api;
+use serenity::all::CreateCommandOption;
+
+pub struct StatsCommand;
+impl Api for StatsCommand {}
+
+/// How far back `/stats` looks
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, OptionChoice)]
+pub enum Window {
+    #[choice(label = "Last 24 Hours")]
+    Day,
+    #[default]
+    #[choice(label = "Last 7 Days")]
+    Week,
+    #[choice(label = "Last 30 Days")]
+    Month,
+    #[choice(label = "All Time")]
+    All,
+}
+
+impl Window {
+    /// Unix seconds the window starts at
+    fn since(&self) -> u64 {
+        const DAY: u64 = 24 * 60 * 60;
+        match self {
+            Window::Day => analytics::since(DAY),
+            Window::Week => analytics::since(7 * DAY),
+            Window::Month => analytics::since(30 * DAY),
+            Window::All => 0,
+        }
+    }
+}
+
+#[derive(CommandArguments)]
+#[arguments(command = StatsCommand)]
+pub struct StatsArguments {
+    /// How far back to look (default: the last 7 days).
+    #[option(default = Window::default())]
+    pub window: Window,
+}
+
+/// Rows per list, so every field stays under Discord's 1024 characters
+const TOP: usize = 8;
+
+fn numbered(rows: Vec<String>) -> String {
+    if rows.is_empty() {
+        return String::from("Nothing yet.");
+    }
+    rows.into_iter()
+        .enumerate()
+        .map(|(idx, row)| format!("{}. {row}", idx + 1))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl InteractionCommand for StatsCommand {
+    const NAME: &'static str = "stats";
+
+    const DESCRIPTION: &'static str = "View the most looked up content and the busiest commands!";
+
+    fn options() -> Option<Vec<CreateCommandOption>> {
+        Some(StatsArguments::options())
+    }
+
+    type Arguments = StatsArguments;
+
+    fn examples() -> Vec<Self::Arguments> {
+        vec![StatsArguments {
+            window: Window::Month,
+        }]
+    }
+
+    fn run(arguments: Self::Arguments) -> CommandResult {
+        let StatsArguments { window } = arguments;
+        let since = window.since();
+        let cdclient = StatsCommand.cdclient();
+
+        let top = |lookup: Lookup, name: &dyn Fn(i32) -> String| -> Result<String, BotError> {
+            let rows = analytics::top_lookups(lookup, since, TOP)?
+                .into_iter()
+                .map(|(id, uses)| format!("{} ({uses})", name(id)))
+                .collect();
+            Ok(numbered(rows))
+        };
+        let objects = top(Lookup::Object, &|id| CdClientObjectsId(id).hyperlink_name())?;
+        let missions = top(Lookup::Mission, &|id| {
+            cdclient.achievement_hyperlinked_name(id)
+        })?;
+        let skills = top(Lookup::Skill, &|id| {
+            CdClientSkillBehaviorId(id).hyperlink_name()
+        })?;
+        let commands = numbered(
+            analytics::busiest_commands(since, TOP)?
+                .into_iter()
+                .map(|(command, uses)| format!("`{}` ({uses})", COMMANDS.invocation_of(&command)))
+                .collect(),
+        );
+
+        let embed = StatsCommand
+            .config()
+            .default_embed()
+            .title(format!("Stats: {}", window.label()))
+            .description("Content counts lookups that succeeded; autocomplete is never counted.")
+            .field("Objects", objects, false)
+            .field("Missions", missions, false)
+            .field("Skills", skills, false)
+            .field("Commands", commands, false);
+
+        let buttons = Window::ALL
+            .iter()
+            .map(|&option| {
+                let arguments = StatsArguments { window: option };
+                match option == window {
+                    true => arguments.to_self_button(option.label()),
+                    false => arguments.to_update_button(option.label()),
+                }
+            })
+            .collect();
+
+        Ok(embed.row(ActionRow::Buttons(buttons)))
+    }
+}
@@ -0,0 +1,133 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::data;
+use crate::error::BotError;
+use crate::interaction_command::{CommandResult, CustomIdOptions, InteractionCommand, ToCustomId};
+use crate::metrics::{self, STARTED_AT, WINDOW};
+use crate::Api;
+use serenity::all::ResolvedOption;
+
+pub struct StatusCommand;
+impl Api for StatusCommand {}
+
+pub struct StatusArguments;
+
+impl ToCustomId for StatusArguments {
+    const CMD: &'static str = StatusCommand::NAME;
+
+    fn parameters(&self) -> String {
+        String::new()
+    }
+
+    fn longest() -> Self {
+        Self
+    }
+}
+
+impl TryFrom<&CustomIdOptions> for StatusArguments {
+    type Error = BotError;
+
+    fn try_from(_: &CustomIdOptions) -> Result<Self, Self::Error> {
+        Ok(StatusArguments)
+    }
+}
+
+impl<'a> TryFrom<&'a [ResolvedOption<'a>]> for StatusArguments {
+    type Error = BotError;
+
+    fn try_from(_: &'a [ResolvedOption<'a>]) -> Result<Self, Self::Error> {
+        Ok(StatusArguments)
+    }
+}
+
+/// Keeps the table inside the embed description limit
+const MAX_ROWS: usize = 30;
+
+fn fmt_ms(duration: Duration) -> String {
+    format!("{}ms", duration.as_millis())
+}
+
+impl InteractionCommand for StatusCommand {
+    const NAME: &'static str = "status";
+
+    const DESCRIPTION: &'static str = "View the uptime and latency of each command!";
+
+    const OWNER_ONLY: bool = true;
+
+    type Arguments = StatusArguments;
+
+    fn run(_: Self::Arguments) -> CommandResult {
+        let data = data::current();
+        let loaded_at = data
+            .loaded_at
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_secs())
+            .unwrap_or(0);
+        let uptime = STARTED_AT.elapsed().as_secs();
+
+        // slowest first, since that is what this is for
+        let mut summary = metrics::summary();
+        summary.sort_by_key(|row| std::cmp::Reverse(row.p95));
+
+        let rows: Vec<_> = summary
+            .iter()
+            .take(MAX_ROWS)
+            .map(|row| {
+                format!(
+                    "{:<24} {:>6} {:>5} {:>7} {:>7}",
+                    format!("{} ({})", row.command, row.kind),
+                    row.count,
+                    row.errors,
+                    fmt_ms(row.p50),
+                    fmt_ms(row.p95)
+                )
+            })
+            .collect();
+
+        let table = if rows.is_empty() {
+            String::from("No interactions yet.")
+        } else {
+            format!(
+                "```\n{:<24} {:>6} {:>5} {:>7} {:>7}\n{}\n```\np50/p95 are over the last {WINDOW} interactions of each command.",
+                "command", "count", "err", "p50", "p95",
+                rows.join("\n")
+            )
+        };
+
+        let embed = StatusCommand
+            .config()
+            .default_embed()
+            .title("Status")
+            .description(table)
+            .field(
+                "Uptime",
+                format!("{}h {}m", uptime / 3600, uptime / 60 % 60),
+                true,
+            )
+            .field("Data Loaded", format!("<t:{loaded_at}:R>"), true)
+            .field("Locales", data.locales.len().to_string(), true);
+
+        let last_reload = data::last_reload().map(|reload| {
+            let finished_at = reload
+                .finished_at
+                .duration_since(UNIX_EPOCH)
+                .map(|time| time.as_secs())
+                .unwrap_or(0);
+            match reload.result {
+                Ok(time) => format!(
+                    "Loaded in {} <t:{finished_at}:R> (`{}`)",
+                    fmt_ms(time),
+                    reload.id
+                ),
+                Err(e) => format!("Failed <t:{finished_at}:R> (`{}`): {e}", reload.id),
+            }
+        });
+        let reloading = data::is_reloading().then(|| String::from("In progress"));
+        let embed = match reloading.or(last_reload) {
+            Some(reload) => embed.field("Last Reload", reload, false),
+            None => embed,
+        };
+
+        Ok(embed)
+    }
+}
@@ -1,13 +1,12 @@
+use crate::arguments::CommandArguments;
 use crate::cdclient::components::PACKAGE_COMPONENT;
 use crate::ids::CdClientObjectsId;
-use crate::interaction_command::{CommandResult, CustomIdOptions, InteractionCommand, ToCustomId};
-use crate::pager::{Pager, START_PAGE};
-use crate::queries::{AutocompleteQueries, LootQueries, ObjectQueries};
-use crate::{int_option, CD_CLIENT, CONFIG, LOCALE_XML};
-use serenity::all::{
-    AutocompleteChoice, CommandOptionType, CreateActionRow, CreateCommandOption, CreateSelectMenu,
-    CreateSelectMenuKind, CreateSelectMenuOption, ResolvedOption, SelectMenuOption,
-};
+use crate::interaction_command::{CommandResult, InteractionCommand, ToCustomId};
+use crate::pager::{Pager, DEFAULT_PAGE_SIZE, START_PAGE};
+use crate::queries::{LootQueries, ObjectQueries};
+use crate::response::{ActionRow, SelectMenu, SelectOption};
+use crate::Api;
+use serenity::all::{CreateCommandOption, SelectMenuOption};
 
 use super::buy::BuyArguments;
 use super::drop::DropArguments;
@@ -16,41 +15,18 @@ use super::package::{PackageArguments, PackageCommand};
 use super::reward::RewardArguments;
 
 pub struct UnpackCommand;
+impl Api for UnpackCommand {}
 
+#[derive(CommandArguments)]
+#[arguments(command = UnpackCommand)]
 pub struct UnpackArguments {
+    /// An item in LEGO Universe.
+    #[option(required, autocomplete = "object")]
     pub item: i32,
+    #[page]
     pub page: usize,
-}
-
-impl ToCustomId for UnpackArguments {
-    const CMD: &'static str = UnpackCommand::NAME;
-
-    fn parameters(&self) -> String {
-        let UnpackArguments { item, page } = self;
-        vec![format!("item={item}"), format!("page={page}")].join("&")
-    }
-}
-
-impl TryFrom<&CustomIdOptions> for UnpackArguments {
-    type Error = String;
-
-    fn try_from(options: &CustomIdOptions) -> Result<Self, Self::Error> {
-        Ok(UnpackArguments {
-            item: options.parse("item")?,
-            page: options.parse("page")?,
-        })
-    }
-}
-
-impl<'a> TryFrom<&'a [ResolvedOption<'a>]> for UnpackArguments {
-    type Error = String;
-
-    fn try_from(options: &'a [ResolvedOption<'a>]) -> Result<Self, Self::Error> {
-        Ok(UnpackArguments {
-            item: int_option!(options, "item"),
-            page: START_PAGE,
-        })
-    }
+    #[custom_id(default = DEFAULT_PAGE_SIZE)]
+    pub size: usize,
 }
 
 impl InteractionCommand for UnpackCommand {
@@ -59,13 +35,7 @@ impl InteractionCommand for UnpackCommand {
     const DESCRIPTION: &'static str = "View all packages that drop an item!";
 
     fn options() -> Option<Vec<CreateCommandOption>> {
-        Some(vec![CreateCommandOption::new(
-            CommandOptionType::Integer,
-            "item",
-            "An item in LEGO Universe.",
-        )
-        .required(true)
-        .set_autocomplete(true)])
+        Some(UnpackArguments::options())
     }
 
     type Arguments = UnpackArguments;
@@ -73,12 +43,16 @@ impl InteractionCommand for UnpackCommand {
     fn handle_autocomplete(
         autocomplete_option: serenity::model::prelude::AutocompleteOption<'_>,
     ) -> Option<Vec<serenity::all::AutocompleteChoice>> {
-        let input = autocomplete_option.value;
-        Some(CD_CLIENT.autocomplete_object(input))
+        UnpackArguments::autocomplete(autocomplete_option)
     }
 
     fn run(arguments: Self::Arguments) -> CommandResult {
-        let UnpackArguments { item: id, page } = arguments;
+        let cdclient = UnpackCommand.cdclient();
+        let UnpackArguments {
+            item: id,
+            page,
+            size,
+        } = arguments;
 
         let object = CdClientObjectsId(id);
         let name = object.req_name();
@@ -87,17 +61,18 @@ impl InteractionCommand for UnpackCommand {
         // Create Embed //
         // ------------ //
 
-        let mut embed = CONFIG
+        let mut embed = UnpackCommand
+            .config()
             .default_embed()
             .title(object.name_id())
             .url(object.explorer_url());
 
-        if let Some(icon_url) = CD_CLIENT.object_icon_url(id) {
+        if let Some(icon_url) = cdclient.object_icon_url(id) {
             embed = embed.thumbnail(icon_url);
         }
 
         let entries = object.packages_chances()?;
-        let pager = Pager::new(entries, page, 5);
+        let pager = Pager::new(entries, page, size);
 
         for (num, entry) in pager.this_page() {
             let field_name = format!("{}. {:.5}% for {}", num, entry.chance * 100.0, &name);
@@ -122,13 +97,13 @@ impl InteractionCommand for UnpackCommand {
 
         let page = START_PAGE;
         let item = id;
-        let earn_button = EarnArguments { item, page }.to_update_button("Earn");
-        let drop_button = DropArguments { item, page }.to_update_button("Drop");
-        let unpack_button = UnpackArguments { item, page }.to_self_button("Unpack");
-        let reward_button = RewardArguments { item, page }.to_update_button("Reward");
-        let buy_button = BuyArguments { item, page }.to_update_button("Buy");
+        let earn_button = EarnArguments { item, page, size }.to_update_button("Earn");
+        let drop_button = DropArguments::new(item, page, size).to_update_button("Drop");
+        let unpack_button = UnpackArguments { item, page, size }.to_self_button("Unpack");
+        let reward_button = RewardArguments { item, page, size }.to_update_button("Reward");
+        let buy_button = BuyArguments { item, page, size }.to_update_button("Buy");
 
-        components.push(CreateActionRow::Buttons(vec![
+        components.push(ActionRow::Buttons(vec![
             earn_button,
             drop_button,
             unpack_button,
@@ -140,7 +115,7 @@ impl InteractionCommand for UnpackCommand {
         // Referenced Objects Row //
         // ---------------------- //
 
-        let options: Vec<CreateSelectMenuOption> = pager
+        let options: Vec<SelectOption> = pager
             .this_page()
             .into_iter()
             .flat_map(|(_, entry)| {
@@ -153,9 +128,9 @@ impl InteractionCommand for UnpackCommand {
             .collect();
 
         if options.len() > 1 {
-            components.push(CreateActionRow::SelectMenu(CreateSelectMenu::new(
+            components.push(ActionRow::SelectMenu(SelectMenu::new(
                 PackageCommand::NAME,
-                CreateSelectMenuKind::String { options },
+                options,
             )));
         }
 
@@ -163,27 +138,17 @@ impl InteractionCommand for UnpackCommand {
         // Pagination Row //
         // -------------- //
 
-        let prev_page_button = UnpackArguments {
+        components.extend(pager.row(|page| UnpackArguments {
             item: id,
-            page: pager.prev(),
-        }
-        .to_update_button(format!("Page {}", pager.prev()))
-        .disabled(pager.is_first_page());
-
-        let next_page_button = UnpackArguments {
+            page,
+            size,
+        }));
+        components.extend(pager.size_menu(|page, size| UnpackArguments {
             item: id,
-            page: pager.next(),
-        }
-        .to_update_button(format!("Page {}", pager.next()))
-        .disabled(pager.is_last_page());
-
-        if pager.has_multiple_pages() {
-            components.push(CreateActionRow::Buttons(vec![
-                prev_page_button,
-                next_page_button,
-            ]));
-        }
+            page,
+            size,
+        }));
 
-        Ok((embed, Some(components)))
+        Ok(embed.components(components))
     }
 }
@@ -1,40 +1,26 @@
-use crate::interaction_command::{CommandResult, CustomIdOptions, InteractionCommand, ToCustomId};
-use crate::queries::{AutocompleteQueries, ObjectQueries};
-use crate::{int_option, CD_CLIENT, CONFIG, LOCALE_XML};
-use serenity::all::{AutocompleteChoice, CommandOptionType, CreateCommandOption, ResolvedOption};
+use crate::arguments::CommandArguments;
+use crate::ids::CdClientObjectsId;
+use crate::interaction_command::{CommandResult, InteractionCommand, ToCustomId};
+use crate::queries::ObjectQueries;
+use crate::response::SelectOption;
+use crate::Api;
+use serenity::all::CreateCommandOption;
 
 pub struct VendorCommand;
+impl Api for VendorCommand {}
 
+#[derive(CommandArguments)]
+#[arguments(command = VendorCommand)]
 pub struct VendorArguments {
-    vendor: i32,
+    /// A vendor in LEGO Universe.
+    #[option(required, autocomplete = "object")]
+    pub vendor: i32,
 }
 
-impl ToCustomId for VendorArguments {
-    const CMD: &'static str = VendorCommand::NAME;
-
-    fn parameters(&self) -> String {
-        let VendorArguments { vendor } = self;
-        format!("vendor={vendor}")
-    }
-}
-
-impl TryFrom<&CustomIdOptions> for VendorArguments {
-    type Error = String;
-
-    fn try_from(options: &CustomIdOptions) -> Result<Self, Self::Error> {
-        Ok(VendorArguments {
-            vendor: options.parse("vendor")?,
-        })
-    }
-}
-
-impl<'a> TryFrom<&'a [ResolvedOption<'a>]> for VendorArguments {
-    type Error = String;
-
-    fn try_from(options: &'a [ResolvedOption<'a>]) -> Result<Self, Self::Error> {
-        Ok(VendorArguments {
-            vendor: int_option!(options, "vendor"),
-        })
+impl From<VendorArguments> for SelectOption {
+    fn from(arguments: VendorArguments) -> Self {
+        let it = CdClientObjectsId(arguments.vendor);
+        SelectOption::new(it.name_id(), arguments.to_custom_id(true))
     }
 }
 
@@ -44,13 +30,7 @@ impl InteractionCommand for VendorCommand {
     const DESCRIPTION: &'static str = "View a vendor in LEGO Universe!";
 
     fn options() -> Option<Vec<CreateCommandOption>> {
-        Some(vec![CreateCommandOption::new(
-            CommandOptionType::Integer,
-            "vendor",
-            "A vendor in LEGO Universe.",
-        )
-        .required(true)
-        .set_autocomplete(true)])
+        Some(VendorArguments::options())
     }
 
     type Arguments = VendorArguments;
@@ -58,26 +38,27 @@ impl InteractionCommand for VendorCommand {
     fn handle_autocomplete(
         autocomplete_option: serenity::model::prelude::AutocompleteOption<'_>,
     ) -> Option<Vec<serenity::all::AutocompleteChoice>> {
-        let input = autocomplete_option.value;
-        Some(CD_CLIENT.autocomplete_object(input))
+        VendorArguments::autocomplete(autocomplete_option)
     }
 
     fn run(arguments: Self::Arguments) -> CommandResult {
+        let cdclient = VendorCommand.cdclient();
         let VendorArguments { vendor: id } = arguments;
 
-        let explorer_url = CD_CLIENT.object_explorer_url(id);
-        let name = CD_CLIENT.req_object_name(id);
-        let item_component = CD_CLIENT.object_item_component(id)?;
+        let explorer_url = cdclient.object_explorer_url(id);
+        let name = cdclient.req_object_name(id);
+        let item_component = cdclient.object_item_component(id)?;
 
-        let mut embed = CONFIG
+        let mut embed = VendorCommand
+            .config()
             .default_embed()
             .title(format!("{} [{}]", name, id))
             .url(explorer_url);
 
-        if let Some(icon_url) = CD_CLIENT.object_icon_url(id) {
+        if let Some(icon_url) = cdclient.object_icon_url(id) {
             embed = embed.thumbnail(icon_url);
         }
 
-        Ok((embed, None))
+        Ok(embed)
     }
 }
@@ -33,7 +33,7 @@ pub trait OptionBuilder: Sized {
 
 // impl<T> OptionBuilder for Option<T>
 /**
-```rust
+```ignore
 embed.try_build(objective, |objective| {
     embed.field("Objective", objective, false)
 });
@@ -0,0 +1,252 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use once_cell::sync::Lazy;
+use tracing::{error, info};
+
+use crate::bot_config::BotConfig;
+use crate::cdclient::CdClient;
+use crate::error::CorrelationId;
+use crate::locale::{LocaleTranslation, LocaleXML, FALLBACK_LOCALE};
+use crate::metrics;
+use crate::CONFIG;
+
+/// Everything loaded from `cdclient.sqlite` and `locale.xml`
+/// - It is swapped as a whole, so readers never see a new cdclient with an old locale
+/// - Readers clone the [`Arc`]s, so a reload never waits on (or invalidates) a running query
+#[derive(Clone)]
+pub struct LuData {
+    pub cdclient: Arc<CdClient>,
+    /// Every locale in `locale.xml`, by name (ex: `en_US`)
+    /// - Phrases a locale is missing are filled in from [`FALLBACK_LOCALE`]
+    pub locales: BTreeMap<String, Arc<LocaleTranslation>>,
+    /// [`BotConfig::locale`] at load time, which is always present in [`Self::locales`]
+    pub default_locale: String,
+    pub loaded_at: SystemTime,
+}
+
+impl LuData {
+    pub fn load(config: &BotConfig) -> Result<Self, String> {
+        let start = Instant::now();
+        let cdclient = CdClient::load_sqlite(&config.cdclient_path)
+            .map_err(|e| format!("Could not load `{}`: {e}", config.cdclient_path.display()))?;
+        metrics::record_data_load("cdclient", start.elapsed());
+
+        let start = Instant::now();
+        let locales = LocaleXML::load_xml(&config.locale_path)
+            .map_err(|e| format!("Could not load `{}`: {e}", config.locale_path.display()))?
+            .with_fallback(FALLBACK_LOCALE)
+            .locales;
+        metrics::record_data_load("locale", start.elapsed());
+
+        if !locales.contains_key(&config.locale) {
+            Err(format!(
+                "Could not find locale `{}` in `{}`",
+                config.locale,
+                config.locale_path.display()
+            ))?
+        }
+        Ok(Self {
+            cdclient: Arc::new(cdclient),
+            locales: locales
+                .into_iter()
+                .map(|(name, locale)| (name, Arc::new(locale)))
+                .collect(),
+            default_locale: config.locale.clone(),
+            loaded_at: SystemTime::now(),
+        })
+    }
+
+    /// - Falls back to the default locale if `name` is not in `locale.xml`
+    pub fn locale(&self, name: &str) -> Arc<LocaleTranslation> {
+        self.locales
+            .get(name)
+            .or_else(|| self.locales.get(&self.default_locale))
+            .cloned()
+            .expect("The default locale is checked when loading")
+    }
+
+    pub fn locale_names(&self) -> Vec<String> {
+        self.locales.keys().cloned().collect()
+    }
+
+    /// Finds the locale closest to `requested`
+    /// - Accepts Discord's locales (`de`, `en-US`) as well as ours (`de_DE`, `en_US`)
+    /// - Falls back to any locale of the same language, ex: `fr` matches `fr_FR`
+    pub fn match_locale(&self, requested: &str) -> Option<String> {
+        let requested = requested.trim().replace('-', "_");
+        let language = |name: &str| name.split('_').next().unwrap_or_default().to_lowercase();
+        self.locales
+            .keys()
+            .find(|name| name.eq_ignore_ascii_case(&requested))
+            .or_else(|| {
+                self.locales
+                    .keys()
+                    .find(|name| language(name) == language(&requested))
+            })
+            .cloned()
+    }
+}
+
+/// The live data and the reloads that replace it, one at a time
+pub struct Snapshot<T> {
+    data: RwLock<T>,
+    reloading: AtomicBool,
+}
+
+impl<T: Clone> Snapshot<T> {
+    pub fn new(data: T) -> Self {
+        Self {
+            data: RwLock::new(data),
+            reloading: AtomicBool::new(false),
+        }
+    }
+
+    pub fn current(&self) -> T {
+        self.data.read().unwrap().clone()
+    }
+
+    pub fn is_reloading(&self) -> bool {
+        self.reloading.load(Ordering::SeqCst)
+    }
+
+    /// Marks a reload as in progress, unless one already is
+    pub fn claim(&self) -> Result<ReloadClaim<'_, T>, String> {
+        self.reloading
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .map(|_| ReloadClaim { snapshot: self })
+            .map_err(|_| String::from("A reload is already in progress"))
+    }
+}
+
+/// A reload in progress, which lets the next one start once dropped, even when loading panicked
+pub struct ReloadClaim<'a, T> {
+    snapshot: &'a Snapshot<T>,
+}
+
+impl<T> ReloadClaim<'_, T> {
+    /// Swaps in what `load` returns, or keeps the current data when it fails or panics
+    /// - Returns how long the load took
+    pub fn load(self, load: impl FnOnce() -> Result<T, String>) -> Result<Duration, String> {
+        let start = Instant::now();
+        let data = panic::catch_unwind(AssertUnwindSafe(load)).unwrap_or_else(|panic| {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            Err(format!("Loading panicked: {message}"))
+        })?;
+        *self.snapshot.data.write().unwrap() = data;
+        Ok(start.elapsed())
+    }
+}
+
+impl<T> Drop for ReloadClaim<'_, T> {
+    fn drop(&mut self) {
+        self.snapshot.reloading.store(false, Ordering::SeqCst);
+    }
+}
+
+static LU_DATA: Lazy<Snapshot<LuData>> =
+    Lazy::new(|| Snapshot::new(LuData::load(&CONFIG).unwrap_or_else(|e| panic!("{e}"))));
+
+static LAST_RELOAD: Mutex<Option<ReloadOutcome>> = Mutex::new(None);
+
+/// How the last reload started with [`spawn_reload`] went
+#[derive(Clone, Debug)]
+pub struct ReloadOutcome {
+    /// What its log lines are tagged with
+    pub id: CorrelationId,
+    pub finished_at: SystemTime,
+    /// How long the load took, or why it failed
+    pub result: Result<Duration, String>,
+}
+
+/// The live snapshot; prefer [`crate::Api`] over calling this directly
+pub fn current() -> LuData {
+    LU_DATA.current()
+}
+
+pub fn is_reloading() -> bool {
+    LU_DATA.is_reloading()
+}
+
+/// The outcome of the last reload started with [`spawn_reload`], once it has finished
+pub fn last_reload() -> Option<ReloadOutcome> {
+    LAST_RELOAD.lock().unwrap().clone()
+}
+
+/// Loads the data files again and swaps them in once both have loaded
+/// - On failure the current data is kept
+/// - Returns how long the load took
+pub fn reload() -> Result<Duration, String> {
+    LU_DATA.claim()?.load(|| LuData::load(&CONFIG))
+}
+
+/// Runs [`reload`] on its own thread, so it can be started from an interaction handler
+/// - Fails right away if a reload is already in progress
+/// - The outcome is logged with `id` and kept for [`last_reload`]
+pub fn spawn_reload(id: CorrelationId) -> Result<(), String> {
+    let claim = LU_DATA.claim()?;
+    thread::spawn(move || {
+        let result = claim.load(|| LuData::load(&CONFIG));
+        match &result {
+            Ok(time) => {
+                info!(correlation_id = %id, "Reloaded data files in {}ms", time.as_millis())
+            }
+            Err(e) => error!(correlation_id = %id, error = e, "Reload failed"),
+        }
+        *LAST_RELOAD.lock().unwrap() = Some(ReloadOutcome {
+            id,
+            finished_at: SystemTime::now(),
+            result,
+        });
+    });
+    Ok(())
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+/// Polls the data files and reloads when they change
+/// - A change is only acted on once the files have stopped changing for one interval, so a file
+///   that is still being copied is not loaded
+pub fn spawn_watcher(interval: Duration) {
+    thread::spawn(move || {
+        let paths = [CONFIG.cdclient_path.clone(), CONFIG.locale_path.clone()];
+        let snapshot = || {
+            paths
+                .iter()
+                .map(PathBuf::as_path)
+                .map(modified)
+                .collect::<Vec<_>>()
+        };
+        let mut loaded = snapshot();
+        let mut pending = None;
+        loop {
+            thread::sleep(interval);
+            let now = snapshot();
+            if now == loaded {
+                pending = None;
+            } else if pending.as_ref() == Some(&now) {
+                // a failed reload is not retried until the files change again
+                match reload() {
+                    Ok(time) => info!("Data files changed, reloaded in {}ms", time.as_millis()),
+                    Err(e) => error!("Data files changed, but reload failed: {e}"),
+                }
+                loaded = now;
+                pending = None;
+            } else {
+                pending = Some(now);
+            }
+        }
+    });
+}
@@ -0,0 +1,105 @@
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub type MsgResult<T> = Result<T, BotError>;
+
+/// Why a command could not answer
+/// - Every kind but [`BotError::Internal`] is shown to the user as is, so write it for them
+/// - A plain message (`Err("...")?`) becomes a [`BotError::BadArgument`], since those are
+///   about what the user gave
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BotError {
+    /// The id or name asked for is not in the data, ex: an unknown object
+    NotFound(String),
+    /// The object exists, but lacks the component a command needs
+    MissingComponent(String),
+    /// An option or custom id that cannot be used
+    BadArgument(String),
+    /// The data points at something that is not there, ex: a registered component without a row
+    DataInconsistency(String),
+    /// A bug or a failure on our side
+    /// - Only the id is shown to the user; the message is logged with it
+    Internal { id: CorrelationId, message: String },
+}
+
+impl BotError {
+    pub fn internal(message: impl Display) -> Self {
+        BotError::Internal {
+            id: CorrelationId::new(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Label for metrics, ex: `not_found`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            BotError::NotFound(_) => "not_found",
+            BotError::MissingComponent(_) => "missing_component",
+            BotError::BadArgument(_) => "bad_argument",
+            BotError::DataInconsistency(_) => "data_inconsistency",
+            BotError::Internal { .. } => "internal",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            BotError::NotFound(msg)
+            | BotError::MissingComponent(msg)
+            | BotError::BadArgument(msg)
+            | BotError::DataInconsistency(msg)
+            | BotError::Internal { message: msg, .. } => msg,
+        }
+    }
+}
+
+/// For logs; use [`crate::bot_config::BotConfig::error_embed`] to show an error to a user
+impl Display for BotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BotError::Internal { id, message } => write!(f, "{message} ({id})"),
+            other => write!(f, "{}", other.message()),
+        }
+    }
+}
+
+impl From<String> for BotError {
+    fn from(msg: String) -> Self {
+        BotError::BadArgument(msg)
+    }
+}
+
+impl From<&str> for BotError {
+    fn from(msg: &str) -> Self {
+        BotError::BadArgument(msg.to_string())
+    }
+}
+
+/// Ties the embed of an internal error to its log line, ex: `5f3a91c2`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CorrelationId(u32);
+
+impl CorrelationId {
+    pub fn new() -> Self {
+        static NEXT: AtomicU32 = AtomicU32::new(0);
+        let count = NEXT.fetch_add(1, Ordering::Relaxed);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.subsec_nanos())
+            .unwrap_or(0);
+        // the counter keeps ids made in the same instant apart
+        CorrelationId(nanos ^ count.wrapping_mul(0x9e37_79b9))
+    }
+}
+
+impl Default for CorrelationId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Display for CorrelationId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:08x}", self.0)
+    }
+}
@@ -14,11 +14,10 @@ use crate::{
         CdClientVendorComponent,
     },
     custom::CollectIntoOptionalVec,
-    Api, CONFIG,
+    error::{BotError, MsgResult},
+    scope, Api,
 };
 
-pub type MsgResult<T> = Result<T, String>;
-
 pub fn fmt_name_id(name: impl Display, id: impl Display) -> String {
     format!("{name} [{id}]")
 }
@@ -39,7 +38,7 @@ pub fn fix_icon_asset(asset: &str) -> String {
 }
 
 pub fn icon_asset_as_url(asset: impl AsRef<str>) -> String {
-    CONFIG.explorer_res_uri(&fix_icon_asset(asset.as_ref()))
+    scope::config().explorer_res_uri(&fix_icon_asset(asset.as_ref()))
 }
 
 /*
@@ -73,7 +72,7 @@ impl CdClientLootTableId {
             .loot_table
             .at_group_key(&self.0)
             .clone()
-            .ok_or_else(|| self.err("does not exist"))
+            .ok_or_else(|| BotError::NotFound(self.err("does not exist")))
     }
 
     pub fn contained_items(&self) -> Option<Vec<CdClientObjectsId>> {
@@ -173,7 +172,7 @@ impl CdClientLootMatrixId {
             .loot_matrix
             .at_group_key(&self.0)
             .clone()
-            .ok_or_else(|| format!("Loot Matrix `{}` does not exist", self.0))
+            .ok_or_else(|| BotError::NotFound(format!("Loot Matrix `{}` does not exist", self.0)))
     }
 
     pub fn contained_items(&self) -> Option<Vec<CdClientObjectsId>> {
@@ -260,7 +259,8 @@ impl CdClientObjectsId {
     }
 
     pub fn try_fetch(&self) -> MsgResult<CdClientObjects> {
-        self.fetch().ok_or_else(|| self.err("does not exist"))
+        self.fetch()
+            .ok_or_else(|| BotError::NotFound(self.err("does not exist")))
     }
 
     pub fn name(&self) -> Option<String> {
@@ -300,11 +300,15 @@ impl CdClientObjectsId {
         self.cdclient()
             .components_registry
             .at_group_key(&self.0)
-            .ok_or_else(|| self.err("has no Registered Components"))?
+            .ok_or_else(|| BotError::MissingComponent(self.err("has no Registered Components")))?
             .into_iter()
             .find(|cr| cr.component_type == C::ID)
             .map(|cr| component(cr.component_id))
-            .ok_or_else(|| self.err(format!("has no Registered {} Component", C::NAME)))
+            .ok_or_else(|| {
+                BotError::MissingComponent(
+                    self.err(format!("has no Registered {} Component", C::NAME)),
+                )
+            })
     }
 
     pub fn get_containing_loot_table_indexes(&self) -> Option<Vec<CdClientLootTableId>> {
@@ -329,14 +333,14 @@ impl CdClientObjectsId {
     pub fn rarity(&self) -> MsgResult<i32> {
         self.item_component()?
             .rarity
-            .ok_or_else(|| self.err("has no rarity"))
+            .ok_or_else(|| BotError::NotFound(self.err("has no rarity")))
     }
 
     pub fn chance_from_loot_matrix_index(&self, lmi: CdClientLootMatrixId) -> MsgResult<f64> {
         let rarity = self.rarity()?;
         let ltis = self
             .get_containing_loot_table_indexes()
-            .ok_or_else(|| self.err("is not in any Loot Tables"))?;
+            .ok_or_else(|| BotError::NotFound(self.err("is not in any Loot Tables")))?;
 
         // I think there is generally no repeats, but we will calculate anyway
         let lms: Vec<_> = lmi
@@ -403,12 +407,13 @@ impl CdClientObjectsId {
     pub fn activities(&self) -> MsgResult<Vec<CdClientActivityRewardsId>> {
         let lmis = self
             .get_containing_loot_matrix_indexes()
-            .ok_or_else(|| self.err("is not in any Loot Matrices"))?;
+            .ok_or_else(|| BotError::NotFound(self.err("is not in any Loot Matrices")))?;
 
         let activities: Vec<_> = self
             .cdclient()
             .activity_rewards
             .iter()
+            .take_while(|_| !scope::is_cancelled())
             .filter(|activity| {
                 activity
                     .loot_matrix_index
@@ -422,12 +427,13 @@ impl CdClientObjectsId {
     pub fn activities_chances(&self) -> MsgResult<Vec<LootMatrixObjectChances>> {
         let lmis = self
             .get_containing_loot_matrix_indexes()
-            .ok_or_else(|| self.err("is not in any Loot Matrices"))?;
+            .ok_or_else(|| BotError::NotFound(self.err("is not in any Loot Matrices")))?;
 
         let activities: Vec<_> = self
             .cdclient()
             .activity_rewards
             .iter()
+            .take_while(|_| !scope::is_cancelled())
             .filter(|act| {
                 act.loot_matrix_index
                     .is_some_and(|lmi| lmis.contains(&CdClientLootMatrixId(lmi)))
@@ -461,12 +467,13 @@ impl CdClientObjectsId {
     pub fn smashables(&self) -> MsgResult<Vec<CdClientObjectsId>> {
         let lmis = self
             .get_containing_loot_matrix_indexes()
-            .ok_or_else(|| self.err("is not in any Loot Matrices"))?;
+            .ok_or_else(|| BotError::NotFound(self.err("is not in any Loot Matrices")))?;
 
         let smashables: Vec<_> = self
             .cdclient()
             .destructible_component
             .iter()
+            .take_while(|_| !scope::is_cancelled())
             .filter(|comp| {
                 comp.loot_matrix_index
                     .is_some_and(|lmi| lmis.contains(&CdClientLootMatrixId(lmi)))
@@ -482,12 +489,13 @@ impl CdClientObjectsId {
     pub fn smashables_chances(&self) -> MsgResult<Vec<LootMatrixObjectChances>> {
         let lmis = self
             .get_containing_loot_matrix_indexes()
-            .ok_or_else(|| self.err("is not in any Loot Matrices"))?;
+            .ok_or_else(|| BotError::NotFound(self.err("is not in any Loot Matrices")))?;
 
         let smashables: Vec<_> = self
             .cdclient()
             .destructible_component
             .iter()
+            .take_while(|_| !scope::is_cancelled())
             .filter(|comp| {
                 comp.loot_matrix_index
                     .is_some_and(|lmi| lmis.contains(&CdClientLootMatrixId(lmi)))
@@ -519,12 +527,13 @@ impl CdClientObjectsId {
     pub fn packages(&self) -> MsgResult<Vec<CdClientObjectsId>> {
         let lmis = self
             .get_containing_loot_matrix_indexes()
-            .ok_or_else(|| self.err("is not in any Loot Matrices"))?;
+            .ok_or_else(|| BotError::NotFound(self.err("is not in any Loot Matrices")))?;
 
         let packages: Vec<_> = self
             .cdclient()
             .package_component
             .iter()
+            .take_while(|_| !scope::is_cancelled())
             .filter(|comp| lmis.contains(&CdClientLootMatrixId(comp.loot_matrix_index)))
             .map(|comp| CdClientPackageComponentId(comp.id))
             .filter_map(|comp| comp.get_objects_with_component())
@@ -536,12 +545,13 @@ impl CdClientObjectsId {
     pub fn packages_chances(&self) -> MsgResult<Vec<LootMatrixObjectChances>> {
         let lmis = self
             .get_containing_loot_matrix_indexes()
-            .ok_or_else(|| self.err("is not in any Loot Matrices"))?;
+            .ok_or_else(|| BotError::NotFound(self.err("is not in any Loot Matrices")))?;
 
         let smashables: Vec<_> = self
             .cdclient()
             .package_component
             .iter()
+            .take_while(|_| !scope::is_cancelled())
             .filter(|comp| lmis.contains(&CdClientLootMatrixId(comp.loot_matrix_index)))
             .chunk_by(|comp| CdClientLootMatrixId(comp.loot_matrix_index))
             .into_iter()
@@ -570,12 +580,13 @@ impl CdClientObjectsId {
     pub fn vendors(&self) -> MsgResult<Vec<CdClientObjectsId>> {
         let lmis = self
             .get_containing_loot_matrix_indexes()
-            .ok_or_else(|| self.err("is not in any Loot Matrices"))?;
+            .ok_or_else(|| BotError::NotFound(self.err("is not in any Loot Matrices")))?;
 
         let vendors: Vec<_> = self
             .cdclient()
             .vendor_component
             .iter()
+            .take_while(|_| !scope::is_cancelled())
             .filter(|comp| lmis.contains(&CdClientLootMatrixId(comp.loot_matrix_index)))
             .map(|comp| CdClientVendorComponentId(comp.id))
             .filter_map(|comp| comp.get_objects_with_component())
@@ -675,7 +686,7 @@ impl CdClientDestructibleComponentId {
             .destructible_component
             .at_key(&self.0)
             .cloned()
-            .ok_or_else(|| self.err("does not exist"))
+            .ok_or_else(|| BotError::DataInconsistency(self.err("does not exist")))
     }
 
     pub fn items_dropped(&self) -> Option<Vec<LootTableChances>> {
@@ -701,7 +712,7 @@ impl CdClientPackageComponentId {
             .package_component
             .at_key(&self.0)
             .cloned()
-            .ok_or_else(|| self.err("does not exist"))
+            .ok_or_else(|| BotError::DataInconsistency(self.err("does not exist")))
     }
 
     pub fn items_dropped(&self) -> Option<Vec<LootTableChances>> {
@@ -727,7 +738,7 @@ impl CdClientItemComponentId {
             .item_component
             .at_key(&self.0)
             .cloned()
-            .ok_or_else(|| self.err("does not exist"))
+            .ok_or_else(|| BotError::DataInconsistency(self.err("does not exist")))
     }
 }
 
@@ -747,7 +758,7 @@ impl CdClientRenderComponentId {
             .render_component
             .at_key(&self.0)
             .cloned()
-            .ok_or_else(|| self.err("does not exist"))
+            .ok_or_else(|| BotError::DataInconsistency(self.err("does not exist")))
     }
 }
 
@@ -767,7 +778,7 @@ impl CdClientVendorComponentId {
             .vendor_component
             .at_key(&self.0)
             .cloned()
-            .ok_or_else(|| self.err("does not exist"))
+            .ok_or_else(|| BotError::DataInconsistency(self.err("does not exist")))
     }
 
     pub fn items_sold(&self) -> Option<Vec<CdClientObjectsId>> {
@@ -813,7 +824,7 @@ impl CdClientActivityRewardsId {
             .activity_rewards
             .at_group_key(&self.0)
             .clone()
-            .ok_or_else(|| self.err("does not exist"))
+            .ok_or_else(|| BotError::NotFound(self.err("does not exist")))
     }
 
     /// ! use a select menu with description to separate which one is being rewarded
@@ -870,7 +881,7 @@ impl CdClientSkillBehaviorId {
             .skill_behavior
             .at_key(&self.0)
             .cloned()
-            .ok_or_else(|| self.err("does not exist"))
+            .ok_or_else(|| BotError::NotFound(self.err("does not exist")))
     }
 
     pub fn thumbnail(&self) -> Option<String> {
@@ -0,0 +1,66 @@
+/*
+* - Replies to `[[Imagination Booster]]` in chat with a compact card of it
+* - Servers opt in with `/settings inline_lookups on`, and can keep it to `inline_channels`
+* - The bot only sees messages with `message_content_intent`, which is off by default
+* - Nothing is replied when nothing is found or the user is rate limited, since nobody asked
+*/
+
+use serenity::all::{CreateEmbed, Message};
+
+use crate::analytics::Lookup;
+use crate::commands::lookup::LookupCommand;
+use crate::interaction_command::CommandResult;
+use crate::metrics::InteractionKind;
+use crate::references::{self, Reference};
+use crate::registry::instrumented;
+use crate::render::DiscordRenderer;
+use crate::scope::{Cancellation, InteractionScope};
+use crate::{settings, Api, CONFIG};
+
+/// What logs, metrics and rate limits go by, like [`InteractionCommand::NAME`]
+pub const NAME: &str = "inline";
+
+/// Cards in one reply, so one message can't fill a channel
+pub const MAX_CARDS: usize = 3;
+
+/// Whether `message` has `[[names]]` in a channel that looks them up
+/// - Settings are only read for messages with names, since this runs on every message
+pub fn wants_cards(message: &Message) -> bool {
+    let Some(guild_id) = message.guild_id else {
+        return false;
+    };
+    !message.author.bot
+        && !references::scan_names(&message.content).is_empty()
+        && settings::guild_settings(guild_id.get())
+            .apply(&CONFIG)
+            .looks_up_inline_in(message.channel_id.get())
+}
+
+/// The card the message command shows of what a name is, without its buttons
+pub fn card(lookup: Lookup, id: i32) -> CommandResult {
+    Ok(LookupCommand::card(lookup, id)?.components(vec![]))
+}
+
+/// The cards of the first [`MAX_CARDS`] names in `message` that are found, in their order
+pub fn cards(message: &Message, cancellation: Cancellation) -> Vec<CreateEmbed> {
+    let names = references::scan_names(&message.content);
+    let arguments = names
+        .iter()
+        .filter_map(|reference| match reference {
+            Reference::Name(name) => Some(format!("[[{name}]]")),
+            Reference::Id(..) => None,
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let scope = InteractionScope::of_message(message).with_cancellation(cancellation);
+    instrumented(InteractionKind::Slash, NAME, arguments, None, scope, || {
+        let cdclient = LookupCommand.cdclient();
+        let found = references::resolve(&cdclient, names);
+        found
+            .into_iter()
+            .filter_map(|(lookup, id)| card(lookup, id).ok())
+            .take(MAX_CARDS)
+            .map(|card| DiscordRenderer.embed(&card))
+            .collect()
+    })
+}
@@ -4,77 +4,22 @@ use std::str::FromStr;
 
 use rusqlite::types::FromSql;
 use serenity::all::{
-    AutocompleteChoice, AutocompleteOption, ButtonStyle, Color, CommandInteraction,
-    CommandOptionChoice, CommandOptionType, ComponentInteraction, CreateActionRow, CreateButton,
-    CreateCommandOption, CreateEmbed, CreateEmbedAuthor, CreateEmbedFooter,
-    CreateInteractionResponseMessage, ResolvedOption, ResolvedValue,
+    ActionRowComponent, AutocompleteChoice, AutocompleteOption, Color, CommandInteraction,
+    CommandOptionChoice, CommandOptionType, CommandType, ComponentInteraction, CreateActionRow,
+    CreateCommandOption, CreateEmbedAuthor, CreateEmbedFooter, CreateInputText,
+    CreateInteractionResponseMessage, CreateModal, InputTextStyle, Message, ModalInteraction,
+    ResolvedOption, ResolvedTarget, ResolvedValue,
 };
 use serenity::builder::CreateCommand;
 use serenity::json::Value;
 
 use crate::custom::Mutated;
-use crate::ids::MsgResult;
-use crate::{CD_CLIENT, CONFIG};
-
-/// **IMPORTANT: This should only be used on required fields**
-#[macro_export]
-macro_rules! string_option {
-    ($options:ident, $name:literal) => {{
-        let field = &$options
-            .iter()
-            .find(|opt| opt.name == $name)
-            .as_ref()
-            .ok_or_else(|| format!("Expected required field: `{}`", $name))?
-            .value;
-        let serenity::all::ResolvedValue::String(field) = field else {
-            Err(format!(
-                "Incorrect type for field `{}`\nGiven:\n```rust\n{:#?}\n```",
-                $name, field
-            ))?
-        };
-        field.clone()
-    }};
-}
-
-/// **IMPORTANT: This should only be used on required fields**
-#[macro_export]
-macro_rules! float_option {
-    ($options:ident, $name:literal) => {{
-        let field = &$options
-            .iter()
-            .find(|opt| opt.name == $name)
-            .as_ref()
-            .ok_or_else(|| format!("Expected required field: `{}`", $name))?
-            .value;
-        let serenity::all::ResolvedValue::Number(field) = field else {
-            Err(format!(
-                "Incorrect type for field `{}`\nGiven:\n```rust\n{:#?}\n```",
-                $name, field
-            ))?
-        };
-        *field
-    }};
-}
-
-/// **IMPORTANT: This should only be used on required fields**
-#[macro_export]
-macro_rules! int_option {
-    ($options:ident, $name:literal) => {{
-        let field = &$options
-            .iter()
-            .find(|opt| opt.name == $name)
-            .as_ref()
-            .ok_or_else(|| format!("Expected required field: `{}`", $name))?
-            .value;
-        let serenity::all::ResolvedValue::Integer(field) = field else {
-            Err(format!(
-                "Incorrect type for field `{}`\nGiven:\n```rust\n{:#?}\n```",
-                $name, field
-            ))?
-        };
-        *field as i32
-    }};
-}
+use crate::error::{BotError, MsgResult};
+use crate::metrics::Outcome;
+use crate::pager::MIN_PAGE;
+use crate::render::DiscordRenderer;
+use crate::response::{ActionRow, Button, ButtonAction, Response};
+use crate::{scope, CONFIG};
 
 // pub fn parse_custom_id(custom_id: &str) -> (&str, BTreeMap<&str, &str>) {
 //     let colon_idx = custom_id.find(":").unwrap_or(0);
@@ -116,10 +61,12 @@ macro_rules! int_option {
 //     }
 // }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub enum CustomIdInteractionType {
     Reply,
     Update,
+    /// Asks for a page in a modal first (see [`page_modal`]), then updates the message with it
+    PageModal,
 }
 
 impl FromStr for CustomIdInteractionType {
@@ -129,6 +76,7 @@ impl FromStr for CustomIdInteractionType {
         Ok(match s {
             "r" => CustomIdInteractionType::Reply,
             "u" => CustomIdInteractionType::Update,
+            "m" => CustomIdInteractionType::PageModal,
             other => Err(format!(
                 "Could not parse `{other}` into an interaction type"
             ))?,
@@ -144,15 +92,101 @@ impl Display for CustomIdInteractionType {
             match self {
                 CustomIdInteractionType::Reply => "r",
                 CustomIdInteractionType::Update => "u",
+                CustomIdInteractionType::PageModal => "m",
             }
         )
     }
 }
 
+/// Discord rejects a component whose custom id is longer
+pub const CUSTOM_ID_LIMIT: usize = 100;
+
+const BASE62: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// A whole number in base 62, ex: `7415` is `1vb`
+fn pack(mut number: u64) -> String {
+    let mut digits = vec![];
+    loop {
+        digits.push(BASE62[(number % 62) as usize]);
+        number /= 62;
+        if number == 0 {
+            break;
+        }
+    }
+    digits.reverse();
+    String::from_utf8(digits).unwrap_or_default()
+}
+
+fn unpack(packed: &str) -> Option<u64> {
+    packed.bytes().try_fold(0u64, |number, digit| {
+        let digit = BASE62.iter().position(|&candidate| candidate == digit)?;
+        number.checked_mul(62)?.checked_add(digit as u64)
+    })
+}
+
+/// What separates the keys and values of a custom id, so values have them percent-encoded
+const RESERVED: [char; 4] = ['%', '&', '=', '~'];
+
+/// `value` as it is kept in a custom id, ex: `R&D` becomes `R%26D`
+pub fn encode_value(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match RESERVED.contains(&c) {
+            true => format!("%{:02X}", c as u8),
+            false => c.to_string(),
+        })
+        .collect()
+}
+
+/// The value [`encode_value`] kept, or `None` when a `%` is not followed by two hex digits
+pub fn decode_value(value: &str) -> Option<String> {
+    let mut bytes = vec![];
+    let mut rest = value.as_bytes();
+    while let Some((&byte, after)) = rest.split_first() {
+        match byte {
+            b'%' => {
+                let hex = std::str::from_utf8(after.get(..2)?).ok()?;
+                bytes.push(u8::from_str_radix(hex, 16).ok()?);
+                rest = &after[2..];
+            }
+            _ => {
+                bytes.push(byte);
+                rest = after;
+            }
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// `{key}={value}&...` with every whole number that gets shorter packed as `{key}~{base 62}`
+fn pack_parameters(parameters: &str) -> String {
+    parameters
+        .split('&')
+        .map(|param| match param.split_once('=') {
+            Some((key, value)) => match value.parse::<u64>() {
+                Ok(number) if pack(number).len() < value.len() && number.to_string() == value => {
+                    format!("{key}~{}", pack(number))
+                }
+                _ => param.to_string(),
+            },
+            None => param.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// A custom id, parsed
+/// - Format: `{interaction}/{cmd}.{version}:{key}={value}&{key}~{packed}&...`
+/// - Values are percent-encoded where they hold `%`, `&`, `=` or `~` (see [`encode_value`])
+/// - Whole numbers are packed in base 62 where that is shorter, but [`Self::map`] always holds
+///   them in decimal, so commands parse them like any other value
+/// - Custom ids from before versions were added have version 0, which no command is at
 #[derive(Debug)]
 pub struct CustomIdOptions {
     pub interaction: CustomIdInteractionType,
     pub cmd: String,
+    /// [`ToCustomId::VERSION`] of the arguments when the custom id was made
+    pub version: u32,
     pub map: BTreeMap<String, String>,
 }
 
@@ -161,9 +195,9 @@ impl CustomIdOptions {
     //     parse_custom_id_options(custom_id)
     // }
     pub fn from_custom_id(custom_id: &str) -> MsgResult<CustomIdOptions> {
-        let slash_idx = custom_id
-            .find("/")
-            .ok_or_else(|| format!("Could not parse interaction type"))?;
+        let slash_idx = custom_id.find("/").ok_or_else(|| {
+            BotError::BadArgument(String::from("Could not parse interaction type"))
+        })?;
         let interaction = &custom_id[..slash_idx];
         let interaction = interaction.parse()?;
 
@@ -171,36 +205,90 @@ impl CustomIdOptions {
 
         let colon_idx = remaining
             .find(":")
-            .ok_or_else(|| format!("Could not parse command type"))?;
-        let cmd = remaining[..colon_idx].to_string();
+            .ok_or_else(|| BotError::BadArgument(String::from("Could not parse command type")))?;
+        let (cmd, version) = match remaining[..colon_idx].split_once('.') {
+            Some((cmd, version)) => (
+                cmd,
+                version.parse().map_err(|_| {
+                    BotError::BadArgument(format!("Could not parse version `{version}`"))
+                })?,
+            ),
+            None => (&remaining[..colon_idx], 0),
+        };
+        let cmd = cmd.to_string();
 
         let remaining = &remaining[colon_idx + 1..];
         let mut options = BTreeMap::new();
         for seg in remaining.split("&") {
             if let Some((key, value)) = seg.split_once("=") {
-                options.insert(key.to_string(), value.to_string());
+                let value = decode_value(value).ok_or_else(|| {
+                    BotError::BadArgument(format!("Could not decode `{key}`: `{value}`"))
+                })?;
+                options.insert(key.to_string(), value);
+            } else if let Some((key, packed)) = seg.split_once("~") {
+                let number = unpack(packed).ok_or_else(|| {
+                    BotError::BadArgument(format!("Could not unpack `{key}`: `{packed}`"))
+                })?;
+                options.insert(key.to_string(), number.to_string());
             }
         }
 
         Ok(CustomIdOptions {
             cmd,
+            version,
             map: options,
             interaction,
         })
     }
 
-    pub fn get(&self, key: &str) -> Result<&str, String> {
-        Ok(self
-            .map
+    pub fn get(&self, key: &str) -> MsgResult<&str> {
+        self.map
             .get(key)
-            .ok_or_else(|| format!("Parameter Missing: `{key}`"))?)
+            .map(String::as_str)
+            .ok_or_else(|| BotError::BadArgument(format!("Parameter Missing: `{key}`")))
+    }
+
+    /// The custom id of a [`page_modal`] with the page it was given, to update the message with
+    pub fn from_page_modal(modal: &ModalInteraction) -> MsgResult<CustomIdOptions> {
+        let mut options = CustomIdOptions::from_custom_id(&modal.data.custom_id)?;
+        let page = modal
+            .data
+            .components
+            .iter()
+            .flat_map(|row| &row.components)
+            .find_map(|component| match component {
+                ActionRowComponent::InputText(input) if input.custom_id == PAGE_INPUT => {
+                    input.value.as_deref()
+                }
+                _ => None,
+            })
+            .unwrap_or_default()
+            .trim();
+        match page.parse::<usize>() {
+            Ok(page) if page >= MIN_PAGE => {
+                options.map.insert(String::from("page"), page.to_string());
+            }
+            _ => Err(BotError::BadArgument(format!(
+                "`{page}` is not a page number"
+            )))?,
+        }
+        options.interaction = CustomIdInteractionType::Update;
+        Ok(options)
     }
 
-    pub fn parse<T: FromStr>(&self, key: &str) -> Result<T, String> {
+    pub fn parse<T: FromStr>(&self, key: &str) -> MsgResult<T> {
         let value = self.get(key)?;
-        value
-            .parse::<T>()
-            .map_err(|e| format!("Parse of Parameter `{key}` Failed: `{value}`"))
+        value.parse::<T>().map_err(|_| {
+            BotError::BadArgument(format!("Parse of Parameter `{key}` Failed: `{value}`"))
+        })
+    }
+
+    /// Like [`Self::parse`], but `None` when the custom id leaves `key` out
+    pub fn parse_optional<T: FromStr>(&self, key: &str) -> MsgResult<Option<T>> {
+        match self.map.contains_key(key) {
+            true => self.parse(key).map(Some),
+            false => Ok(None),
+        }
     }
 
     // pub fn into_custom_id(&self) -> String {
@@ -213,45 +301,97 @@ impl CustomIdOptions {
 
 pub trait ToCustomId {
     const CMD: &'static str;
+    /// Bump this whenever the parameters change, so the buttons of older messages say that they
+    /// are outdated instead of running with arguments that no longer parse
+    const VERSION: u32 = 1;
+
     fn to_custom_id(&self, reply: bool) -> String {
+        let interaction = match reply {
+            true => CustomIdInteractionType::Reply,
+            false => CustomIdInteractionType::Update,
+        };
+        self.to_custom_id_for(interaction)
+    }
+
+    /// - See [`CustomIdOptions`] for the format
+    fn to_custom_id_for(&self, interaction: CustomIdInteractionType) -> String {
         format!(
-            "{}/{}:{}",
-            if reply { "r" } else { "u" },
+            "{interaction}/{}.{}:{}",
             Self::CMD,
-            self.parameters()
+            Self::VERSION,
+            pack_parameters(&self.parameters())
         )
     }
+
     fn parameters(&self) -> String;
 
-    /// - The reason for the random custom_id is that Discord won't let me have duplicates
-    /// - Duplicates occur when I have Drop button on /drop because of Page 1 button
-    fn to_self_button(&self, label: impl Into<String>) -> CreateButton {
-        CreateButton::new("----")
-            .label(label)
-            .style(ButtonStyle::Success)
-            .disabled(true)
+    /// The arguments with the longest custom id, which is tested to fit [`CUSTOM_ID_LIMIT`]
+    fn longest() -> Self
+    where
+        Self: Sized;
+
+    /// - The button of what is already shown, ex: the Drop button on /drop
+    /// - It never runs anything, so it has no custom id (see [`ButtonAction::Current`])
+    fn to_self_button(&self, label: impl Into<String>) -> Button {
+        Button::new(label, ButtonAction::Current)
     }
 
-    fn to_reply_button(&self, label: impl Into<String>) -> CreateButton {
-        CreateButton::new(self.to_custom_id(true)).label(label)
+    fn to_reply_button(&self, label: impl Into<String>) -> Button {
+        Button::new(label, ButtonAction::Run(self.to_custom_id(true)))
     }
-    fn to_update_button(&self, label: impl Into<String>) -> CreateButton {
-        CreateButton::new(self.to_custom_id(false)).label(label)
+    fn to_update_button(&self, label: impl Into<String>) -> Button {
+        Button::new(label, ButtonAction::Run(self.to_custom_id(false)))
     }
+
+    /// - Opens a [`page_modal`], and the page it is given replaces `page` in these arguments
+    fn to_page_modal_button(&self, label: impl Into<String>) -> Button {
+        let custom_id = self.to_custom_id_for(CustomIdInteractionType::PageModal);
+        Button::new(label, ButtonAction::Run(custom_id))
+    }
+}
+
+/// The id of the text input of a [`page_modal`]
+const PAGE_INPUT: &str = "page";
+
+/// Asks for a page number; submitting it comes back with the same custom id
+/// - Read it with [`CustomIdOptions::from_page_modal`]
+pub fn page_modal(custom_id: &str) -> CreateModal {
+    let input = CreateInputText::new(InputTextStyle::Short, "Page", PAGE_INPUT)
+        .placeholder("ex: 3")
+        .min_length(1)
+        .max_length(4);
+    CreateModal::new(custom_id, "Go to Page").components(vec![CreateActionRow::InputText(input)])
+}
+
+pub type CommandResult = MsgResult<Response>;
+
+/// Reply shown to anyone who is not in [`crate::bot_config::BotConfig::owner_ids`]
+fn owner_only_response() -> CreateInteractionResponseMessage {
+    scope::set_outcome(Outcome::Denied);
+    let response =
+        scope::config().failure_embed("Owners Only", "Only the bot owners can use this command!");
+    DiscordRenderer.message(&response).ephemeral(true)
 }
 
-pub type CommandResult = Result<(CreateEmbed, Option<Vec<CreateActionRow>>), String>;
+/// Records the error as the outcome of the interaction and renders it
+fn error_response(error: BotError) -> Response {
+    let response = scope::config().error_embed(&error);
+    scope::set_outcome(Outcome::Error(error));
+    response
+}
 
 pub trait InteractionCommand {
     const NAME: &'static str;
     const DESCRIPTION: &'static str;
+    /// Only users in [`crate::bot_config::BotConfig::owner_ids`] may run this command
+    const OWNER_ONLY: bool = false;
 
     /// This is so that it can respond from either a slash command or component interaction
     /// - This type must be parsable from a string (message component id)
-    ///   - Format: `{cmd}:{key}={value}&` which is parsed into [`CustomIdOptions`]
+    ///   - Format: `{cmd}.{version}:{key}={value}&` which is parsed into [`CustomIdOptions`]
     /// - This type must be parsable from a &[ResolvedOption] (slash command)
-    type Arguments: for<'a> TryFrom<&'a CustomIdOptions, Error = String>
-        + for<'a> TryFrom<&'a [ResolvedOption<'a>], Error = String>
+    type Arguments: for<'a> TryFrom<&'a CustomIdOptions, Error = BotError>
+        + for<'a> TryFrom<&'a [ResolvedOption<'a>], Error = BotError>
         + ToCustomId;
     // I'm holding off on this for now because it requires me to create strings :(
     // + for<'a> Into<CustomIdOptions<'a>>;
@@ -270,46 +410,116 @@ pub trait InteractionCommand {
         cmd
     }
 
+    /// This command as the subcommand `name` of a [`crate::registry::CommandGroup`]
+    fn register_subcommand(name: &str) -> CreateCommandOption {
+        let mut subcommand =
+            CreateCommandOption::new(CommandOptionType::SubCommand, name, Self::DESCRIPTION);
+        for option in Self::options().unwrap_or_default() {
+            subcommand = subcommand.add_sub_option(option)
+        }
+        subcommand
+    }
+
+    /// What to try the command with, which `/help` offers as buttons that run them
+    fn examples() -> Vec<Self::Arguments> {
+        vec![]
+    }
+
     // fn run(arguments: Self::Arguments) -> (CreateEmbed, Option<Vec<CreateActionRow>>);
     fn run(arguments: Self::Arguments) -> CommandResult;
 
+    /// Parses the arguments from a custom id and runs the command, without any Discord context
+    fn run_custom_id(options: &CustomIdOptions) -> CommandResult {
+        Self::Arguments::try_from(options).and_then(|args| Self::run(args))
+    }
+
     fn handle_autocomplete(
         autocomplete_option: AutocompleteOption<'_>,
     ) -> Option<Vec<AutocompleteChoice>> {
         None
     }
 
-    fn handle_slash_command(command: &CommandInteraction) -> CreateInteractionResponseMessage {
-        let result = Self::Arguments::try_from(command.data.options().as_slice())
-            .and_then(|args| Self::run(args));
-        let (embed, components) = result.unwrap_or_else(|msg| CONFIG.error_embed(msg));
-
-        let mut response = CreateInteractionResponseMessage::new().embed(embed);
-        if let Some(components) = components {
-            response = response.components(components);
+    /// - `options` are this command's own, ex: under `drop` for `/item drop`
+    fn handle_slash_command(
+        command: &CommandInteraction,
+        options: &[ResolvedOption],
+    ) -> CreateInteractionResponseMessage {
+        if Self::OWNER_ONLY && !CONFIG.is_owner(command.user.id.get()) {
+            return owner_only_response();
         }
-        response
+        let result = Self::Arguments::try_from(options).and_then(|args| Self::run(args));
+        let response = result.unwrap_or_else(error_response);
+        DiscordRenderer.message(&response)
     }
 
     fn handle_component_interaction(
         interaction: &ComponentInteraction,
         options: &CustomIdOptions,
     ) -> CreateInteractionResponseMessage {
-        let result = Self::Arguments::try_from(options).and_then(|args| Self::run(args));
-        let (embed, components) = result.unwrap_or_else(|msg| CONFIG.error_embed(msg));
-        let mut components = components.unwrap_or_else(|| vec![]);
+        if Self::OWNER_ONLY && !CONFIG.is_owner(interaction.user.id.get()) {
+            return owner_only_response();
+        }
+        let mut response = Self::run_custom_id(options).unwrap_or_else(error_response);
 
         match options.interaction {
             CustomIdInteractionType::Reply => {
-                let back_button = CreateButton::new_link(interaction.message.link()).label("Back");
-                let back_component_row = CreateActionRow::Buttons(vec![back_button]);
-                components.push(back_component_row);
+                let back_button = Button::link("Back", interaction.message.link());
+                response = response.row(ActionRow::Buttons(vec![back_button]));
             }
-            CustomIdInteractionType::Update => {}
+            CustomIdInteractionType::Update | CustomIdInteractionType::PageModal => {}
+        };
+
+        DiscordRenderer.message(&response)
+    }
+
+    /// The page a [`page_modal`] was given, which always updates the message it came from
+    fn handle_modal_submit(
+        interaction: &ModalInteraction,
+        options: &CustomIdOptions,
+    ) -> CreateInteractionResponseMessage {
+        if Self::OWNER_ONLY && !CONFIG.is_owner(interaction.user.id.get()) {
+            return owner_only_response();
+        }
+        let response = Self::run_custom_id(options).unwrap_or_else(error_response);
+        DiscordRenderer.message(&response)
+    }
+}
+
+/// A command in the Apps menu of a message, ex: "Look up in Crab Force"
+pub trait MessageCommand {
+    /// What logs, metrics and rate limits go by, like [`InteractionCommand::NAME`]
+    const NAME: &'static str;
+    /// What Discord shows in the Apps menu
+    const LABEL: &'static str;
+
+    /// - `text` is what the message says, see [`message_text`]
+    fn run(text: &str) -> CommandResult;
+
+    fn register() -> CreateCommand {
+        CreateCommand::new(Self::LABEL).kind(CommandType::Message)
+    }
+
+    fn handle_message_command(command: &CommandInteraction) -> CreateInteractionResponseMessage {
+        let text = match command.data.target() {
+            Some(ResolvedTarget::Message(message)) => message_text(message),
+            _ => String::new(),
         };
+        let response = Self::run(&text).unwrap_or_else(error_response);
+        DiscordRenderer.message(&response)
+    }
+}
 
-        CreateInteractionResponseMessage::new()
-            .embed(embed)
-            .components(components)
+/// The content of a message with the urls, titles, descriptions and fields of its embeds, ex: to
+/// look up what a card of the bot shows
+pub fn message_text(message: &Message) -> String {
+    let mut lines = vec![message.content.clone()];
+    for embed in &message.embeds {
+        lines.extend(embed.url.clone());
+        lines.extend(embed.title.clone());
+        lines.extend(embed.description.clone());
+        for field in &embed.fields {
+            lines.push(format!("{}\n{}", field.name, field.value));
+        }
     }
+    lines.join("\n")
 }
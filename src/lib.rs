@@ -0,0 +1,68 @@
+pub mod analytics;
+#[cfg(feature = "api")]
+pub mod api;
+pub mod arguments;
+pub mod bot_config;
+pub mod cdclient;
+pub mod commands;
+pub mod custom;
+pub mod data;
+pub mod error;
+pub mod ids;
+pub mod inline;
+pub mod interaction_command;
+pub mod limits;
+pub mod locale;
+pub mod metrics;
+pub mod pager;
+#[cfg(feature = "prometheus")]
+pub mod prometheus;
+pub mod queries;
+pub mod ratelimit;
+pub mod references;
+pub mod registration;
+pub mod registry;
+pub mod render;
+pub mod repeat;
+pub mod response;
+pub mod scope;
+pub mod settings;
+
+// so `#[derive(CommandArguments)]` can name `::lu_discord_bot` inside this crate too
+extern crate self as lu_discord_bot;
+
+use std::sync::Arc;
+
+use bot_config::BotConfig;
+use cdclient::CdClient;
+use locale::LocaleTranslation;
+use once_cell::sync::Lazy;
+
+/// Loaded on first use, so a binary can set `CRAB_FORCE_*` overrides before that
+pub static CONFIG: Lazy<BotConfig> = Lazy::new(|| {
+    BotConfig::load().unwrap_or_else(|errors| {
+        eprintln!("Invalid configuration:");
+        for error in errors {
+            eprintln!("- {error}");
+        }
+        std::process::exit(1)
+    })
+});
+
+/// Access to the config and to the live data snapshot (see [`data::LuData`])
+/// - Implement it on anything that needs to query the data
+/// - Never store what these return; call them again so that a `/reload` is picked up
+/// - Inside an interaction both resolve to the settings of its guild (see [`scope`])
+pub trait Api {
+    fn config(&self) -> Arc<BotConfig> {
+        scope::config()
+    }
+
+    fn locale(&self) -> Arc<LocaleTranslation> {
+        data::current().locale(&scope::locale())
+    }
+
+    fn cdclient(&self) -> Arc<CdClient> {
+        data::current().cdclient
+    }
+}
@@ -0,0 +1,198 @@
+use tracing::debug;
+
+use crate::response::{ActionRow, Field, Response};
+
+/// Discord rejects the whole response when any of these is exceeded, so [`fit`] keeps to them
+/// - Lengths are in characters
+pub const TITLE: usize = 256;
+pub const DESCRIPTION: usize = 4096;
+pub const FIELDS: usize = 25;
+pub const FIELD_NAME: usize = 256;
+pub const FIELD_VALUE: usize = 1024;
+pub const FOOTER: usize = 2048;
+pub const AUTHOR_NAME: usize = 256;
+/// Title, description, field names and values, footer and author name together
+pub const TOTAL: usize = 6000;
+pub const ROWS: usize = 5;
+pub const BUTTONS_PER_ROW: usize = 5;
+pub const BUTTON_LABEL: usize = 80;
+pub const SELECT_OPTIONS: usize = 25;
+pub const SELECT_OPTION_TEXT: usize = 100;
+
+/// Discord rejects empty field names and values, so they get this instead
+const BLANK: &str = "\u{200b}";
+
+fn len(text: &str) -> usize {
+    text.chars().count()
+}
+
+fn more(hidden: usize) -> String {
+    format!("\u{2026}and {hidden} more")
+}
+
+/// Cuts `text` to `limit` characters, ending in `…` if anything was cut
+pub fn truncate_chars(text: &str, limit: usize) -> String {
+    if len(text) <= limit {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(limit.saturating_sub(1)).collect();
+    cut.push('\u{2026}');
+    cut
+}
+
+/// Keeps as many whole lines as fit in `limit`, ending in `…and N more` for the lines left out
+/// - Commands list one entry per line, so this never cuts an entry in half
+pub fn truncate_lines(text: &str, limit: usize) -> String {
+    if len(text) <= limit {
+        return text.to_string();
+    }
+    let lines: Vec<_> = text.lines().collect();
+    // the first line alone is too long, or `text` is a single line
+    let mut fitting = truncate_chars(text, limit);
+    let mut used = 0;
+    for (idx, line) in lines.iter().enumerate() {
+        used += len(line) + 1;
+        let notice = more(lines.len() - idx - 1);
+        if used + len(&notice) > limit {
+            break;
+        }
+        fitting = format!("{}\n{notice}", lines[..=idx].join("\n"));
+    }
+    fitting
+}
+
+/// What counts towards [`TOTAL`]
+pub fn total_len(response: &Response) -> usize {
+    let text = |text: &Option<String>| text.as_deref().map(len).unwrap_or(0);
+    text(&response.title)
+        + text(&response.description)
+        + response
+            .fields
+            .iter()
+            .map(|field| len(&field.name) + len(&field.value))
+            .sum::<usize>()
+        + response
+            .footer
+            .as_ref()
+            .map(|footer| len(&footer.text))
+            .unwrap_or(0)
+        + response
+            .author
+            .as_ref()
+            .map(|author| len(&author.name))
+            .unwrap_or(0)
+}
+
+fn non_empty(text: String) -> String {
+    if text.trim().is_empty() {
+        String::from(BLANK)
+    } else {
+        text
+    }
+}
+
+/// Makes the response fit every limit above, so Discord never rejects it
+/// - Text is cut at a line where it can be (see [`truncate_lines`])
+/// - Fields past [`FIELDS`] or [`TOTAL`] are dropped from the end and counted in a last field
+/// - Components past their limits are dropped
+pub fn fit(mut response: Response) -> Response {
+    let before = response.clone();
+
+    response.title = response.title.map(|title| truncate_chars(&title, TITLE));
+    response.description = response
+        .description
+        .map(|description| truncate_lines(&description, DESCRIPTION));
+    if let Some(footer) = response.footer.as_mut() {
+        footer.text = truncate_chars(&footer.text, FOOTER);
+    }
+    if let Some(author) = response.author.as_mut() {
+        author.name = truncate_chars(&author.name, AUTHOR_NAME);
+    }
+    let fields: Vec<_> = response
+        .fields
+        .drain(..)
+        .map(|field| Field {
+            name: non_empty(truncate_chars(&field.name, FIELD_NAME)),
+            value: non_empty(truncate_lines(&field.value, FIELD_VALUE)),
+            inline: field.inline,
+        })
+        .collect();
+
+    // keep as many leading fields as fit, leaving room for the notice of the rest
+    let notice = |hidden: usize| Field {
+        name: String::from(BLANK),
+        value: more(hidden),
+        inline: false,
+    };
+    let mut kept = fields.len().min(FIELDS);
+    loop {
+        let hidden = fields.len() - kept;
+        if hidden > 0 && kept == FIELDS {
+            kept -= 1;
+            continue;
+        }
+        response.fields = fields[..kept].to_vec();
+        if hidden > 0 {
+            response.fields.push(notice(hidden));
+        }
+        if total_len(&response) <= TOTAL || kept == 0 {
+            break;
+        }
+        kept -= 1;
+    }
+    // only a huge description is left to cut
+    let over = total_len(&response).saturating_sub(TOTAL);
+    if over > 0 {
+        if let Some(description) = response.description.as_ref() {
+            let limit = len(description).saturating_sub(over);
+            response.description = Some(truncate_lines(description, limit));
+        }
+    }
+
+    response.components = fit_components(response.components);
+
+    if response != before {
+        debug!(
+            title = before.title.as_deref().unwrap_or_default(),
+            "Response was cut to fit Discord's limits"
+        );
+    }
+    response
+}
+
+fn fit_components(rows: Vec<ActionRow>) -> Vec<ActionRow> {
+    rows.into_iter()
+        .flat_map(|row| match row {
+            // a row too long for Discord continues in the next
+            ActionRow::Buttons(buttons) => buttons
+                .chunks(BUTTONS_PER_ROW)
+                .map(|buttons| {
+                    let buttons = buttons
+                        .iter()
+                        .cloned()
+                        .map(|mut button| {
+                            button.label = truncate_chars(&button.label, BUTTON_LABEL);
+                            button
+                        })
+                        .collect();
+                    ActionRow::Buttons(buttons)
+                })
+                .collect::<Vec<_>>(),
+            ActionRow::SelectMenu(mut menu) => {
+                menu.options.truncate(SELECT_OPTIONS);
+                menu.placeholder = menu
+                    .placeholder
+                    .map(|placeholder| truncate_chars(&placeholder, SELECT_OPTION_TEXT));
+                for option in menu.options.iter_mut() {
+                    option.label = truncate_chars(&option.label, SELECT_OPTION_TEXT);
+                    option.description = option
+                        .description
+                        .as_ref()
+                        .map(|description| truncate_chars(description, SELECT_OPTION_TEXT));
+                }
+                vec![ActionRow::SelectMenu(menu)]
+            }
+        })
+        .take(ROWS)
+        .collect()
+}
@@ -1,4 +1,5 @@
-/// I only need the phrases in en_US so I will not deal with all the config parts
+/// Every locale is parsed, and phrases missing from one fall back to [`FALLBACK_LOCALE`]
+/// - I will not deal with all the config parts
 use std::{collections::BTreeMap, fs, path::Path};
 
 use once_cell::sync::Lazy;
@@ -25,6 +26,9 @@ impl<K: Ord + Clone, V: std::fmt::Debug + Default> ModifyExistingOrInsertDefault
     }
 }
 
+/// Locale that every other locale falls back to when a phrase is missing
+pub const FALLBACK_LOCALE: &str = "en_US";
+
 #[derive(Debug, Default)]
 pub struct LocaleXML {
     pub locales: BTreeMap<String, LocaleTranslation>,
@@ -189,6 +193,62 @@ impl LocaleXML {
         }
         Ok(xml)
     }
+
+    /// Fills the phrases each locale is missing with the ones from `fallback`
+    /// - Does nothing if `fallback` is not one of the locales
+    pub fn with_fallback(mut self, fallback: &str) -> Self {
+        let Some(fallback_translation) = self.locales.remove(fallback) else {
+            return self;
+        };
+        for translation in self.locales.values_mut() {
+            translation.fill_missing(&fallback_translation);
+        }
+        _ = self
+            .locales
+            .insert(fallback.to_string(), fallback_translation);
+        self
+    }
+}
+
+/// Copies whatever `self` is missing from `fallback`
+pub trait FillMissing {
+    fn fill_missing(&mut self, fallback: &Self);
+}
+
+impl<K: Ord + Clone, V: FillMissing + Clone> FillMissing for BTreeMap<K, V> {
+    fn fill_missing(&mut self, fallback: &Self) {
+        for (key, value) in fallback {
+            match self.get_mut(key) {
+                Some(this) => this.fill_missing(value),
+                None => _ = self.insert(key.clone(), value.clone()),
+            }
+        }
+    }
+}
+
+impl FillMissing for String {
+    fn fill_missing(&mut self, _: &Self) {}
+}
+
+impl<T: FillMissing + Clone> FillMissing for Option<T> {
+    fn fill_missing(&mut self, fallback: &Self) {
+        match (self.as_mut(), fallback) {
+            (Some(this), Some(fallback)) => this.fill_missing(fallback),
+            (None, Some(_)) => *self = fallback.clone(),
+            (_, None) => {}
+        }
+    }
+}
+
+/// Implements [`FillMissing`] field by field
+macro_rules! fill_missing {
+    ($ty:ty { $($field:ident),+ $(,)? }) => {
+        impl FillMissing for $ty {
+            fn fill_missing(&mut self, fallback: &Self) {
+                $(self.$field.fill_missing(&fallback.$field);)+
+            }
+        }
+    };
 }
 
 type LocaleMap<T> = BTreeMap<i32, T>;
@@ -208,6 +268,55 @@ pub struct LocaleTranslation {
     pub other: BTreeMap<String, String>,
 }
 
+fill_missing!(LocaleTranslation {
+    activities,
+    item_sets,
+    mission_email,
+    mission_tasks,
+    mission_text,
+    missions,
+    objects,
+    preconditions,
+    skill_behavior,
+    other,
+});
+fill_missing!(LocaleActivities { activity_name });
+fill_missing!(LocaleItemSets { kit_name });
+fill_missing!(LocaleMissionEmail {
+    announce_text,
+    body_text,
+    sender_name,
+    subject_text,
+});
+fill_missing!(LocaleMissionTasks { description });
+fill_missing!(LocaleMissionText {
+    accept_chat_bubble,
+    chat_state_1,
+    chat_state_2,
+    chat_state_3_turnin,
+    completion_succeed_tip,
+    in_progress,
+    offer,
+    ready_to_complete,
+    description,
+    chat_state_3,
+    chat_state_4,
+    chat_state_4_turnin,
+    offer_repeatable,
+});
+fill_missing!(LocaleMissions { name });
+fill_missing!(LocaleObjects { name, description });
+fill_missing!(LocalePreconditions { failure_reason });
+fill_missing!(LocaleSkillBehavior {
+    name,
+    description_ui,
+});
+
+/// A description is only ever replaced as a whole, so segments of two locales are never mixed
+impl FillMissing for SkillBehaviorDescriptionUI {
+    fn fill_missing(&mut self, _: &Self) {}
+}
+
 trait StartsWithStripped {
     /// check if it starts with substr and then returns remaining
     fn starts_with_remainder(&self, prefix: &str) -> Option<&str>;
@@ -406,11 +515,11 @@ Given
 <translation locale="en_US">%(DamageCombo) 3+4+4 %(ChargeUp)Does 12 damage to enemies</translation>
 ```
 Which would have already been parsed to
-```no_run
+```text
 "%(DamageCombo) 3+4+4 %(ChargeUp)Does 12 damage to enemies"
 ```
 Matches
-```no_run
+```text
 Some(Captures({
     0: Some("%(DamageCombo) 3+4+4 "),
     1: Some("DamageCombo"),
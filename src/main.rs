@@ -1,418 +1,590 @@
-pub mod bot_config;
-pub mod cdclient;
-mod commands;
-pub mod custom;
-pub mod ids;
-pub mod interaction_command;
-pub mod locale;
-pub mod pager;
-pub mod queries;
-pub mod repeat;
-
-use std::collections::BTreeMap;
-use std::env;
-use std::future::Future;
-use std::path::Path;
-use std::sync::{Arc, RwLock, RwLockReadGuard};
-use std::time::Instant;
-
-use bot_config::BotConfig;
-use cdclient::CdClient;
-use commands::achievement::AchievementCommand;
-use commands::activity::ActivityCommand;
-use commands::brick::BrickCommand;
-use commands::buy::BuyCommand;
-use commands::cooldowngroup::CooldownGroupCommand;
-use commands::drop::DropCommand;
-use commands::earn::EarnCommand;
-use commands::enemy::EnemyCommand;
-use commands::get::GetCommand;
-use commands::item::ItemCommand;
-use commands::level::LevelCommand;
-use commands::loottable::LootTableCommand;
-use commands::mission::MissionCommand;
-use commands::npc::NpcCommand;
-use commands::package::PackageCommand;
-use commands::preconditions::PreconditionsCommand;
-use commands::reward::RewardCommand;
-use commands::skill::SkillCommand;
-use commands::skillitems::SkillItemsCommand;
-use commands::skills::SkillsCommand;
-use commands::smash::SmashCommand;
-use commands::unpack::UnpackCommand;
-use commands::vendor::VendorCommand;
-use interaction_command::{CustomIdInteractionType, CustomIdOptions, InteractionCommand};
-use locale::{LocaleTranslation, LocaleXML};
+use std::time::{Duration, Instant};
+
+use lu_discord_bot::bot_config::BotConfig;
+use lu_discord_bot::commands::COMMANDS;
+use lu_discord_bot::error::{BotError, CorrelationId};
+use lu_discord_bot::interaction_command::{page_modal, CustomIdInteractionType, CustomIdOptions};
+use lu_discord_bot::metrics::{self, InteractionKind};
+use lu_discord_bot::ratelimit::{self, Cooldown};
+use lu_discord_bot::render::DiscordRenderer;
+use lu_discord_bot::scope::{self, Cancellation, InteractionScope};
+use lu_discord_bot::{analytics, data, inline, registration, settings, CONFIG};
 use once_cell::sync::Lazy;
-use queries::ObjectQueries;
 use serenity::all::{
-    AutocompleteChoice, CommandInteraction, ComponentInteraction, ComponentInteractionDataKind,
-    CreateActionRow, CreateAutocompleteResponse, CreateEmbed, CreateEmbedAuthor, CreateEmbedFooter,
+    AutocompleteChoice, CommandInteraction, ComponentInteractionDataKind, CreateAllowedMentions,
+    CreateAutocompleteResponse, CreateInteractionResponseMessage, CreateMessage, InteractionId,
+    Message,
 };
 use serenity::async_trait;
-use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage};
-use serenity::model::application::{Command, Interaction};
+use serenity::builder::CreateInteractionResponse;
+use serenity::json;
+use serenity::model::application::Interaction;
 use serenity::model::gateway::Ready;
-use serenity::model::id::GuildId;
 use serenity::prelude::*;
+use tokio::signal;
+use tokio::task::{self, JoinError, JoinHandle};
+use tokio::time;
+use tracing::{debug, error, info, warn};
+use tracing_subscriber::EnvFilter;
 
-static CD_CLIENT: Lazy<CdClient> =
-    Lazy::new(|| CdClient::load_sqlite(Path::new("/home/dgmastertemple/cdclient.sqlite")).unwrap());
-
-static LOCALE_XML: Lazy<LocaleXML> =
-    Lazy::new(|| LocaleXML::load_xml(Path::new("/home/dgmastertemple/locale.xml")).unwrap());
-
-static CONFIG: Lazy<BotConfig> = Lazy::new(|| BotConfig::default());
-
-#[derive(Default)]
-pub struct LocaleXML2 {
-    pub locales: BTreeMap<String, Arc<RwLock<LocaleTranslation>>>,
-}
-
-static RW_LOCALE_XML: Lazy<Arc<RwLock<LocaleXML2>>> =
-    Lazy::new(|| Arc::new(RwLock::new(LocaleXML2::default())));
-
-static RW_LOCALE: Lazy<Arc<RwLock<LocaleTranslation>>> = Lazy::new(|| {
-    let locale = &CONFIG.locale;
-    // path should be in config
-    let translation = LocaleXML::load_xml(Path::new("/home/dgmastertemple/locale.xml"))
-        .unwrap()
-        .locales
-        .remove(locale)
-        .expect(format!("Could not find locale {:?}", locale).as_str());
-    Arc::new(RwLock::new(translation))
-});
-
-static RW_CD_CLIENT: Lazy<Arc<RwLock<CdClient>>> = Lazy::new(|| {
-    Arc::new(RwLock::new(
-        CdClient::load_sqlite(Path::new("/home/dgmastertemple/cdclient.sqlite")).unwrap(),
-    ))
-});
-
-static RW_CONFIG: Lazy<Arc<RwLock<BotConfig>>> =
-    Lazy::new(|| Arc::new(RwLock::new(BotConfig::default())));
-
-// ALSO I CAN IMPL THIS ON MY COMMANDS
-pub trait Api {
-    fn config(&self) -> RwLockReadGuard<'_, BotConfig> {
-        RW_CONFIG.read().unwrap()
-    }
-
-    fn locale(&self) -> RwLockReadGuard<'_, LocaleTranslation> {
-        RW_LOCALE.read().unwrap()
-    }
-
-    fn cdclient(&self) -> RwLockReadGuard<'_, CdClient> {
-        RW_CD_CLIENT.read().unwrap()
-    }
-}
+struct Handler;
 
-pub fn handle_autocomplete(completion: &CommandInteraction) -> Option<Vec<AutocompleteChoice>> {
-    let option = completion.data.autocomplete()?;
-    let content = match completion.data.name.as_str() {
-        AchievementCommand::NAME => AchievementCommand::handle_autocomplete(option),
-        ActivityCommand::NAME => ActivityCommand::handle_autocomplete(option),
-        BrickCommand::NAME => BrickCommand::handle_autocomplete(option),
-        BuyCommand::NAME => BuyCommand::handle_autocomplete(option),
-        CooldownGroupCommand::NAME => CooldownGroupCommand::handle_autocomplete(option),
-        DropCommand::NAME => DropCommand::handle_autocomplete(option),
-        EarnCommand::NAME => EarnCommand::handle_autocomplete(option),
-        EnemyCommand::NAME => EnemyCommand::handle_autocomplete(option),
-        GetCommand::NAME => GetCommand::handle_autocomplete(option),
-        ItemCommand::NAME => ItemCommand::handle_autocomplete(option),
-        LevelCommand::NAME => LevelCommand::handle_autocomplete(option),
-        LootTableCommand::NAME => LootTableCommand::handle_autocomplete(option),
-        MissionCommand::NAME => MissionCommand::handle_autocomplete(option),
-        NpcCommand::NAME => NpcCommand::handle_autocomplete(option),
-        PackageCommand::NAME => PackageCommand::handle_autocomplete(option),
-        PreconditionsCommand::NAME => PreconditionsCommand::handle_autocomplete(option),
-        RewardCommand::NAME => RewardCommand::handle_autocomplete(option),
-        SkillCommand::NAME => SkillCommand::handle_autocomplete(option),
-        SkillItemsCommand::NAME => SkillItemsCommand::handle_autocomplete(option),
-        SkillsCommand::NAME => SkillsCommand::handle_autocomplete(option),
-        SmashCommand::NAME => SmashCommand::handle_autocomplete(option),
-        UnpackCommand::NAME => UnpackCommand::handle_autocomplete(option),
-        VendorCommand::NAME => VendorCommand::handle_autocomplete(option),
-        _ => None,
+/// Runs the autocomplete handler on the blocking pool, giving up after [`AUTOCOMPLETE_TIMEOUT`]
+async fn autocomplete_choices(completion: &CommandInteraction) -> Option<Vec<AutocompleteChoice>> {
+    let cancellation = Cancellation::default();
+    let handler = {
+        let completion = completion.clone();
+        let cancellation = cancellation.clone();
+        task::spawn_blocking(move || COMMANDS.handle_autocomplete(&completion, cancellation))
     };
-    content
-}
-
-pub fn handle_slash_command(
-    command: &CommandInteraction,
-) -> Option<CreateInteractionResponseMessage> {
-    match command.data.name.as_str() {
-        AchievementCommand::NAME => Some(AchievementCommand::handle_slash_command(command)),
-        ActivityCommand::NAME => Some(ActivityCommand::handle_slash_command(command)),
-        BrickCommand::NAME => Some(BrickCommand::handle_slash_command(command)),
-        BuyCommand::NAME => Some(BuyCommand::handle_slash_command(command)),
-        CooldownGroupCommand::NAME => Some(CooldownGroupCommand::handle_slash_command(command)),
-        DropCommand::NAME => Some(DropCommand::handle_slash_command(command)),
-        EarnCommand::NAME => Some(EarnCommand::handle_slash_command(command)),
-        EnemyCommand::NAME => Some(EnemyCommand::handle_slash_command(command)),
-        GetCommand::NAME => Some(GetCommand::handle_slash_command(command)),
-        ItemCommand::NAME => Some(ItemCommand::handle_slash_command(command)),
-        LevelCommand::NAME => Some(LevelCommand::handle_slash_command(command)),
-        LootTableCommand::NAME => Some(LootTableCommand::handle_slash_command(command)),
-        MissionCommand::NAME => Some(MissionCommand::handle_slash_command(command)),
-        NpcCommand::NAME => Some(NpcCommand::handle_slash_command(command)),
-        PackageCommand::NAME => Some(PackageCommand::handle_slash_command(command)),
-        PreconditionsCommand::NAME => Some(PreconditionsCommand::handle_slash_command(command)),
-        RewardCommand::NAME => Some(RewardCommand::handle_slash_command(command)),
-        SkillCommand::NAME => Some(SkillCommand::handle_slash_command(command)),
-        SkillItemsCommand::NAME => Some(SkillItemsCommand::handle_slash_command(command)),
-        SkillsCommand::NAME => Some(SkillsCommand::handle_slash_command(command)),
-        SmashCommand::NAME => Some(SmashCommand::handle_slash_command(command)),
-        UnpackCommand::NAME => Some(UnpackCommand::handle_slash_command(command)),
-        VendorCommand::NAME => Some(VendorCommand::handle_slash_command(command)),
-        _ => None,
-    }
-}
-
-pub fn handle_component_interaction(
-    interaction: &ComponentInteraction,
-    options: &CustomIdOptions,
-) -> Option<CreateInteractionResponseMessage> {
-    match options.cmd.as_str() {
-        AchievementCommand::NAME => Some(AchievementCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        ActivityCommand::NAME => Some(ActivityCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        BrickCommand::NAME => Some(BrickCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        BuyCommand::NAME => Some(BuyCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        CooldownGroupCommand::NAME => Some(CooldownGroupCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        DropCommand::NAME => Some(DropCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        EarnCommand::NAME => Some(EarnCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        EnemyCommand::NAME => Some(EnemyCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        GetCommand::NAME => Some(GetCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        ItemCommand::NAME => Some(ItemCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        LevelCommand::NAME => Some(LevelCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        LootTableCommand::NAME => Some(LootTableCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        MissionCommand::NAME => Some(MissionCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        NpcCommand::NAME => Some(NpcCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        PackageCommand::NAME => Some(PackageCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        PreconditionsCommand::NAME => Some(PreconditionsCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        RewardCommand::NAME => Some(RewardCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        SkillCommand::NAME => Some(SkillCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        SkillItemsCommand::NAME => Some(SkillItemsCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        SkillsCommand::NAME => Some(SkillsCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        SmashCommand::NAME => Some(SmashCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        UnpackCommand::NAME => Some(UnpackCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        VendorCommand::NAME => Some(VendorCommand::handle_component_interaction(
-            interaction,
-            options,
-        )),
-        _ => None,
+    match time::timeout(AUTOCOMPLETE_TIMEOUT, handler).await {
+        Ok(Ok(choices)) => choices,
+        Ok(Err(why)) => {
+            error!(command = %completion.data.name, "Autocomplete panicked: {why}");
+            None
+        }
+        Err(_) => {
+            cancellation.cancel();
+            None
+        }
     }
 }
 
-struct Handler;
+/// Discord drops autocomplete responses after 3 seconds, and they cannot be deferred
+const AUTOCOMPLETE_TIMEOUT: Duration = Duration::from_millis(2500);
 
 #[async_trait]
 impl EventHandler for Handler {
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-        if let Interaction::Autocomplete(ref completion) = interaction {
-            let start = Instant::now();
-            let content = handle_autocomplete(completion);
-            let time = start.elapsed().as_millis();
-            println!("Autocompletion query took {time}ms");
-
-            let data =
-                CreateAutocompleteResponse::new().set_choices(content.unwrap_or_else(|| vec![]));
+        if let Interaction::Autocomplete(completion) = interaction {
+            let name = COMMANDS.command_name(&completion.data);
+            let limited = ratelimit::check(
+                InteractionKind::Autocomplete,
+                name,
+                completion.user.id.get(),
+                completion.guild_id.map(|id| id.get()),
+            );
+            let choices = match limited {
+                Ok(()) => autocomplete_choices(&completion).await,
+                Err(cooldown) => {
+                    debug!(command = name, user = %completion.user.id, "Rate limited: {cooldown}");
+                    None
+                }
+            };
+
+            let data = CreateAutocompleteResponse::new().set_choices(choices.unwrap_or_default());
             let builder = CreateInteractionResponse::Autocomplete(data);
             if let Err(why) = completion.create_response(&ctx.http, builder).await {
-                println!("Cannot respond to auto-completion request: {why}");
+                metrics::record_discord_failure("autocomplete_response");
+                warn!(command = name, "Cannot respond to autocomplete: {why}");
             }
+            return;
         }
 
-        if let Interaction::Command(ref command) = interaction {
-            // println!("Received command interaction: {command:#?}");
-            let start = Instant::now();
-            let content = handle_slash_command(command);
-            let time = start.elapsed().as_millis();
-            println!("Slash Command query took {time}ms");
-
-            if let Some(content) = content {
-                let builder = CreateInteractionResponse::Message(content);
-                if let Err(why) = command.create_response(&ctx.http, builder).await {
-                    println!("Cannot respond to slash command: {why}");
-                }
+        if let Interaction::Command(command) = interaction {
+            let name = COMMANDS.command_name(&command.data);
+            let pending = PendingResponse {
+                id: command.id,
+                token: &command.token,
+                interaction: CustomIdInteractionType::Reply,
+                operation: "slash_command_response",
+                command: name,
+            };
+            let limited = ratelimit::check(
+                InteractionKind::Slash,
+                name,
+                command.user.id.get(),
+                command.guild_id.map(|id| id.get()),
+            );
+            if let Err(cooldown) = limited {
+                pending
+                    .cooldown(&ctx, InteractionScope::of_command(&command), cooldown)
+                    .await;
+                return;
             }
+
+            let cancellation = Cancellation::default();
+            let handler = {
+                let command = command.clone();
+                let cancellation = cancellation.clone();
+                task::spawn_blocking(move || COMMANDS.handle_slash_command(&command, cancellation))
+            };
+            pending.respond(&ctx, handler, cancellation).await;
+            return;
         }
 
-        if let Interaction::Component(ref interaction) = interaction {
-            let Ok(ref options) = (match &interaction.data.kind {
+        if let Interaction::Component(interaction) = interaction {
+            let options = match &interaction.data.kind {
                 ComponentInteractionDataKind::Button => {
                     CustomIdOptions::from_custom_id(interaction.data.custom_id.as_str())
                 }
                 ComponentInteractionDataKind::StringSelect { values } => values
                     .first()
-                    .ok_or_else(|| format!("No selection given"))
+                    .ok_or_else(|| BotError::BadArgument(String::from("No selection given")))
                     .and_then(|value| CustomIdOptions::from_custom_id(value)),
-                _ => Err(format!("Unsupported command interaction type")),
-            }) else {
-                return;
+                _ => Err(BotError::BadArgument(String::from(
+                    "Unsupported command interaction type",
+                ))),
+            };
+            let options = match options {
+                Ok(options) => options,
+                Err(e) => {
+                    let pending = PendingResponse {
+                        id: interaction.id,
+                        token: &interaction.token,
+                        interaction: CustomIdInteractionType::Reply,
+                        operation: "component_response",
+                        command: "unknown",
+                    };
+                    pending.outdated(&ctx, &interaction.data.custom_id, e).await;
+                    return;
+                }
             };
 
-            dbg!(&options);
+            let cmd = options.cmd.clone();
+            let pending = PendingResponse {
+                id: interaction.id,
+                token: &interaction.token,
+                interaction: options.interaction,
+                operation: "component_response",
+                command: &cmd,
+            };
+            if let Err(e) = COMMANDS.check_custom_id(&options) {
+                pending.outdated(&ctx, &interaction.data.custom_id, e).await;
+                return;
+            }
+            // opening the modal runs nothing; the page it is given comes back as a modal submit
+            if let CustomIdInteractionType::PageModal = options.interaction {
+                let modal = page_modal(&interaction.data.custom_id);
+                pending
+                    .create_response(&ctx, CreateInteractionResponse::Modal(modal))
+                    .await;
+                return;
+            }
+            let limited = ratelimit::check(
+                InteractionKind::Component,
+                &cmd,
+                interaction.user.id.get(),
+                interaction.guild_id.map(|id| id.get()),
+            );
+            if let Err(cooldown) = limited {
+                pending
+                    .cooldown(&ctx, InteractionScope::of_component(&interaction), cooldown)
+                    .await;
+                return;
+            }
 
-            let start = Instant::now();
-            if let Some(content) = handle_component_interaction(interaction, options) {
-                let time = start.elapsed().as_millis();
-                println!("Component query took {time}ms");
-                let builder = match options.interaction {
-                    CustomIdInteractionType::Reply => CreateInteractionResponse::Message(content),
-                    CustomIdInteractionType::Update => {
-                        CreateInteractionResponse::UpdateMessage(content)
+            let cancellation = Cancellation::default();
+            let handler = {
+                let interaction = interaction.clone();
+                let cancellation = cancellation.clone();
+                task::spawn_blocking(move || {
+                    COMMANDS.handle_component_interaction(&interaction, &options, cancellation)
+                })
+            };
+            pending.respond(&ctx, handler, cancellation).await;
+            return;
+        }
+
+        if let Interaction::Modal(modal) = interaction {
+            let options = match CustomIdOptions::from_page_modal(&modal) {
+                Ok(options) => options,
+                Err(error) => {
+                    let content = CreateInteractionResponseMessage::new()
+                        .embed(DiscordRenderer.embed(&CONFIG.error_embed(&error)))
+                        .ephemeral(true);
+                    let builder = CreateInteractionResponse::Message(content);
+                    if let Err(why) = modal.create_response(&ctx.http, builder).await {
+                        metrics::record_discord_failure("modal_response");
+                        warn!(custom_id = %modal.data.custom_id, "Cannot respond to modal: {why}");
                     }
-                };
-                if let Err(why) = interaction.create_response(&ctx.http, builder).await {
-                    println!("Cannot respond to slash interaction: {why}");
+                    return;
                 }
+            };
+
+            let cmd = options.cmd.clone();
+            let pending = PendingResponse {
+                id: modal.id,
+                token: &modal.token,
+                interaction: options.interaction,
+                operation: "modal_response",
+                command: &cmd,
+            };
+            if let Err(e) = COMMANDS.check_custom_id(&options) {
+                pending.outdated(&ctx, &modal.data.custom_id, e).await;
+                return;
+            }
+            let limited = ratelimit::check(
+                InteractionKind::Component,
+                &cmd,
+                modal.user.id.get(),
+                modal.guild_id.map(|id| id.get()),
+            );
+            if let Err(cooldown) = limited {
+                pending
+                    .cooldown(&ctx, InteractionScope::of_modal(&modal), cooldown)
+                    .await;
+                return;
             }
+
+            let cancellation = Cancellation::default();
+            let handler = {
+                let modal = modal.clone();
+                let cancellation = cancellation.clone();
+                task::spawn_blocking(move || {
+                    COMMANDS.handle_modal_submit(&modal, &options, cancellation)
+                })
+            };
+            pending.respond(&ctx, handler, cancellation).await;
+        }
+    }
+
+    /// Only received with `message_content_intent`, for inline lookups
+    async fn message(&self, ctx: Context, message: Message) {
+        if !inline::wants_cards(&message) {
+            return;
+        }
+        let limited = ratelimit::check(
+            InteractionKind::Slash,
+            inline::NAME,
+            message.author.id.get(),
+            message.guild_id.map(|id| id.get()),
+        );
+        if let Err(cooldown) = limited {
+            debug!(command = inline::NAME, user = %message.author.id, "Rate limited: {cooldown}");
+            return;
+        }
+
+        let cancellation = Cancellation::default();
+        let handler = {
+            let message = message.clone();
+            let cancellation = cancellation.clone();
+            task::spawn_blocking(move || inline::cards(&message, cancellation))
+        };
+        let timeout = Duration::from_secs(CONFIG.handler_timeout_secs);
+        let cards = match time::timeout(timeout, handler).await {
+            Ok(Ok(cards)) => cards,
+            Ok(Err(why)) => {
+                error!(command = inline::NAME, "Inline lookup panicked: {why}");
+                return;
+            }
+            Err(_) => {
+                cancellation.cancel();
+                return;
+            }
+        };
+        if cards.is_empty() {
+            return;
+        }
+
+        let reply = CreateMessage::new()
+            .reference_message(&message)
+            .allowed_mentions(CreateAllowedMentions::new())
+            .embeds(cards);
+        if let Err(why) = message.channel_id.send_message(&ctx.http, reply).await {
+            metrics::record_discord_failure("inline_reply");
+            warn!(command = inline::NAME, "Cannot reply to a message: {why}");
         }
     }
 
     async fn ready(&self, ctx: Context, ready: Ready) {
-        println!("{} is connected!", ready.user.name);
+        info!("{} is connected!", ready.user.name);
+
+        registration::register_commands(&ctx).await;
+    }
+}
+
+/// A slash command, component interaction or modal submit whose handler runs on the blocking pool
+struct PendingResponse<'a> {
+    id: InteractionId,
+    token: &'a str,
+    interaction: CustomIdInteractionType,
+    /// For [`metrics::record_discord_failure`]
+    operation: &'static str,
+    command: &'a str,
+}
+
+impl PendingResponse<'_> {
+    /// - Responds right away if the handler finishes within `defer_after_ms`
+    /// - Otherwise defers, so the user sees "thinking…" (or a loading button), and edits the
+    ///   response in once the handler finishes
+    /// - Cancels the handler after `handler_timeout_secs` and edits in an error instead
+    ///
+    /// A deferred reply is never ephemeral, since that is decided before the handler finishes
+    async fn respond(
+        &self,
+        ctx: &Context,
+        mut handler: JoinHandle<Option<CreateInteractionResponseMessage>>,
+        cancellation: Cancellation,
+    ) {
+        let defer_after = Duration::from_millis(CONFIG.defer_after_ms);
+        let timeout = Duration::from_secs(CONFIG.handler_timeout_secs);
+
+        if let Ok(result) = time::timeout(defer_after, &mut handler).await {
+            let Some(content) = self.finished(result) else {
+                return;
+            };
+            let builder = match self.interaction {
+                CustomIdInteractionType::Reply => CreateInteractionResponse::Message(content),
+                // a modal submit is an update by then (see `CustomIdOptions::from_page_modal`)
+                CustomIdInteractionType::Update | CustomIdInteractionType::PageModal => {
+                    CreateInteractionResponse::UpdateMessage(content)
+                }
+            };
+            self.create_response(ctx, builder).await;
+            return;
+        }
+
+        let builder = match self.interaction {
+            CustomIdInteractionType::Reply => {
+                CreateInteractionResponse::Defer(CreateInteractionResponseMessage::new())
+            }
+            CustomIdInteractionType::Update | CustomIdInteractionType::PageModal => {
+                CreateInteractionResponse::Acknowledge
+            }
+        };
+        if !self.create_response(ctx, builder).await {
+            cancellation.cancel();
+            return;
+        }
+
+        let content = match time::timeout(timeout.saturating_sub(defer_after), handler).await {
+            Ok(result) => self.finished(result),
+            Err(_) => {
+                cancellation.cancel();
+                let response = CONFIG.failure_embed(
+                    "Timed Out",
+                    format!(
+                        "This took longer than {} seconds, try something more specific!",
+                        timeout.as_secs()
+                    ),
+                );
+                Some(
+                    CreateInteractionResponseMessage::new().embed(DiscordRenderer.embed(&response)),
+                )
+            }
+        };
+        // the deferred response must be replaced, or it keeps "thinking…"
+        let content = content.unwrap_or_else(|| {
+            let response =
+                CONFIG.failure_embed("Unknown Command", "This command no longer exists!");
+            CreateInteractionResponseMessage::new().embed(DiscordRenderer.embed(&response))
+        });
+        self.edit_response(ctx, &content).await;
+    }
+
+    /// Replies only to the user with when they can try again, instead of running the handler
+    async fn cooldown(&self, ctx: &Context, scope: InteractionScope, cooldown: Cooldown) {
+        info!(
+            command = self.command,
+            user = scope.user_id,
+            "Rate limited: {cooldown}"
+        );
+        let (response, _) = scope::with_scope(scope, || cooldown.embed(self.command));
+        let content = CreateInteractionResponseMessage::new()
+            .embed(DiscordRenderer.embed(&response))
+            .ephemeral(true);
+        self.create_response(ctx, CreateInteractionResponse::Message(content))
+            .await;
+    }
 
-        let guild_id = GuildId::new(
-            env::var("GUILD_ID")
-                .expect("Expected GUILD_ID in environment")
-                .parse()
-                .expect("GUILD_ID must be an integer"),
+    /// Replies only to the user that the message is outdated, instead of running its custom id
+    /// - Ex: the bot was updated since the message was sent, and its arguments changed
+    async fn outdated(&self, ctx: &Context, custom_id: &str, why: BotError) {
+        info!(
+            command = self.command,
+            custom_id, "Outdated custom id: {why}"
         );
+        let response = CONFIG.failure_embed(
+            "Outdated Message",
+            "This message is outdated, run the command again!",
+        );
+        let content = CreateInteractionResponseMessage::new()
+            .embed(DiscordRenderer.embed(&response))
+            .ephemeral(true);
+        self.create_response(ctx, CreateInteractionResponse::Message(content))
+            .await;
+    }
+
+    /// The response of a handler that returned, or an error embed if it panicked
+    fn finished(
+        &self,
+        result: Result<Option<CreateInteractionResponseMessage>, JoinError>,
+    ) -> Option<CreateInteractionResponseMessage> {
+        result.unwrap_or_else(|why| {
+            let id = CorrelationId::new();
+            error!(correlation_id = %id, command = self.command, "Handler panicked: {why}");
+            let error = BotError::Internal {
+                id,
+                message: why.to_string(),
+            };
+            let response = CONFIG.error_embed(&error);
+            Some(CreateInteractionResponseMessage::new().embed(DiscordRenderer.embed(&response)))
+        })
+    }
+
+    /// Whether Discord accepted the response
+    async fn create_response(&self, ctx: &Context, builder: CreateInteractionResponse) -> bool {
+        let result = ctx
+            .http
+            .create_interaction_response(self.id, self.token, &builder, vec![])
+            .await;
+        if let Err(ref why) = result {
+            metrics::record_discord_failure(self.operation);
+            warn!(
+                command = self.command,
+                "Cannot respond to interaction: {why}"
+            );
+        }
+        result.is_ok()
+    }
 
-        let commands = guild_id
-            .set_commands(
-                &ctx.http,
-                vec![
-                    AchievementCommand::register(),
-                    ActivityCommand::register(),
-                    BrickCommand::register(),
-                    BuyCommand::register(),
-                    CooldownGroupCommand::register(),
-                    DropCommand::register(),
-                    EarnCommand::register(),
-                    EnemyCommand::register(),
-                    GetCommand::register(),
-                    ItemCommand::register(),
-                    LevelCommand::register(),
-                    LootTableCommand::register(),
-                    MissionCommand::register(),
-                    NpcCommand::register(),
-                    PackageCommand::register(),
-                    PreconditionsCommand::register(),
-                    RewardCommand::register(),
-                    SkillCommand::register(),
-                    SkillItemsCommand::register(),
-                    SkillsCommand::register(),
-                    SmashCommand::register(),
-                    UnpackCommand::register(),
-                    VendorCommand::register(),
-                ],
-            )
+    async fn edit_response(&self, ctx: &Context, content: &CreateInteractionResponseMessage) {
+        // an edit takes the same fields as a response, except these
+        let mut body = match json::to_value(content) {
+            Ok(body) => body,
+            Err(why) => {
+                error!(command = self.command, "Cannot serialize response: {why}");
+                return;
+            }
+        };
+        if let Some(body) = body.as_object_mut() {
+            body.remove("tts");
+            body.remove("flags");
+        }
+        let result = ctx
+            .http
+            .edit_original_interaction_response(self.token, &body, vec![])
             .await;
+        if let Err(why) = result {
+            metrics::record_discord_failure("edit_response");
+            warn!(
+                command = self.command,
+                "Cannot edit deferred response: {why}"
+            );
+        }
+    }
+}
 
-        println!("I now have the following guild slash commands: {commands:#?}");
+/// Logs to stdout, filtered by `RUST_LOG` (ex: `RUST_LOG=lu_discord_bot=debug`)
+/// - With `log_json` every line is a JSON object, including the fields of its spans
+fn init_logging() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+    if CONFIG.log_json {
+        builder.json().init();
+    } else {
+        builder.init();
     }
 }
 
 #[tokio::main]
 async fn main() {
-    // Configure the client with your Discord bot token in the environment.
-    let token = env::var("DISCORD_TOKEN").expect("Expected a token in the environment");
-
-    // {
-    //     // force it to load
-    //     let start = Instant::now();
-    //     let levels = &RW_CD_CLIENT.level_progression_lookup.len();
-    //     println!("Total levels: {levels}");
-    //     let end = start.elapsed().as_millis();
-    //     println!("'cdclient.sqlite' loaded in {end}ms");
-    //     // force it to load
-    //     let start = Instant::now();
-    //     let locale_count = &RW_LOCALE_XML.locales.len();
-    //     println!("Total locales: {locale_count}");
-    //     let end = start.elapsed().as_millis();
-    //     println!("'locale.xml' loaded in {end}ms");
-    // }
+    // Report every configuration problem before connecting, the token included
+    let token = match BotConfig::load_bot() {
+        Ok((_, token)) => token,
+        Err(errors) => {
+            eprintln!("Invalid configuration:");
+            for error in errors {
+                eprintln!("- {error}");
+            }
+            std::process::exit(1)
+        }
+    };
+    // reads the same config, which is valid now
+    Lazy::force(&CONFIG);
+    init_logging();
+    Lazy::force(&metrics::STARTED_AT);
+    if let Err(e) = settings::init().and_then(|_| analytics::init()) {
+        error!("{e}");
+        std::process::exit(1)
+    }
+    // Panics on a duplicate or misrouted command before connecting
+    Lazy::force(&COMMANDS);
+    for name in CONFIG.rate_limits.commands.keys() {
+        let is_message_command = COMMANDS
+            .message_commands()
+            .any(|command| command.name == name);
+        if COMMANDS.get(name).is_none() && !is_message_command && name != inline::NAME {
+            warn!("`rate_limits.commands.{name}` is not a command, so it never applies");
+        }
+    }
+
+    if let Some(addr) = CONFIG.metrics_addr {
+        #[cfg(feature = "prometheus")]
+        tokio::spawn(lu_discord_bot::prometheus::serve(addr));
+        #[cfg(not(feature = "prometheus"))]
+        warn!("`metrics_addr` is set to {addr}, but the bot was built without `--features prometheus`");
+    }
+    if let Some(addr) = CONFIG.api_addr {
+        #[cfg(feature = "api")]
+        tokio::spawn(lu_discord_bot::api::serve(addr));
+        #[cfg(not(feature = "api"))]
+        warn!("`api_addr` is set to {addr}, but the bot was built without `--features api`");
+    }
+
+    // Load the data files now instead of on the first command
+    let start = Instant::now();
+    let data = data::current();
+    info!(
+        objects = data.cdclient.objects.len(),
+        missions = data.locale(&CONFIG.locale).missions.len(),
+        locales = data.locales.len(),
+        "Loaded data files in {}ms",
+        start.elapsed().as_millis()
+    );
+    drop(data);
+
+    if CONFIG.watch_data_files {
+        data::spawn_watcher(Duration::from_secs(CONFIG.watch_interval_secs));
+    }
+
+    // Messages are only read for inline lookups, which servers opt into
+    let intents = match CONFIG.message_content_intent {
+        true => GatewayIntents::GUILD_MESSAGES | GatewayIntents::MESSAGE_CONTENT,
+        false => GatewayIntents::empty(),
+    };
 
     // Build our client.
-    let mut client = Client::builder(token, GatewayIntents::empty())
+    let mut client = Client::builder(token, intents)
         .event_handler(Handler)
         .await
         .expect("Error creating client");
 
+    let shard_manager = client.shard_manager.clone();
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        info!("Shutting down");
+        shard_manager.shutdown_all().await;
+    });
+
     // Finally, start a single shard, and start listening to events.
     //
     // Shards will automatically attempt to reconnect, and will perform exponential backoff until
     // it reconnects.
     if let Err(why) = client.start().await {
-        println!("Client error: {why:?}");
+        error!("Client error: {why}");
+    }
+
+    // what was recorded since the last batch
+    if let Err(e) = analytics::flush() {
+        error!("Could not record interactions: {e}");
+    }
+}
+
+/// Ctrl+C, or SIGTERM from a service manager or `docker stop`
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        let mut terminate = signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("Could not listen for SIGTERM");
+        tokio::select! {
+            _ = signal::ctrl_c() => {}
+            _ = terminate.recv() => {}
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        _ = signal::ctrl_c().await;
     }
 }
@@ -0,0 +1,275 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::Display;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+
+use crate::error::BotError;
+
+/// How many of the latest latencies are kept per command and kind
+pub const WINDOW: usize = 500;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum InteractionKind {
+    Slash,
+    Component,
+    Autocomplete,
+}
+
+impl Display for InteractionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                InteractionKind::Slash => "slash",
+                InteractionKind::Component => "component",
+                InteractionKind::Autocomplete => "autocomplete",
+            }
+        )
+    }
+}
+
+/// How an interaction ended
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Ok,
+    /// The command returned an error embed
+    Error(BotError),
+    /// The user may not run the command
+    Denied,
+    /// The handler ran past `handler_timeout_secs` and was cancelled
+    TimedOut,
+}
+
+impl Outcome {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Outcome::Ok => "ok",
+            Outcome::Error(_) => "error",
+            Outcome::Denied => "denied",
+            Outcome::TimedOut => "timed_out",
+        }
+    }
+}
+
+/// Upper bounds of the latency histogram buckets, in seconds
+pub const BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Cumulative like Prometheus expects: a sample counts towards every bucket it fits in
+#[derive(Clone, Debug, Default)]
+pub struct Histogram {
+    pub buckets: [u64; BUCKETS.len()],
+    pub sum: f64,
+    pub count: u64,
+}
+
+impl Histogram {
+    pub fn observe(&mut self, duration: Duration) {
+        let secs = duration.as_secs_f64();
+        for (bucket, bound) in self.buckets.iter_mut().zip(BUCKETS) {
+            if secs <= bound {
+                *bucket += 1;
+            }
+        }
+        self.sum += secs;
+        self.count += 1;
+    }
+
+    /// The samples of `name` in the Prometheus text format, ex: `labels` is `file="locale.xml"`
+    /// - Without labels, `_sum` and `_count` have no braces at all
+    #[cfg(feature = "prometheus")]
+    pub fn render_prometheus(&self, name: &str, labels: &str) -> String {
+        use std::fmt::Write;
+
+        let mut out = String::new();
+        let sep = if labels.is_empty() { "" } else { "," };
+        for (bound, count) in BUCKETS.iter().zip(self.buckets) {
+            _ = writeln!(out, "{name}_bucket{{{labels}{sep}le=\"{bound}\"}} {count}");
+        }
+        _ = writeln!(
+            out,
+            "{name}_bucket{{{labels}{sep}le=\"+Inf\"}} {}",
+            self.count
+        );
+        let labels = if labels.is_empty() {
+            String::new()
+        } else {
+            format!("{{{labels}}}")
+        };
+        _ = writeln!(out, "{name}_sum{labels} {}", self.sum);
+        _ = writeln!(out, "{name}_count{labels} {}", self.count);
+        out
+    }
+}
+
+#[derive(Default)]
+struct CommandMetrics {
+    latencies: VecDeque<Duration>,
+    histogram: Histogram,
+    count: u64,
+    errors: u64,
+    outcomes: BTreeMap<&'static str, u64>,
+    error_classes: BTreeMap<&'static str, u64>,
+}
+
+/// Rolling latencies of one command and kind, see [`summary`]
+#[derive(Clone, Debug)]
+pub struct CommandSummary {
+    pub command: String,
+    pub kind: InteractionKind,
+    /// Every interaction since startup
+    pub count: u64,
+    pub errors: u64,
+    /// Over the last [`WINDOW`] interactions
+    pub p50: Duration,
+    pub p95: Duration,
+}
+
+static METRICS: Lazy<Mutex<BTreeMap<(String, InteractionKind), CommandMetrics>>> =
+    Lazy::new(|| Mutex::new(BTreeMap::new()));
+
+pub static STARTED_AT: Lazy<Instant> = Lazy::new(Instant::now);
+
+pub fn record(command: &str, kind: InteractionKind, latency: Duration, outcome: &Outcome) {
+    let mut metrics = METRICS.lock().unwrap();
+    let entry = metrics.entry((command.to_string(), kind)).or_default();
+    if entry.latencies.len() == WINDOW {
+        entry.latencies.pop_front();
+    }
+    entry.latencies.push_back(latency);
+    entry.histogram.observe(latency);
+    entry.count += 1;
+    *entry.outcomes.entry(outcome.label()).or_default() += 1;
+    if let Outcome::Error(error) = outcome {
+        entry.errors += 1;
+        *entry.error_classes.entry(error.kind()).or_default() += 1;
+    }
+}
+
+static DISCORD_FAILURES: Lazy<Mutex<BTreeMap<&'static str, u64>>> =
+    Lazy::new(|| Mutex::new(BTreeMap::new()));
+
+/// A request to Discord failed, ex: `operation = "create_response"`
+pub fn record_discord_failure(operation: &'static str) {
+    *DISCORD_FAILURES
+        .lock()
+        .unwrap()
+        .entry(operation)
+        .or_default() += 1;
+}
+
+static DATA_LOADS: Lazy<Mutex<BTreeMap<&'static str, Histogram>>> =
+    Lazy::new(|| Mutex::new(BTreeMap::new()));
+
+/// How long loading a data file took, ex: `file = "cdclient"`
+pub fn record_data_load(file: &'static str, duration: Duration) {
+    DATA_LOADS
+        .lock()
+        .unwrap()
+        .entry(file)
+        .or_default()
+        .observe(duration);
+}
+
+/// `sorted` must be sorted and non-empty
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    let idx = ((sorted.len() - 1) as f64 * p).round() as usize;
+    sorted[idx]
+}
+
+pub fn summary() -> Vec<CommandSummary> {
+    let metrics = METRICS.lock().unwrap();
+    metrics
+        .iter()
+        .filter(|(_, entry)| !entry.latencies.is_empty())
+        .map(|((command, kind), entry)| {
+            let mut sorted: Vec<_> = entry.latencies.iter().copied().collect();
+            sorted.sort();
+            CommandSummary {
+                command: command.clone(),
+                kind: *kind,
+                count: entry.count,
+                errors: entry.errors,
+                p50: percentile(&sorted, 0.50),
+                p95: percentile(&sorted, 0.95),
+            }
+        })
+        .collect()
+}
+
+/// A label value as the Prometheus text format needs it: `\`, `"` and newlines escaped
+#[cfg(feature = "prometheus")]
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Every metric in the Prometheus text format
+#[cfg(feature = "prometheus")]
+pub fn render_prometheus() -> String {
+    use std::fmt::Write;
+
+    let mut out = String::new();
+    let metrics = METRICS.lock().unwrap();
+
+    out.push_str("# HELP crab_force_interactions_total Interactions handled, by outcome\n");
+    out.push_str("# TYPE crab_force_interactions_total counter\n");
+    for ((command, kind), entry) in metrics.iter() {
+        let command = escape_label(command);
+        for (outcome, count) in &entry.outcomes {
+            _ = writeln!(
+                out,
+                "crab_force_interactions_total{{command=\"{command}\",kind=\"{kind}\",outcome=\"{outcome}\"}} {count}"
+            );
+        }
+    }
+
+    out.push_str("# HELP crab_force_interaction_duration_seconds Time spent in a handler\n");
+    out.push_str("# TYPE crab_force_interaction_duration_seconds histogram\n");
+    for ((command, kind), entry) in metrics.iter() {
+        let labels = format!("command=\"{}\",kind=\"{kind}\"", escape_label(command));
+        out.push_str(
+            &entry
+                .histogram
+                .render_prometheus("crab_force_interaction_duration_seconds", &labels),
+        );
+    }
+
+    out.push_str("# HELP crab_force_errors_total Error embeds sent, by class\n");
+    out.push_str("# TYPE crab_force_errors_total counter\n");
+    for ((command, kind), entry) in metrics.iter() {
+        let command = escape_label(command);
+        for (class, count) in &entry.error_classes {
+            _ = writeln!(
+                out,
+                "crab_force_errors_total{{command=\"{command}\",kind=\"{kind}\",class=\"{class}\"}} {count}"
+            );
+        }
+    }
+    drop(metrics);
+
+    out.push_str("# HELP crab_force_discord_api_failures_total Failed requests to Discord\n");
+    out.push_str("# TYPE crab_force_discord_api_failures_total counter\n");
+    for (operation, count) in DISCORD_FAILURES.lock().unwrap().iter() {
+        let operation = escape_label(operation);
+        _ = writeln!(
+            out,
+            "crab_force_discord_api_failures_total{{operation=\"{operation}\"}} {count}"
+        );
+    }
+
+    out.push_str("# HELP crab_force_data_load_duration_seconds Time spent loading a data file\n");
+    out.push_str("# TYPE crab_force_data_load_duration_seconds histogram\n");
+    for (file, loads) in DATA_LOADS.lock().unwrap().iter() {
+        let labels = format!("file=\"{}\"", escape_label(file));
+        out.push_str(&loads.render_prometheus("crab_force_data_load_duration_seconds", &labels));
+    }
+
+    out
+}
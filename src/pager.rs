@@ -1,9 +1,16 @@
 /*
-* - This I believe should cover pages where it is just Discord Embed Fields
-* - This doesn't paginate the Discord Embed Description
+* - This covers pages of Discord Embed Fields, of the Description ([`Pager::lines`]),
+*   of columns of fields ([`Pager::columns`]) and of select menu options ([`Pager::select_menu`])
+* - The page is kept in the custom id, ex: `DropArguments { page }`, and [`Pager::row`] builds the
+*   buttons that change it
+* - Commands that let the user pick a page size keep it there too, ex: `DropArguments { size }`,
+*   and [`Pager::size_menu`] builds the menu that changes it
 */
 
 use crate::commands::package::PackageArguments;
+use crate::interaction_command::ToCustomId;
+use crate::limits::SELECT_OPTIONS;
+use crate::response::{ActionRow, Button, SelectMenu, SelectOption};
 
 /// Page starts at 1
 pub const MIN_PAGE: usize = 1;
@@ -13,6 +20,13 @@ pub const START_PAGE: usize = MIN_PAGE;
 /// This is discords field limit
 // pub const PAGE_SIZE: usize = 15;
 
+/// What [`Pager::size_menu`] offers
+pub const PAGE_SIZES: [usize; 3] = [5, 10, 15];
+pub const DEFAULT_PAGE_SIZE: usize = PAGE_SIZES[0];
+
+/// Discord won't allow duplicate custom ids in a message, so this is never a command name
+const PAGE_SIZE_MENU: &str = "-page-size";
+
 /// Page starts at 1
 #[derive(Clone, Debug)]
 pub struct Pager<T: Clone> {
@@ -25,13 +39,15 @@ pub struct Pager<T: Clone> {
 
 impl<T: Clone> Pager<T> {
     pub fn new(entries: Vec<T>, page: usize, page_size: usize) -> Self {
+        let page_size = page_size.max(1);
         let mut max_page =
             (entries.len() / page_size) + (if entries.len() % page_size == 0 { 0 } else { 1 });
         if max_page < MIN_PAGE {
             max_page = MIN_PAGE;
         }
         Self {
-            current: page,
+            // the data may have shrunk since the custom id was made, ex: after a `/reload`
+            current: page.clamp(MIN_PAGE, max_page),
             min_page: MIN_PAGE,
             max_page,
             page_size,
@@ -63,6 +79,29 @@ impl<T: Clone> Pager<T> {
         self.min_page == self.max_page
     }
 
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// The last page, which is [`MIN_PAGE`] when there are no entries
+    pub fn page_count(&self) -> usize {
+        self.max_page
+    }
+
+    pub fn page_size(&self) -> usize {
+        self.page_size
+    }
+
+    /// Entries on every page together
+    pub fn total(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether `page` can be shown; a pager made with any other page shows the nearest one
+    pub fn has_page(&self, page: usize) -> bool {
+        (self.min_page..=self.max_page).contains(&page)
+    }
+
     /// This returns all elements on this page with their 1-based entry number and entry
     pub fn this_page(&self) -> Vec<(usize, T)> {
         // page starts at 1
@@ -75,4 +114,106 @@ impl<T: Clone> Pager<T> {
             .collect();
         paged_entries
     }
+
+    /// The buttons to the other pages, or nothing when there is only one page
+    /// - `to_page` gives the arguments of the command at another page, ex:
+    ///   `|page| DropArguments { item, page }`
+    /// - First and Last are left out when the previous or next page is already them, since
+    ///   Discord won't allow two buttons with the same custom id
+    /// - Go to page… asks for the page in a modal (see [`ToCustomId::to_page_modal_button`])
+    pub fn row<A: ToCustomId>(&self, to_page: impl Fn(usize) -> A) -> Option<ActionRow> {
+        if self.is_one_page() {
+            return None;
+        }
+        let mut buttons: Vec<Button> = vec![];
+        if self.prev() > self.min_page {
+            buttons.push(to_page(self.min_page).to_update_button("First"));
+        }
+        buttons.push(
+            to_page(self.prev())
+                .to_update_button(format!("Page {}", self.prev()))
+                .disabled(self.is_first_page()),
+        );
+        buttons.push(
+            to_page(self.current)
+                .to_page_modal_button(format!("Go to page… ({}/{})", self.current, self.max_page)),
+        );
+        buttons.push(
+            to_page(self.next())
+                .to_update_button(format!("Page {}", self.next()))
+                .disabled(self.is_last_page()),
+        );
+        if self.next() < self.max_page {
+            buttons.push(to_page(self.max_page).to_update_button("Last"));
+        }
+        Some(ActionRow::Buttons(buttons))
+    }
+
+    /// A menu of [`PAGE_SIZES`], or nothing when every entry fits on the smallest page
+    /// - `to_size` gives the arguments of the command at a page and page size, ex:
+    ///   `|page, size| DropArguments { item, page, size }`
+    /// - Each size opens the page with the first entry shown now
+    pub fn size_menu<A: ToCustomId>(
+        &self,
+        to_size: impl Fn(usize, usize) -> A,
+    ) -> Option<ActionRow> {
+        if self.entries.len() <= PAGE_SIZES[0] {
+            return None;
+        }
+        let first_entry = (self.current - 1) * self.page_size;
+        let options = PAGE_SIZES
+            .into_iter()
+            .map(|size| {
+                let page = first_entry / size + 1;
+                SelectOption::new(
+                    format!("{size} per page"),
+                    to_size(page, size).to_custom_id(false),
+                )
+            })
+            .collect();
+        let menu = SelectMenu::new(PAGE_SIZE_MENU, options)
+            .placeholder(format!("{} per page", self.page_size));
+        Some(ActionRow::SelectMenu(menu))
+    }
+
+    /// The options of the entries on this page, or nothing when there are none
+    /// - Keep the page size at most [`SELECT_OPTIONS`], since the rest are left out
+    pub fn select_menu(
+        &self,
+        custom_id: &str,
+        to_option: impl Fn(T) -> SelectOption,
+    ) -> Option<ActionRow> {
+        let options: Vec<_> = self
+            .this_page()
+            .into_iter()
+            .map(|(_, entry)| to_option(entry))
+            .take(SELECT_OPTIONS)
+            .collect();
+        (!options.is_empty()).then(|| ActionRow::SelectMenu(SelectMenu::new(custom_id, options)))
+    }
+
+    /// The entries on this page, one per line, ex: for the description
+    /// - `to_line` gets the 1-based entry number and entry, like [`Self::this_page`]
+    pub fn lines(&self, to_line: impl Fn(usize, T) -> String) -> String {
+        self.this_page()
+            .into_iter()
+            .map(|(num, entry)| to_line(num, entry))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The entries on this page split into `count` columns, like [`Self::lines`]
+    /// - The first columns get the extra entries, and empty columns are left out
+    pub fn columns(&self, count: usize, to_line: impl Fn(usize, T) -> String) -> Vec<String> {
+        let lines: Vec<_> = self
+            .this_page()
+            .into_iter()
+            .map(|(num, entry)| to_line(num, entry))
+            .collect();
+        let per_column = lines.len().div_ceil(count.max(1)).max(1);
+        lines
+            .chunks(per_column)
+            .map(|column| column.join("\n"))
+            .collect()
+    }
 }
@@ -0,0 +1,65 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time;
+use tracing::{info, warn};
+
+use crate::metrics;
+
+/// How long a scraper gets to send its request, so an idle connection is not kept open forever
+pub const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Serves [`metrics::render_prometheus`] at `GET /metrics`
+/// - This is all a scraper needs, so it is a bare HTTP/1.1 responder instead of a web framework
+/// - Try it with `curl http://127.0.0.1:9185/metrics`
+pub async fn serve(addr: SocketAddr) {
+    let listener = match TcpListener::bind(addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            warn!("Could not serve metrics at {addr}: {e}");
+            return;
+        }
+    };
+    info!("Serving metrics at http://{addr}/metrics");
+    serve_on(listener).await
+}
+
+/// [`serve`] on a listener that is already bound, ex: to port 0
+pub async fn serve_on(listener: TcpListener) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(respond(stream));
+            }
+            Err(e) => warn!("Could not accept a metrics connection: {e}"),
+        }
+    }
+}
+
+async fn respond(mut stream: TcpStream) {
+    let mut request = [0; 1024];
+    let Ok(Ok(len)) = time::timeout(READ_TIMEOUT, stream.read(&mut request)).await else {
+        return;
+    };
+    let request = String::from_utf8_lossy(&request[..len]);
+    let (status, content_type, body) = match request.lines().next() {
+        Some(line) if line.starts_with("GET /metrics ") => (
+            "200 OK",
+            "text/plain; version=0.0.4",
+            metrics::render_prometheus(),
+        ),
+        _ => (
+            "404 Not Found",
+            "text/plain",
+            String::from("Metrics are at /metrics\n"),
+        ),
+    };
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    _ = stream.write_all(response.as_bytes()).await;
+    _ = stream.shutdown().await;
+}
@@ -9,21 +9,13 @@ use crate::{
         CdClientPackageComponent, CdClientRenderComponent, CdClientSkillBehavior,
         CdClientVendorComponent,
     },
+    error::{BotError, MsgResult},
     ids::CdClientObjectsId,
-    locale::LocaleTranslation,
-    CD_CLIENT, CONFIG, LOCALE_XML,
+    scope, Api,
 };
 
 pub struct Queries<'a>(&'a CdClient);
 
-pub type MsgResult<T> = Result<T, String>;
-
-pub trait LocaleQueries {
-    fn locale(&self) -> &LocaleTranslation {
-        LOCALE_XML.locales.get(&CONFIG.locale).unwrap()
-    }
-}
-
 pub fn explorer_link_name(
     name: impl Display,
     id: impl Display,
@@ -32,7 +24,8 @@ pub fn explorer_link_name(
     format!("{name} [`[{id}]`]({explorer_url})")
 }
 
-impl LocaleQueries for CdClient {}
+/// Gives the query traits access to the live locale
+impl Api for CdClient {}
 
 //------------------//
 // Auto-Completions //
@@ -55,6 +48,7 @@ impl AutocompleteQueries for CdClient {
         }
         self.objects
             .iter()
+            .take_while(|_| !scope::is_cancelled())
             // .filter(|item| item.name.len() > 0)
             .map(|item| {
                 let id = item.id;
@@ -82,6 +76,7 @@ impl AutocompleteQueries for CdClient {
         }
         self.skill_behavior
             .iter()
+            .take_while(|_| !scope::is_cancelled())
             .map(|skill| {
                 let id = skill.skill_id;
                 let name = self.req_skill_name(id);
@@ -100,6 +95,7 @@ impl AutocompleteQueries for CdClient {
         self.locale()
             .missions
             .iter()
+            .take_while(|_| !scope::is_cancelled())
             .map(|(id, mission)| {
                 let name = mission
                     .name
@@ -120,6 +116,7 @@ impl AutocompleteQueries for CdClient {
         self.locale()
             .missions
             .iter()
+            .take_while(|_| !scope::is_cancelled())
             .map(|(id, mission)| {
                 let name = mission
                     .name
@@ -142,7 +139,7 @@ pub fn fix_icon_asset(asset: &str) -> String {
 }
 
 pub fn icon_asset_as_url(asset: &str) -> String {
-    CONFIG.explorer_res_uri(&fix_icon_asset(asset))
+    scope::config().explorer_res_uri(&fix_icon_asset(asset))
 }
 
 pub trait LootQueries {
@@ -226,37 +223,42 @@ impl ObjectQueries for CdClient {
             .components_registry
             .at_group_key(&item_id)
             .ok_or_else(|| {
-                format!(
+                BotError::MissingComponent(format!(
                     "{} has no Registered Components",
                     self.object_explorer_url(item_id)
-                )
+                ))
             })?;
 
         let item_component_id = components
             .iter()
             .find(|comp| comp.component_type == PACKAGE_COMPONENT)
             .ok_or_else(|| {
-                format!(
+                BotError::MissingComponent(format!(
                     "{} has no Registered Package Component",
                     self.object_explorer_url(item_id)
-                )
+                ))
             })?
             .component_id;
 
         let item_component = self
             .package_component
             .at_key(&item_component_id)
-            .ok_or_else(|| format!("Package Component `{}` does not exist", item_component_id))?;
+            .ok_or_else(|| {
+                BotError::DataInconsistency(format!(
+                    "Package Component `{}` does not exist",
+                    item_component_id
+                ))
+            })?;
 
         Ok(item_component)
     }
 
     fn object_package_ids(&self, item_id: i32) -> MsgResult<Vec<i32>> {
         let lmis = self.loot_matrix_indexes_with_item(item_id).ok_or_else(|| {
-            format!(
-                "{} it not in any Loot Matrices",
+            BotError::NotFound(format!(
+                "{} is not in any Loot Matrices",
                 self.object_explorer_url(item_id)
-            )
+            ))
         })?;
 
         let pkg_ids: Vec<i32> = self
@@ -280,10 +282,7 @@ impl ObjectQueries for CdClient {
 
     fn object_name(&self, item_id: i32) -> Option<String> {
         let item = self.objects.at_key(&item_id)?;
-        LOCALE_XML
-            .locales
-            .get(&CONFIG.locale)
-            .unwrap()
+        self.locale()
             .objects
             .get(&item_id)
             .map(|o| o.name.clone())
@@ -303,11 +302,11 @@ impl ObjectQueries for CdClient {
     }
 
     fn get_object(&self, item_id: i32) -> MsgResult<&CdClientObjects> {
-        CD_CLIENT.objects.at_key(&item_id).ok_or_else(|| {
-            format!(
+        self.objects.at_key(&item_id).ok_or_else(|| {
+            BotError::NotFound(format!(
                 "{} does not exist!",
-                CD_CLIENT.object_hyperlinked_name(item_id)
-            )
+                self.object_hyperlinked_name(item_id)
+            ))
         })
     }
 
@@ -316,33 +315,38 @@ impl ObjectQueries for CdClient {
             .components_registry
             .at_group_key(&item_id)
             .ok_or_else(|| {
-                format!(
+                BotError::MissingComponent(format!(
                     "{} has no Registered Components",
                     self.object_explorer_url(item_id)
-                )
+                ))
             })?;
 
         let item_component_id = components
             .iter()
             .find(|comp| comp.component_type == ITEM_COMPONENT)
             .ok_or_else(|| {
-                format!(
+                BotError::MissingComponent(format!(
                     "{} has no Registered Item Component",
                     self.object_explorer_url(item_id)
-                )
+                ))
             })?
             .component_id;
 
         let item_component = self
             .item_component
             .at_key(&item_component_id)
-            .ok_or_else(|| format!("Item Component `{}` does not exist", item_component_id))?;
+            .ok_or_else(|| {
+                BotError::DataInconsistency(format!(
+                    "Item Component `{}` does not exist",
+                    item_component_id
+                ))
+            })?;
 
         Ok(item_component)
     }
 
     fn object_explorer_url(&self, item_id: i32) -> String {
-        CONFIG.explorer_uri(format!("/objects/{}", item_id))
+        scope::config().explorer_uri(format!("/objects/{}", item_id))
     }
 
     fn object_render_component(&self, item_id: i32) -> MsgResult<&CdClientRenderComponent> {
@@ -350,27 +354,32 @@ impl ObjectQueries for CdClient {
             .components_registry
             .at_group_key(&item_id)
             .ok_or_else(|| {
-                format!(
+                BotError::MissingComponent(format!(
                     "{} has no Registered Components",
                     self.object_explorer_url(item_id)
-                )
+                ))
             })?;
 
         let render_component_id = components
             .iter()
             .find(|comp| comp.component_type == RENDER_COMPONENT)
             .ok_or_else(|| {
-                format!(
+                BotError::MissingComponent(format!(
                     "{} has no Registered Render Component",
                     self.object_explorer_url(item_id)
-                )
+                ))
             })?
             .component_id;
 
         let render_component = self
             .render_component
             .at_key(&render_component_id)
-            .ok_or_else(|| format!("Render Component `{}` does not exist", render_component_id))?;
+            .ok_or_else(|| {
+                BotError::DataInconsistency(format!(
+                    "Render Component `{}` does not exist",
+                    render_component_id
+                ))
+            })?;
 
         Ok(render_component)
     }
@@ -382,10 +391,10 @@ impl ObjectQueries for CdClient {
 
     fn object_vendor_ids(&self, item_id: i32) -> MsgResult<Vec<i32>> {
         let lmis = self.loot_matrix_indexes_with_item(item_id).ok_or_else(|| {
-            format!(
-                "{} it not in any Loot Matrices",
+            BotError::NotFound(format!(
+                "{} is not in any Loot Matrices",
                 self.object_explorer_url(item_id)
-            )
+            ))
         })?;
         let vc_ids: Vec<i32> = self
             .vendor_component
@@ -466,9 +475,12 @@ pub trait SkillQueries {
 
 impl SkillQueries for CdClient {
     fn get_skill(&self, id: i32) -> MsgResult<&CdClientSkillBehavior> {
-        self.skill_behavior
-            .at_key(&id)
-            .ok_or_else(|| format!("{} does not exist!", CD_CLIENT.skill_hyperlinked_name(id)))
+        self.skill_behavior.at_key(&id).ok_or_else(|| {
+            BotError::NotFound(format!(
+                "{} does not exist!",
+                self.skill_hyperlinked_name(id)
+            ))
+        })
     }
 
     fn skill_name(&self, id: i32) -> Option<String> {
@@ -484,11 +496,11 @@ impl SkillQueries for CdClient {
     }
 
     fn skill_explorer_url(&self, id: i32) -> String {
-        CONFIG.explorer_uri(format!("/skills/{}", id))
+        scope::config().explorer_uri(format!("/skills/{}", id))
     }
 
     fn cooldown_group_hyperlinked_name(&self, id: i32) -> String {
-        let url = CONFIG.explorer_uri(format!("/skills/cooldowngroups/{}", id));
+        let url = scope::config().explorer_uri(format!("/skills/cooldowngroups/{}", id));
         explorer_link_name(format!("Group {id}"), id, url)
     }
 
@@ -519,11 +531,11 @@ pub trait AchievementQueries {
     fn req_achievement_name(&self, id: i32) -> String;
     fn achievement_icon_url(&self, id: i32) -> Option<String>;
     fn achievement_explorer_url(&self, id: i32) -> String {
-        CONFIG.explorer_uri(format!("/missions/{}", id))
+        scope::config().explorer_uri(format!("/missions/{}", id))
     }
     fn achievement_hyperlinked_name(&self, id: i32) -> String {
         let name = self.req_achievement_name(id);
-        let url = CONFIG.explorer_uri(format!("/missions/{}", id));
+        let url = scope::config().explorer_uri(format!("/missions/{}", id));
         explorer_link_name(name, id, url)
     }
     fn get_achievement(&self, id: i32) -> MsgResult<&CdClientMissions>;
@@ -619,10 +631,10 @@ impl AchievementQueries for CdClient {
 
     fn get_achievement(&self, id: i32) -> MsgResult<&CdClientMissions> {
         self.missions.at_key(&id).ok_or_else(|| {
-            format!(
+            BotError::NotFound(format!(
                 "{} does not exist!",
-                CD_CLIENT.achievement_hyperlinked_name(id)
-            )
+                self.achievement_hyperlinked_name(id)
+            ))
         })
     }
 }
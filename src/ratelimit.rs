@@ -0,0 +1,315 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+
+use crate::commands::COMMANDS;
+use crate::metrics::InteractionKind;
+use crate::response::Response;
+use crate::{scope, CONFIG};
+
+/// A token bucket: `capacity` uses in a burst, refilled evenly over `per_secs`
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Limit {
+    pub capacity: u32,
+    pub per_secs: u64,
+}
+
+impl Limit {
+    pub const fn new(capacity: u32, per_secs: u64) -> Self {
+        Self { capacity, per_secs }
+    }
+
+    fn tokens_per_sec(&self) -> f64 {
+        self.capacity as f64 / self.per_secs as f64
+    }
+}
+
+/// How often users and guilds may interact with the bot
+/// - An interaction must fit in every bucket it falls in: its user's, its guild's and the
+///   bucket of its user and command
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimits {
+    pub enabled: bool,
+    /// Owners (see [`crate::bot_config::BotConfig::owner_ids`]) are never limited
+    pub exempt_owners: bool,
+    /// Every slash command and component interaction of one user
+    pub user: Limit,
+    /// Every slash command and component interaction in one guild
+    pub guild: Limit,
+    /// One user's uses of one command, unless [`Self::commands`] has its own limit
+    pub command: Limit,
+    /// Per command name, ex: `drop = { capacity = 3, per_secs = 15 }`
+    pub commands: BTreeMap<String, Limit>,
+    /// Autocomplete of one user; a limited user gets no suggestions instead of an embed
+    pub autocomplete: Limit,
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        // these scan every loot matrix, so they get less than a lookup like `/level`
+        let heavy = Limit::new(3, 15);
+        RateLimits {
+            enabled: true,
+            exempt_owners: true,
+            user: Limit::new(10, 20),
+            guild: Limit::new(60, 60),
+            command: Limit::new(5, 10),
+            commands: BTreeMap::from([
+                (String::from("drop"), heavy),
+                (String::from("unpack"), heavy),
+                (String::from("reward"), heavy),
+            ]),
+            autocomplete: Limit::new(20, 10),
+        }
+    }
+}
+
+impl RateLimits {
+    pub fn validate(&self, errors: &mut Vec<String>) {
+        let limits = [
+            (String::from("user"), &self.user),
+            (String::from("guild"), &self.guild),
+            (String::from("command"), &self.command),
+            (String::from("autocomplete"), &self.autocomplete),
+        ]
+        .into_iter()
+        .chain(
+            self.commands
+                .iter()
+                .map(|(name, limit)| (format!("commands.{name}"), limit)),
+        );
+        for (field, limit) in limits {
+            if limit.capacity == 0 || limit.per_secs == 0 {
+                errors.push(format!(
+                    "`rate_limits.{field}`: `capacity` and `per_secs` must be at least 1"
+                ));
+            }
+        }
+    }
+
+    fn command_limit(&self, command: &str) -> Limit {
+        self.commands.get(command).copied().unwrap_or(self.command)
+    }
+}
+
+/// The bucket that ran out
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LimitScope {
+    User,
+    Guild,
+    Command,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Cooldown {
+    pub scope: LimitScope,
+    /// Until the bucket has a token again
+    pub retry_after: Duration,
+}
+
+impl Cooldown {
+    /// Sent ephemerally instead of running the command, in the style of its interaction's server
+    pub fn embed(&self, command: &str) -> Response {
+        let reason = match self.scope {
+            LimitScope::User => String::from("You are using commands too quickly!"),
+            LimitScope::Guild => String::from("This server is using the bot a lot right now!"),
+            LimitScope::Command => format!(
+                "You are using `{}` too quickly!",
+                COMMANDS.invocation_of(command)
+            ),
+        };
+        let ready_at = SystemTime::now() + self.retry_after;
+        // rounded up, so it never says "now" while still limited
+        let ready_at = ready_at
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_secs() + 1)
+            .unwrap_or(0);
+        scope::config().failure_embed(
+            "Slow Down",
+            format!("{reason}\nTry again <t:{ready_at}:R>."),
+        )
+    }
+}
+
+impl Display for Cooldown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let scope = match self.scope {
+            LimitScope::User => "user",
+            LimitScope::Guild => "guild",
+            LimitScope::Command => "command",
+        };
+        write!(
+            f,
+            "{scope} limit, retry after {}ms",
+            self.retry_after.as_millis()
+        )
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum BucketKey {
+    User(u64),
+    Guild(u64),
+    Command(u64, String),
+    Autocomplete(u64),
+}
+
+impl BucketKey {
+    fn limit(&self, limits: &RateLimits) -> Limit {
+        match self {
+            BucketKey::User(_) => limits.user,
+            BucketKey::Guild(_) => limits.guild,
+            BucketKey::Command(_, command) => limits.command_limit(command),
+            BucketKey::Autocomplete(_) => limits.autocomplete,
+        }
+    }
+}
+
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn full(limit: &Limit, now: Instant) -> Self {
+        Self {
+            tokens: limit.capacity as f64,
+            updated: now,
+        }
+    }
+
+    fn refill(&mut self, limit: &Limit, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * limit.tokens_per_sec()).min(limit.capacity as f64);
+        self.updated = now;
+    }
+
+    /// How long until a token is available (zero if one is)
+    fn wait(&self, limit: &Limit) -> Duration {
+        match self.tokens >= 1.0 {
+            true => Duration::ZERO,
+            false => Duration::from_secs_f64((1.0 - self.tokens) / limit.tokens_per_sec()),
+        }
+    }
+}
+
+/// How often buckets that are full again are dropped, since a new one starts out full anyway
+pub const PRUNE_EVERY: Duration = Duration::from_secs(60);
+
+/// The buckets of every user, guild and command that interacted recently
+pub struct RateLimiter {
+    buckets: HashMap<BucketKey, TokenBucket>,
+    pruned: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            buckets: HashMap::new(),
+            pruned: now,
+        }
+    }
+
+    /// Buckets that are not full, or have not been pruned yet
+    pub fn len(&self) -> usize {
+        self.buckets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buckets.is_empty()
+    }
+
+    /// Takes a token from every bucket the interaction falls in, or none if any of them is empty
+    /// - Owners are not exempt here, see [`check`]
+    pub fn check(
+        &mut self,
+        limits: &RateLimits,
+        kind: InteractionKind,
+        command: &str,
+        user_id: u64,
+        guild_id: Option<u64>,
+        now: Instant,
+    ) -> Result<(), Cooldown> {
+        let keys = match kind {
+            InteractionKind::Autocomplete => vec![BucketKey::Autocomplete(user_id)],
+            InteractionKind::Slash | InteractionKind::Component => {
+                let mut keys = vec![
+                    BucketKey::User(user_id),
+                    BucketKey::Command(user_id, command.to_string()),
+                ];
+                keys.extend(guild_id.map(BucketKey::Guild));
+                keys
+            }
+        };
+
+        if now.saturating_duration_since(self.pruned) >= PRUNE_EVERY {
+            self.prune(limits, now);
+        }
+
+        let mut cooldown: Option<Cooldown> = None;
+        for key in &keys {
+            let limit = key.limit(limits);
+            let bucket = self
+                .buckets
+                .entry(key.clone())
+                .or_insert_with(|| TokenBucket::full(&limit, now));
+            bucket.refill(&limit, now);
+            let retry_after = bucket.wait(&limit);
+            let scope = match key {
+                BucketKey::User(_) | BucketKey::Autocomplete(_) => LimitScope::User,
+                BucketKey::Guild(_) => LimitScope::Guild,
+                BucketKey::Command(..) => LimitScope::Command,
+            };
+            // report the bucket that takes longest to refill
+            if !retry_after.is_zero() && cooldown.is_none_or(|c| c.retry_after < retry_after) {
+                cooldown = Some(Cooldown { scope, retry_after });
+            }
+        }
+        if let Some(cooldown) = cooldown {
+            return Err(cooldown);
+        }
+
+        for key in &keys {
+            if let Some(bucket) = self.buckets.get_mut(key) {
+                bucket.tokens -= 1.0;
+            }
+        }
+        Ok(())
+    }
+
+    /// Drops the buckets that are full again
+    fn prune(&mut self, limits: &RateLimits, now: Instant) {
+        self.buckets.retain(|key, bucket| {
+            let limit = key.limit(limits);
+            bucket.refill(&limit, now);
+            bucket.tokens < limit.capacity as f64
+        });
+        self.pruned = now;
+    }
+}
+
+static LIMITER: Lazy<Mutex<RateLimiter>> =
+    Lazy::new(|| Mutex::new(RateLimiter::new(Instant::now())));
+
+/// [`RateLimiter::check`] with the configured limits, unless they are off or the user is exempt
+pub fn check(
+    kind: InteractionKind,
+    command: &str,
+    user_id: u64,
+    guild_id: Option<u64>,
+) -> Result<(), Cooldown> {
+    let limits = &CONFIG.rate_limits;
+    if !limits.enabled || (limits.exempt_owners && CONFIG.is_owner(user_id)) {
+        return Ok(());
+    }
+    LIMITER
+        .lock()
+        .unwrap()
+        .check(limits, kind, command, user_id, guild_id, Instant::now())
+}
@@ -0,0 +1,207 @@
+/*
+* - Finds what a message refers to: LOT numbers, explorer links and `[[Item Name]]` brackets
+* - Names are found the way autocomplete suggests them, so `[[name]]` gets what typing it would
+* - Explorer links are matched by running the url helpers of [`crate::queries`] in reverse, so
+*   they follow whatever explorer the server is configured with
+*/
+
+use std::ops::Range;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::analytics::Lookup;
+use crate::cdclient::CdClient;
+use crate::queries::{AchievementQueries, AutocompleteQueries, ObjectQueries, SkillQueries};
+
+/// Discord shows at most 25 options in a select menu
+pub const MAX_REFERENCES: usize = 25;
+
+/// `LOT 7415`, `LOT:7415` or `lot #7415`
+static LOT: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\blot\s*[:#]?\s*#?(\d+)\b").unwrap());
+/// `[7415]`, which is how the bot itself shows ids, ex: `Flaming Sword [7415]`
+/// - As the text of a link, ex: `Mission [`[1727]`](…/missions/1727)`, the link is what counts
+/// - Otherwise it is what the first explorer link with the same id is, ex: the url of a mission
+///   card titled `Mission [1727]`, or else an object
+static BRACKETED_ID: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[`?(\d+)`?\]").unwrap());
+/// `[[Item Name]]`
+static NAME: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[\[([^\[\]]+)\]\]").unwrap());
+
+/// Something a message refers to
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Reference {
+    Id(Lookup, i32),
+    /// The name of an item or else a mission, ex: `Flaming Sword` of `[[Flaming Sword]]`
+    Name(String),
+}
+
+/// The explorer urls of each kind without their id, ex: `https://explorer.lu/objects/`
+pub struct ExplorerUrls {
+    pub objects: String,
+    pub missions: String,
+    pub skills: String,
+}
+
+impl ExplorerUrls {
+    /// The urls that `cdclient` links to, for the current interaction's explorer
+    pub fn of(cdclient: &CdClient) -> Self {
+        Self {
+            objects: without_id(|id| cdclient.object_explorer_url(id)),
+            missions: without_id(|id| cdclient.achievement_explorer_url(id)),
+            skills: without_id(|id| cdclient.skill_explorer_url(id)),
+        }
+    }
+
+    /// Matches the url with its id, with or without `https://`, ex: `explorer.lu/objects/7415`
+    fn regex(url: &str) -> Option<Regex> {
+        let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+        Regex::new(&format!(r"(?i)(?:https?://)?{}(\d+)", regex::escape(url))).ok()
+    }
+}
+
+/// A url helper in reverse: the url it makes with the id cut off
+fn without_id(url_of: impl Fn(i32) -> String) -> String {
+    let url = url_of(0);
+    url.strip_suffix('0').unwrap_or(&url).to_string()
+}
+
+/// What `text` refers to, in the order it refers to them and each only once
+/// - A message that is only a number is a LOT number
+pub fn scan(text: &str, urls: &ExplorerUrls) -> Vec<Reference> {
+    if let Ok(id) = text.trim().parse() {
+        return vec![Reference::Id(Lookup::Object, id)];
+    }
+    let mut found: Vec<(usize, Reference)> = vec![];
+    for (url, lookup) in [
+        (&urls.objects, Lookup::Object),
+        (&urls.missions, Lookup::Mission),
+        (&urls.skills, Lookup::Skill),
+    ] {
+        if let Some(regex) = ExplorerUrls::regex(url) {
+            found.extend(
+                ids(&regex, text)
+                    .into_iter()
+                    .map(|(span, id)| (span.start, Reference::Id(lookup, id))),
+            );
+        }
+    }
+    let links = found.clone();
+    for (span, id) in ids(&BRACKETED_ID, text) {
+        // the text of a link, which the link already counts for
+        if text[span.end..].starts_with("](") {
+            continue;
+        }
+        let first_link = links
+            .iter()
+            .filter_map(|(start, reference)| match reference {
+                Reference::Id(lookup, linked) if *linked == id => Some((*start, *lookup)),
+                _ => None,
+            })
+            .min_by_key(|(start, _)| *start);
+        let lookup = first_link.map_or(Lookup::Object, |(_, lookup)| lookup);
+        found.push((span.start, Reference::Id(lookup, id)));
+    }
+    found.extend(
+        ids(&LOT, text)
+            .into_iter()
+            .map(|(span, id)| (span.start, Reference::Id(Lookup::Object, id))),
+    );
+    found.extend(names(text));
+    ordered(found)
+}
+
+/// The ids `regex` captures in `text`, with where each whole match is
+fn ids(regex: &Regex, text: &str) -> Vec<(Range<usize>, i32)> {
+    regex
+        .captures_iter(text)
+        .filter_map(|captures| {
+            let (all, id) = (captures.get(0)?, captures.get(1)?);
+            Some((all.range(), id.as_str().parse().ok()?))
+        })
+        .collect()
+}
+
+/// Only the `[[Item Name]]`s of `text`, each only once
+pub fn scan_names(text: &str) -> Vec<Reference> {
+    ordered(names(text))
+}
+
+fn names(text: &str) -> Vec<(usize, Reference)> {
+    NAME.captures_iter(text)
+        .filter_map(|captures| {
+            let (all, name) = (captures.get(0)?, captures.get(1)?);
+            let name = name.as_str().trim();
+            // `[[7415]]` is already an id
+            (!name.is_empty() && name.parse::<i32>().is_err())
+                .then(|| (all.start(), Reference::Name(name.to_string())))
+        })
+        .collect()
+}
+
+/// By where they are in the message, without repeats
+fn ordered(mut found: Vec<(usize, Reference)>) -> Vec<Reference> {
+    found.sort_by_key(|(start, _)| *start);
+    let mut references: Vec<Reference> = vec![];
+    for (_, reference) in found {
+        if !references.contains(&reference) {
+            references.push(reference);
+        }
+    }
+    references
+}
+
+/// What a name refers to: an item, or else a mission, as autocomplete would suggest it
+/// - The suggestion named exactly that comes first, or else the first suggestion; case is ignored
+pub fn find_name(cdclient: &CdClient, name: &str) -> Option<(Lookup, i32)> {
+    let name = name.trim().to_lowercase();
+    [
+        (Lookup::Object, cdclient.autocomplete_object(&name)),
+        (Lookup::Mission, cdclient.autocomplete_achievement(&name)),
+    ]
+    .into_iter()
+    .find_map(|(lookup, choices)| {
+        // choices are named `[{id}] {name}`
+        let suggestions: Vec<(i32, String)> = choices
+            .into_iter()
+            .filter_map(|choice| {
+                let choice = serde_json::to_value(choice).ok()?;
+                let id = choice["value"].as_i64()?.try_into().ok()?;
+                let (_, suggested) = choice["name"].as_str()?.split_once("] ")?;
+                Some((id, suggested.to_lowercase()))
+            })
+            .collect();
+        suggestions
+            .iter()
+            .find(|(_, suggested)| *suggested == name)
+            .or(suggestions.first())
+            .map(|(id, _)| (lookup, *id))
+    })
+}
+
+/// The objects, missions and skills of `references` that exist, each only once
+/// - At most [`MAX_REFERENCES`]
+pub fn resolve(cdclient: &CdClient, references: Vec<Reference>) -> Vec<(Lookup, i32)> {
+    let mut resolved = vec![];
+    for reference in references {
+        let found = match reference {
+            Reference::Id(lookup, id) => {
+                let exists = match lookup {
+                    Lookup::Object => cdclient.get_object(id).is_ok(),
+                    Lookup::Mission => cdclient.get_achievement(id).is_ok(),
+                    Lookup::Skill => cdclient.get_skill(id).is_ok(),
+                };
+                exists.then_some((lookup, id))
+            }
+            Reference::Name(ref name) => find_name(cdclient, name),
+        };
+        if let Some(found) = found {
+            if !resolved.contains(&found) {
+                resolved.push(found);
+            }
+        }
+        if resolved.len() == MAX_REFERENCES {
+            break;
+        }
+    }
+    resolved
+}
@@ -0,0 +1,205 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serenity::all::{Command, CommandId, Context, GuildId};
+use serenity::json::{self, Value};
+use tracing::{error, info};
+
+use crate::commands::COMMANDS;
+use crate::metrics;
+use crate::CONFIG;
+
+/// How slash commands are registered with Discord once the bot is ready
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RegistrationMode {
+    /// Overwrite the commands of every guild in `guild_ids`
+    #[default]
+    Guilds,
+    /// Overwrite the global commands (they can take a while to show up everywhere)
+    Global,
+    /// For every guild in `guild_ids`, fetch what is registered and only create, edit or delete
+    /// the commands that changed
+    Dev,
+}
+
+impl RegistrationMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "guilds" => Some(Self::Guilds),
+            "global" => Some(Self::Global),
+            "dev" => Some(Self::Dev),
+            _ => None,
+        }
+    }
+
+    pub fn needs_guild_ids(&self) -> bool {
+        matches!(self, Self::Guilds | Self::Dev)
+    }
+}
+
+/// Registers [`COMMANDS`] according to [`crate::bot_config::BotConfig::registration`]
+/// - A failure in one guild is reported and does not stop the others
+pub async fn register_commands(ctx: &Context) {
+    let guild_ids = CONFIG.guild_ids.iter().copied().map(GuildId::new);
+    match CONFIG.registration {
+        RegistrationMode::Global => {
+            match Command::set_global_commands(&ctx.http, COMMANDS.register_all()).await {
+                Ok(commands) => info!("Registered {} global commands", commands.len()),
+                Err(why) => {
+                    metrics::record_discord_failure("register_commands");
+                    error!("Could not register global commands: {why}")
+                }
+            }
+        }
+        RegistrationMode::Guilds => {
+            for guild_id in guild_ids {
+                match guild_id
+                    .set_commands(&ctx.http, COMMANDS.register_all())
+                    .await
+                {
+                    Ok(commands) => {
+                        info!("Registered {} commands in {guild_id}", commands.len())
+                    }
+                    Err(why) => {
+                        metrics::record_discord_failure("register_commands");
+                        error!("Could not register commands in {guild_id}: {why}")
+                    }
+                }
+            }
+        }
+        RegistrationMode::Dev => {
+            for guild_id in guild_ids {
+                match sync_guild_commands(ctx, guild_id).await {
+                    Ok(summary) => info!("Synced commands in {guild_id}: {summary}"),
+                    Err(why) => {
+                        metrics::record_discord_failure("sync_commands");
+                        error!("Could not sync commands in {guild_id}: {why}")
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// What [`sync_guild_commands`] did
+#[derive(Debug, Default)]
+pub struct SyncSummary {
+    pub created: Vec<String>,
+    pub edited: Vec<String>,
+    pub deleted: Vec<String>,
+    pub unchanged: usize,
+}
+
+impl std::fmt::Display for SyncSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let list = |names: &[String]| match names.is_empty() {
+            true => String::from("none"),
+            false => names.join(", "),
+        };
+        write!(
+            f,
+            "created [{}], edited [{}], deleted [{}], {} unchanged",
+            list(&self.created),
+            list(&self.edited),
+            list(&self.deleted),
+            self.unchanged
+        )
+    }
+}
+
+/// Only sends the commands that differ from what the guild already has
+/// - Registering a whole guild re-creates every command, which is slow and rate limited; this
+///   keeps restarts while developing fast
+pub async fn sync_guild_commands(
+    ctx: &Context,
+    guild_id: GuildId,
+) -> serenity::Result<SyncSummary> {
+    let mut existing: BTreeMap<String, (CommandId, Value)> = guild_id
+        .get_commands(&ctx.http)
+        .await?
+        .into_iter()
+        .map(|command| {
+            let value = normalize_command(json::to_value(&command).unwrap_or(Value::Null));
+            (command.name.clone(), (command.id, value))
+        })
+        .collect();
+
+    let mut summary = SyncSummary::default();
+    for builder in COMMANDS.register_all() {
+        let value = normalize_command(json::to_value(&builder).unwrap_or(Value::Null));
+        let Some(name) = value.get("name").and_then(Value::as_str).map(String::from) else {
+            continue;
+        };
+        match existing.remove(&name) {
+            Some((_, old)) if old == value => summary.unchanged += 1,
+            Some((id, _)) => {
+                guild_id.edit_command(&ctx.http, id, builder).await?;
+                summary.edited.push(name);
+            }
+            None => {
+                guild_id.create_command(&ctx.http, builder).await?;
+                summary.created.push(name);
+            }
+        }
+    }
+    for (name, (id, _)) in existing {
+        guild_id.delete_command(&ctx.http, id).await?;
+        summary.deleted.push(name);
+    }
+    Ok(summary)
+}
+
+/// The fields that Discord echoes back and that we set through [`serenity::all::CreateCommand`]
+const COMPARED_KEYS: &[&str] = &[
+    "name",
+    "description",
+    "type",
+    "options",
+    "required",
+    "autocomplete",
+    "choices",
+    "value",
+    "min_value",
+    "max_value",
+    "min_length",
+    "max_length",
+    "channel_types",
+    "default_member_permissions",
+];
+
+/// Makes a registered [`Command`] and a [`serenity::all::CreateCommand`] comparable
+/// - Ids, versions and fields we never set are dropped
+/// - Empty or default values are dropped, since only one side sends them
+fn normalize_command(value: Value) -> Value {
+    let mut value = normalize(value);
+    // slash commands are type 1, which the builder leaves out
+    if let Value::Object(ref mut map) = value {
+        map.entry("type").or_insert(Value::from(1));
+    }
+    value
+}
+
+fn normalize(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .filter(|(key, _)| COMPARED_KEYS.contains(&key.as_str()))
+                .map(|(key, value)| (key, normalize(value)))
+                .filter(|(_, value)| !is_empty(value))
+                .collect(),
+        ),
+        Value::Array(values) => Value::Array(values.into_iter().map(normalize).collect()),
+        value => value,
+    }
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null | Value::Bool(false) => true,
+        Value::String(s) => s.is_empty(),
+        Value::Array(values) => values.is_empty(),
+        Value::Object(map) => map.is_empty(),
+        _ => false,
+    }
+}
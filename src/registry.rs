@@ -0,0 +1,622 @@
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use serenity::all::{
+    AutocompleteChoice, AutocompleteOption, CommandData, CommandDataOption, CommandDataOptionValue,
+    CommandInteraction, CommandOptionType, CommandType, ComponentInteraction, CreateCommandOption,
+    CreateInteractionResponseMessage, ModalInteraction, ResolvedOption, ResolvedValue,
+};
+use serenity::builder::CreateCommand;
+use tracing::{debug, error, field, info, info_span, warn};
+
+use crate::analytics::{self, Lookup};
+use crate::error::{BotError, MsgResult};
+use crate::interaction_command::{
+    CommandResult, CustomIdOptions, InteractionCommand, MessageCommand, ToCustomId,
+};
+use crate::metrics::{self, InteractionKind, Outcome};
+use crate::scope::{self, Cancellation, InteractionScope};
+
+/// The type-erased entry points of one [`InteractionCommand`]
+#[derive(Clone, Copy)]
+pub struct RegisteredCommand {
+    pub name: &'static str,
+    /// [`InteractionCommand::OWNER_ONLY`]
+    pub owner_only: bool,
+    pub register: fn() -> CreateCommand,
+    /// Registers it as a subcommand with this name, see [`CommandGroup`]
+    pub register_subcommand: fn(&str) -> CreateCommandOption,
+    pub autocomplete: fn(AutocompleteOption<'_>) -> Option<Vec<AutocompleteChoice>>,
+    pub slash_command:
+        fn(&CommandInteraction, &[ResolvedOption]) -> CreateInteractionResponseMessage,
+    pub component_interaction:
+        fn(&ComponentInteraction, &CustomIdOptions) -> CreateInteractionResponseMessage,
+    pub modal_submit: fn(&ModalInteraction, &CustomIdOptions) -> CreateInteractionResponseMessage,
+    /// Runs the command from a custom id outside of Discord, ex: in `crab-cli`
+    pub run: fn(&CustomIdOptions) -> CommandResult,
+    /// [`ToCustomId::VERSION`] of its arguments
+    pub version: u32,
+    /// Parses the arguments of a custom id and makes the custom id again from them
+    pub custom_id: fn(&CustomIdOptions) -> MsgResult<String>,
+    /// The custom id of [`ToCustomId::longest`]
+    pub longest_custom_id: fn() -> String,
+    /// The custom ids of [`InteractionCommand::examples`], which reply with them
+    pub examples: fn() -> Vec<String>,
+}
+
+impl RegisteredCommand {
+    /// - Panics if the custom ids of the command's arguments would not route back to it
+    pub fn of<C: InteractionCommand>() -> Self {
+        assert_eq!(
+            C::NAME,
+            <C::Arguments as ToCustomId>::CMD,
+            "The custom ids of `{}` would not route back to it",
+            C::NAME
+        );
+        Self {
+            name: C::NAME,
+            owner_only: C::OWNER_ONLY,
+            register: C::register,
+            register_subcommand: C::register_subcommand,
+            autocomplete: C::handle_autocomplete,
+            slash_command: C::handle_slash_command,
+            component_interaction: C::handle_component_interaction,
+            modal_submit: C::handle_modal_submit,
+            run: C::run_custom_id,
+            version: <C::Arguments as ToCustomId>::VERSION,
+            custom_id: |options| {
+                C::Arguments::try_from(options)
+                    .map(|args| args.to_custom_id_for(options.interaction))
+            },
+            longest_custom_id: || C::Arguments::longest().to_custom_id(true),
+            examples: || {
+                C::examples()
+                    .iter()
+                    .map(|args| args.to_custom_id(true))
+                    .collect()
+            },
+        }
+    }
+}
+
+/// The type-erased entry points of one [`MessageCommand`]
+#[derive(Clone, Copy)]
+pub struct RegisteredMessageCommand {
+    pub name: &'static str,
+    pub label: &'static str,
+    pub register: fn() -> CreateCommand,
+    pub message_command: fn(&CommandInteraction) -> CreateInteractionResponseMessage,
+    /// Runs the command on some text outside of Discord
+    pub run: fn(&str) -> CommandResult,
+}
+
+impl RegisteredMessageCommand {
+    pub fn of<C: MessageCommand>() -> Self {
+        Self {
+            name: C::NAME,
+            label: C::LABEL,
+            register: C::register,
+            message_command: C::handle_message_command,
+            run: C::run,
+        }
+    }
+}
+
+/// Discord's limit of subcommands and subcommand groups under one name
+const SUBCOMMANDS: usize = 25;
+
+/// Subcommands under one name, ex: `/item info|drop|unpack`
+/// - Each subcommand is a whole [`InteractionCommand`], and its custom ids still go by
+///   [`InteractionCommand::NAME`], ex: `/item drop` makes `u/drop.1:..` buttons
+/// - A group inside a group is a subcommand group, ex: `/item sources drop`; Discord allows no
+///   deeper than that
+#[derive(Clone)]
+pub struct CommandGroup {
+    name: &'static str,
+    description: &'static str,
+    subcommands: Vec<Subcommand>,
+}
+
+#[derive(Clone)]
+enum Subcommand {
+    Command {
+        name: &'static str,
+        command: RegisteredCommand,
+    },
+    Group(CommandGroup),
+}
+
+impl CommandGroup {
+    pub fn new(name: &'static str, description: &'static str) -> Self {
+        Self {
+            name,
+            description,
+            subcommands: vec![],
+        }
+    }
+
+    /// `C` as the subcommand `name`, ex: `.command::<ItemCommand>("info")` for `/item info`
+    pub fn command<C: InteractionCommand>(self, name: &'static str) -> Self {
+        self.add(Subcommand::Command {
+            name,
+            command: RegisteredCommand::of::<C>(),
+        })
+    }
+
+    /// - Panics if `group` has groups of its own, since Discord allows no deeper than this
+    pub fn group(self, group: CommandGroup) -> Self {
+        assert!(
+            group
+                .subcommands
+                .iter()
+                .all(|subcommand| matches!(subcommand, Subcommand::Command { .. })),
+            "Subcommand group `{}` of `{}` is nested too deep for Discord",
+            group.name,
+            self.name
+        );
+        self.add(Subcommand::Group(group))
+    }
+
+    /// - Panics if the name is already taken or there are more than Discord allows
+    fn add(mut self, subcommand: Subcommand) -> Self {
+        let name = subcommand.name();
+        assert!(
+            self.subcommands.iter().all(|other| other.name() != name),
+            "Subcommand `{name}` of `{}` is registered twice",
+            self.name
+        );
+        self.subcommands.push(subcommand);
+        assert!(
+            self.subcommands.len() <= SUBCOMMANDS,
+            "`{}` has more than {SUBCOMMANDS} subcommands",
+            self.name
+        );
+        self
+    }
+
+    /// Every command under this group, in order
+    fn commands(&self) -> Vec<RegisteredCommand> {
+        self.subcommands
+            .iter()
+            .flat_map(|subcommand| match subcommand {
+                Subcommand::Command { command, .. } => vec![*command],
+                Subcommand::Group(group) => group.commands(),
+            })
+            .collect()
+    }
+
+    /// Every command under this group and how it is run, ex: `item drop`
+    fn invocations(&self, prefix: &str) -> Vec<(String, RegisteredCommand)> {
+        self.subcommands
+            .iter()
+            .flat_map(|subcommand| match subcommand {
+                Subcommand::Command { name, command } => {
+                    vec![(format!("{prefix} {name}"), *command)]
+                }
+                Subcommand::Group(group) => group.invocations(&format!("{prefix} {}", group.name)),
+            })
+            .collect()
+    }
+
+    fn options(&self) -> Vec<CreateCommandOption> {
+        self.subcommands
+            .iter()
+            .map(|subcommand| match subcommand {
+                Subcommand::Command { name, command } => (command.register_subcommand)(name),
+                Subcommand::Group(group) => group.options().into_iter().fold(
+                    CreateCommandOption::new(
+                        CommandOptionType::SubCommandGroup,
+                        group.name,
+                        group.description,
+                    ),
+                    |option, subcommand| option.add_sub_option(subcommand),
+                ),
+            })
+            .collect()
+    }
+
+    /// The subcommand that `options` picked and its own options
+    fn resolve<'o, 'a>(
+        &self,
+        options: &'o [ResolvedOption<'a>],
+    ) -> Option<(&RegisteredCommand, &'o [ResolvedOption<'a>])> {
+        let picked = options.first()?;
+        self.subcommands
+            .iter()
+            .find(|subcommand| subcommand.name() == picked.name)
+            .and_then(|subcommand| match (subcommand, &picked.value) {
+                (Subcommand::Command { command, .. }, ResolvedValue::SubCommand(options)) => {
+                    Some((command, options.as_slice()))
+                }
+                (Subcommand::Group(group), ResolvedValue::SubCommandGroup(options)) => {
+                    group.resolve(options)
+                }
+                _ => None,
+            })
+    }
+}
+
+impl Subcommand {
+    fn name(&self) -> &'static str {
+        match self {
+            Subcommand::Command { name, .. } => name,
+            Subcommand::Group(group) => group.name,
+        }
+    }
+}
+
+/// What a top-level slash command runs
+#[derive(Clone)]
+enum SlashCommand {
+    /// The command of this name, ex: `/mission`
+    Command(&'static str),
+    Group(CommandGroup),
+}
+
+/// Every command the bot knows about, keyed by [`InteractionCommand::NAME`]
+/// - Adding a command here gives it registration, autocomplete, slash and component routing
+/// - Every handler runs inside the [`InteractionScope`] of its interaction
+/// - Handlers block, so call these from the blocking pool; `cancellation` tells a handler that
+///   nobody waits for it anymore
+#[derive(Default)]
+pub struct CommandRegistry {
+    commands: BTreeMap<&'static str, RegisteredCommand>,
+    /// Keyed by the name of the slash command, which is not the command's for subcommands
+    slash: BTreeMap<&'static str, SlashCommand>,
+    /// Keyed by [`MessageCommand::LABEL`], which is what Discord sends
+    messages: BTreeMap<&'static str, RegisteredMessageCommand>,
+}
+
+impl CommandRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `C` as a top-level slash command
+    /// - Panics if the name is already taken, or if the custom ids of the command's arguments
+    ///   would not route back to it
+    pub fn with<C: InteractionCommand>(self) -> Self {
+        self.add(RegisteredCommand::of::<C>())
+            .add_slash(C::NAME, SlashCommand::Command(C::NAME))
+    }
+
+    /// Every command of `group` as its subcommands, instead of as top-level slash commands
+    /// - Panics like [`Self::with`]
+    pub fn group(self, group: CommandGroup) -> Self {
+        let this = group
+            .commands()
+            .into_iter()
+            .fold(self, |this, command| this.add(command));
+        this.add_slash(group.name, SlashCommand::Group(group))
+    }
+
+    /// `C` in the Apps menu of every message
+    /// - Panics if the name or label is already taken
+    pub fn with_message<C: MessageCommand>(mut self) -> Self {
+        assert!(
+            self.get(C::NAME).is_none()
+                && self.message_commands().all(|other| other.name != C::NAME),
+            "Command `{}` is registered twice",
+            C::NAME
+        );
+        let previous = self
+            .messages
+            .insert(C::LABEL, RegisteredMessageCommand::of::<C>());
+        assert!(
+            previous.is_none(),
+            "Message command `{}` is registered twice",
+            C::LABEL
+        );
+        self
+    }
+
+    fn add(mut self, command: RegisteredCommand) -> Self {
+        let previous = self.commands.insert(command.name, command);
+        assert!(
+            previous.is_none()
+                && self
+                    .message_commands()
+                    .all(|other| other.name != command.name),
+            "Command `{}` is registered twice",
+            command.name
+        );
+        self
+    }
+
+    fn add_slash(mut self, name: &'static str, slash: SlashCommand) -> Self {
+        let previous = self.slash.insert(name, slash);
+        assert!(
+            previous.is_none(),
+            "Slash command `{name}` is registered twice"
+        );
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&RegisteredCommand> {
+        self.commands.get(name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &RegisteredCommand> {
+        self.commands.values()
+    }
+
+    pub fn message_commands(&self) -> impl Iterator<Item = &RegisteredMessageCommand> {
+        self.messages.values()
+    }
+
+    /// Every command and how it is run without the `/`, ex: `item drop` for
+    /// [`crate::commands::drop::DropCommand`]
+    /// - In the order Discord lists them: by name, with subcommands in the order of their group
+    pub fn invocations(&self) -> Vec<(String, RegisteredCommand)> {
+        self.slash
+            .iter()
+            .flat_map(|(name, slash)| match slash {
+                SlashCommand::Command(command) => self
+                    .get(command)
+                    .map(|command| vec![(name.to_string(), *command)])
+                    .unwrap_or_default(),
+                SlashCommand::Group(group) => group.invocations(name),
+            })
+            .collect()
+    }
+
+    /// How users run the command named `name`, ex: `/item drop` for `drop` or the label of a
+    /// message command, since [`InteractionCommand::NAME`] alone is not a slash command once grouped
+    pub fn invocation_of(&self, name: &str) -> String {
+        if let Some(message) = self.message_commands().find(|message| message.name == name) {
+            return message.label.to_string();
+        }
+        self.invocations()
+            .into_iter()
+            .find(|(_, command)| command.name == name)
+            .map_or_else(
+                || format!("/{name}"),
+                |(invocation, _)| format!("/{invocation}"),
+            )
+    }
+
+    /// The command that the slash command `name` with `options` runs and its own options, ex:
+    /// [`crate::commands::drop::DropCommand`] and `item` for `/item drop item:7415`
+    pub fn resolve<'o, 'a>(
+        &self,
+        name: &str,
+        options: &'o [ResolvedOption<'a>],
+    ) -> Option<(&RegisteredCommand, &'o [ResolvedOption<'a>])> {
+        match self.slash.get(name)? {
+            SlashCommand::Command(name) => Some((self.get(name)?, options)),
+            SlashCommand::Group(group) => group.resolve(options),
+        }
+    }
+
+    /// [`InteractionCommand::NAME`] of what a slash command or autocomplete runs, ex: `drop` for
+    /// `/item drop`, which rate limits and logs go by
+    /// - The name of the slash command when it runs nothing
+    pub fn command_name<'d>(&self, data: &'d CommandData) -> &'d str {
+        if data.kind == CommandType::Message {
+            if let Some(command) = self.messages.get(data.name.as_str()) {
+                return command.name;
+            }
+        }
+        match self.resolve(&data.name, &data.options()) {
+            Some((command, _)) => command.name,
+            None => &data.name,
+        }
+    }
+
+    /// Why a custom id can't run anymore, ex: its message is from before the arguments changed
+    /// - Show that the message is outdated instead of running it
+    pub fn check_custom_id(&self, options: &CustomIdOptions) -> MsgResult<()> {
+        let Some(command) = self.get(&options.cmd) else {
+            Err(format!("`{}` is not a command", options.cmd))?
+        };
+        if options.version != command.version {
+            Err(format!(
+                "`{}` is at version {}, not {}",
+                command.name, command.version, options.version
+            ))?
+        }
+        (command.custom_id)(options).map(|_| ())
+    }
+
+    pub fn register_all(&self) -> Vec<CreateCommand> {
+        self.slash
+            .iter()
+            .filter_map(|(name, slash)| match slash {
+                SlashCommand::Command(name) => Some((self.get(name)?.register)()),
+                SlashCommand::Group(group) => Some(group.options().into_iter().fold(
+                    CreateCommand::new(*name).description(group.description),
+                    |command, subcommand| command.add_option(subcommand),
+                )),
+            })
+            .chain(self.messages.values().map(|command| (command.register)()))
+            .collect()
+    }
+
+    pub fn handle_autocomplete(
+        &self,
+        completion: &CommandInteraction,
+        cancellation: Cancellation,
+    ) -> Option<Vec<AutocompleteChoice>> {
+        let option = completion.data.autocomplete()?;
+        let (command, _) = self.resolve(&completion.data.name, &completion.data.options())?;
+        instrumented(
+            InteractionKind::Autocomplete,
+            command.name,
+            format!("{}={}", option.name, option.value),
+            None,
+            InteractionScope::of_command(completion).with_cancellation(cancellation),
+            || (command.autocomplete)(option),
+        )
+    }
+
+    /// - Also runs message commands, which are counted as slash commands
+    pub fn handle_slash_command(
+        &self,
+        command: &CommandInteraction,
+        cancellation: Cancellation,
+    ) -> Option<CreateInteractionResponseMessage> {
+        if command.data.kind == CommandType::Message {
+            let registered = self.messages.get(command.data.name.as_str())?;
+            let message = command.data.target_id.map(|id| format!("message={id}"));
+            return Some(instrumented(
+                InteractionKind::Slash,
+                registered.name,
+                message.unwrap_or_default(),
+                None,
+                InteractionScope::of_command(command).with_cancellation(cancellation),
+                || (registered.message_command)(command),
+            ));
+        }
+        let options = command.data.options();
+        let (registered, options) = self.resolve(&command.data.name, &options)?;
+        Some(instrumented(
+            InteractionKind::Slash,
+            registered.name,
+            describe_options(&command.data.options),
+            lookup_in_options(&command.data.options),
+            InteractionScope::of_command(command).with_cancellation(cancellation),
+            || (registered.slash_command)(command, options),
+        ))
+    }
+
+    pub fn handle_component_interaction(
+        &self,
+        interaction: &ComponentInteraction,
+        options: &CustomIdOptions,
+        cancellation: Cancellation,
+    ) -> Option<CreateInteractionResponseMessage> {
+        let registered = self.get(options.cmd.as_str())?;
+        let (arguments, lookup) = describe_custom_id(options);
+        Some(instrumented(
+            InteractionKind::Component,
+            registered.name,
+            arguments,
+            lookup,
+            InteractionScope::of_component(interaction).with_cancellation(cancellation),
+            || (registered.component_interaction)(interaction, options),
+        ))
+    }
+
+    /// - Counted as a component interaction, since it comes from a button of a message
+    pub fn handle_modal_submit(
+        &self,
+        interaction: &ModalInteraction,
+        options: &CustomIdOptions,
+        cancellation: Cancellation,
+    ) -> Option<CreateInteractionResponseMessage> {
+        let registered = self.get(options.cmd.as_str())?;
+        let (arguments, lookup) = describe_custom_id(options);
+        Some(instrumented(
+            InteractionKind::Component,
+            registered.name,
+            arguments,
+            lookup,
+            InteractionScope::of_modal(interaction).with_cancellation(cancellation),
+            || (registered.modal_submit)(interaction, options),
+        ))
+    }
+}
+
+/// Formats custom id options as `key=value`, with the first that names an object, mission or skill
+fn describe_custom_id(options: &CustomIdOptions) -> (String, Option<(Lookup, i32)>) {
+    let arguments = options
+        .map
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let lookup = options
+        .map
+        .iter()
+        .find_map(|(key, value)| Some((Lookup::of_key(key)?, value.parse().ok()?)));
+    (arguments, lookup)
+}
+
+/// Runs a handler inside its [`InteractionScope`] and an `interaction` span
+/// - Logs one event per interaction with its latency and outcome (autocomplete only at debug)
+/// - Records the latency for [`metrics::summary`] and the interaction for [`analytics`]
+pub(crate) fn instrumented<R>(
+    kind: InteractionKind,
+    command: &str,
+    arguments: String,
+    lookup: Option<(Lookup, i32)>,
+    scope: InteractionScope,
+    handler: impl FnOnce() -> R,
+) -> R {
+    let span = info_span!(
+        "interaction",
+        %kind,
+        command,
+        arguments,
+        guild = scope.guild_id,
+        user = scope.user_id,
+        locale = %scope.locale,
+        latency_ms = field::Empty,
+        outcome = field::Empty,
+    );
+    let _entered = span.enter();
+
+    let start = Instant::now();
+    let (result, scope) = scope::with_scope(scope, handler);
+    let latency = start.elapsed();
+    let outcome = match scope.cancellation.is_cancelled() {
+        true => Outcome::TimedOut,
+        false => scope.outcome.unwrap_or(Outcome::Ok),
+    };
+
+    span.record("latency_ms", latency.as_millis() as u64);
+    span.record("outcome", outcome.label());
+    match (&outcome, kind) {
+        // only the correlation id is shown to the user, so this is where the details go
+        (Outcome::Error(BotError::Internal { id, message }), _) => {
+            error!(correlation_id = %id, error = message, "interaction failed")
+        }
+        // worth fixing in the data files, unlike a typo in an option
+        (Outcome::Error(error @ BotError::DataInconsistency(_)), _) => {
+            warn!(error = %error, "interaction failed")
+        }
+        (Outcome::Error(error), _) => info!(error = %error, "interaction failed"),
+        (Outcome::TimedOut, _) => warn!("interaction timed out"),
+        (_, InteractionKind::Autocomplete) => debug!("interaction handled"),
+        _ => info!("interaction handled"),
+    }
+
+    metrics::record(command, kind, latency, &outcome);
+    analytics::record(kind, command, lookup, scope.guild_id, &outcome);
+    result
+}
+
+/// The first option that names an object, mission or skill by id, ex: `item` in `/item drop item:7415`
+fn lookup_in_options(options: &[CommandDataOption]) -> Option<(Lookup, i32)> {
+    options.iter().find_map(|option| match &option.value {
+        CommandDataOptionValue::SubCommand(options)
+        | CommandDataOptionValue::SubCommandGroup(options) => lookup_in_options(options),
+        CommandDataOptionValue::Integer(id) => {
+            Some((Lookup::of_key(&option.name)?, i32::try_from(*id).ok()?))
+        }
+        _ => None,
+    })
+}
+
+/// Formats slash command options as `name=value`, ex: `item=7415 page=2`
+fn describe_options(options: &[CommandDataOption]) -> String {
+    options
+        .iter()
+        .map(|option| match &option.value {
+            CommandDataOptionValue::SubCommand(options)
+            | CommandDataOptionValue::SubCommandGroup(options) => {
+                format!("{} {}", option.name, describe_options(options))
+            }
+            CommandDataOptionValue::String(value) => format!("{}={value}", option.name),
+            CommandDataOptionValue::Integer(value) => format!("{}={value}", option.name),
+            CommandDataOptionValue::Number(value) => format!("{}={value}", option.name),
+            CommandDataOptionValue::Boolean(value) => format!("{}={value}", option.name),
+            CommandDataOptionValue::User(value) => format!("{}={value}", option.name),
+            CommandDataOptionValue::Autocomplete { value, .. } => {
+                format!("{}={value}", option.name)
+            }
+            _ => format!("{}=?", option.name),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
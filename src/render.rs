@@ -0,0 +1,276 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value;
+use serenity::all::{
+    ButtonStyle as DiscordButtonStyle, CreateActionRow, CreateButton, CreateEmbed,
+    CreateEmbedAuthor, CreateEmbedFooter, CreateInteractionResponseMessage, CreateSelectMenu,
+    CreateSelectMenuKind, CreateSelectMenuOption,
+};
+
+use crate::limits;
+use crate::response::{ActionRow, ButtonAction, ButtonStyle, Response};
+
+/// Turns a [`Response`] into what one frontend shows
+pub trait Renderer {
+    type Output;
+
+    fn render(&self, response: &Response) -> Self::Output;
+}
+
+/// Serenity builders, for the bot
+pub struct DiscordRenderer;
+
+/// Plain text without Markdown, ex: for a terminal
+pub struct TextRenderer;
+
+/// A Markdown document, ex: for a wiki page or a GitHub comment
+pub struct MarkdownRenderer;
+
+/// [`Response`] as is, for anything that wants to inspect it
+pub struct JsonRenderer;
+
+/// - Discord won't allow duplicate custom ids in a message, and the current button is never
+///   pressed, so they all get this placeholder
+const CURRENT_CUSTOM_ID: &str = "----";
+
+impl Renderer for DiscordRenderer {
+    type Output = (CreateEmbed, Vec<CreateActionRow>);
+
+    /// - Cuts the response to Discord's limits first (see [`limits::fit`]), since Discord rejects
+    ///   the whole message over any of them
+    fn render(&self, response: &Response) -> Self::Output {
+        let response = limits::fit(response.clone());
+        (build_embed(&response), build_components(&response))
+    }
+}
+
+impl DiscordRenderer {
+    /// Only the embed, ex: for an error or a cooldown, where the components would be built for nothing
+    /// - [`Renderer::render`] fits the response once for both the embed and the components
+    pub fn embed(&self, response: &Response) -> CreateEmbed {
+        build_embed(&limits::fit(response.clone()))
+    }
+
+    /// The embed and components of a reply or update
+    pub fn message(&self, response: &Response) -> CreateInteractionResponseMessage {
+        let (embed, components) = self.render(response);
+        CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .components(components)
+    }
+}
+
+fn build_embed(response: &Response) -> CreateEmbed {
+    let mut embed = CreateEmbed::new();
+    if let Some(title) = &response.title {
+        embed = embed.title(title);
+    }
+    if let Some(url) = &response.url {
+        embed = embed.url(url);
+    }
+    if let Some(description) = &response.description {
+        embed = embed.description(description);
+    }
+    if let Some(thumbnail) = &response.thumbnail {
+        embed = embed.thumbnail(thumbnail);
+    }
+    for field in &response.fields {
+        embed = embed.field(&field.name, &field.value, field.inline);
+    }
+    if let Some(color) = response.color {
+        embed = embed.color(color);
+    }
+    if let Some(author) = &response.author {
+        let mut builder = CreateEmbedAuthor::new(&author.name);
+        if let Some(icon_url) = &author.icon_url {
+            builder = builder.icon_url(icon_url);
+        }
+        if let Some(url) = &author.url {
+            builder = builder.url(url);
+        }
+        embed = embed.author(builder);
+    }
+    if let Some(footer) = &response.footer {
+        let mut builder = CreateEmbedFooter::new(&footer.text);
+        if let Some(icon_url) = &footer.icon_url {
+            builder = builder.icon_url(icon_url);
+        }
+        embed = embed.footer(builder);
+    }
+    embed
+}
+
+fn build_components(response: &Response) -> Vec<CreateActionRow> {
+    response
+        .components
+        .iter()
+        .map(|row| match row {
+            ActionRow::Buttons(buttons) => {
+                let buttons = buttons
+                    .iter()
+                    .map(|button| {
+                        let builder = match &button.action {
+                            ButtonAction::Run(custom_id) => CreateButton::new(custom_id),
+                            ButtonAction::Link(url) => CreateButton::new_link(url),
+                            ButtonAction::Current => CreateButton::new(CURRENT_CUSTOM_ID)
+                                .style(DiscordButtonStyle::Success)
+                                .disabled(true),
+                        };
+                        let builder = builder.label(&button.label);
+                        match button.action {
+                            ButtonAction::Run(_) => builder
+                                .style(discord_style(button.style))
+                                .disabled(button.disabled),
+                            ButtonAction::Link(_) => builder.disabled(button.disabled),
+                            ButtonAction::Current => builder,
+                        }
+                    })
+                    .collect();
+                CreateActionRow::Buttons(buttons)
+            }
+            ActionRow::SelectMenu(menu) => {
+                let options = menu
+                    .options
+                    .iter()
+                    .map(|option| {
+                        let builder = CreateSelectMenuOption::new(&option.label, &option.value);
+                        match &option.description {
+                            Some(description) => builder.description(description),
+                            None => builder,
+                        }
+                    })
+                    .collect();
+                let builder = CreateSelectMenu::new(
+                    &menu.custom_id,
+                    CreateSelectMenuKind::String { options },
+                );
+                CreateActionRow::SelectMenu(match &menu.placeholder {
+                    Some(placeholder) => builder.placeholder(placeholder),
+                    None => builder,
+                })
+            }
+        })
+        .collect()
+}
+
+fn discord_style(style: ButtonStyle) -> DiscordButtonStyle {
+    match style {
+        ButtonStyle::Primary => DiscordButtonStyle::Primary,
+        ButtonStyle::Secondary => DiscordButtonStyle::Secondary,
+        ButtonStyle::Success => DiscordButtonStyle::Success,
+        ButtonStyle::Danger => DiscordButtonStyle::Danger,
+    }
+}
+
+static LINK: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[([^\]]*)\]\([^)]*\)").unwrap());
+
+/// Drops the Markdown commands put in their text, ex: `[Name](url)` becomes `Name`
+fn plain(text: &str) -> String {
+    LINK.replace_all(text, "$1")
+        .replace("**", "")
+        .replace('`', "")
+}
+
+impl Renderer for TextRenderer {
+    type Output = String;
+
+    fn render(&self, response: &Response) -> Self::Output {
+        let mut lines = vec![];
+        if let Some(title) = &response.title {
+            lines.push(plain(title));
+        }
+        if let Some(url) = &response.url {
+            lines.push(url.clone());
+        }
+        if let Some(description) = &response.description {
+            lines.push(String::new());
+            lines.push(plain(description));
+        }
+        for field in &response.fields {
+            lines.push(String::new());
+            lines.push(format!("{}:", plain(&field.name)));
+            lines.push(plain(&field.value));
+        }
+        for (idx, row) in response.components.iter().enumerate() {
+            lines.push(String::new());
+            lines.push(format!("Row {}:", idx + 1));
+            match row {
+                ActionRow::Buttons(buttons) => {
+                    for button in buttons {
+                        let target = match &button.action {
+                            ButtonAction::Run(custom_id) => custom_id.clone(),
+                            ButtonAction::Link(url) => url.clone(),
+                            ButtonAction::Current => String::from("(shown)"),
+                        };
+                        let disabled = if button.disabled { " (disabled)" } else { "" };
+                        lines.push(format!("    [{}] {target}{disabled}", button.label));
+                    }
+                }
+                ActionRow::SelectMenu(menu) => {
+                    for option in &menu.options {
+                        lines.push(format!("    <{}> {}", option.label, option.value));
+                    }
+                }
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+impl Renderer for MarkdownRenderer {
+    type Output = String;
+
+    fn render(&self, response: &Response) -> Self::Output {
+        let mut blocks = vec![];
+        match (&response.title, &response.url) {
+            (Some(title), Some(url)) => blocks.push(format!("## [{title}]({url})")),
+            (Some(title), None) => blocks.push(format!("## {title}")),
+            (None, _) => {}
+        }
+        if let Some(thumbnail) = &response.thumbnail {
+            blocks.push(format!("![]({thumbnail})"));
+        }
+        if let Some(description) = &response.description {
+            blocks.push(description.clone());
+        }
+        for field in &response.fields {
+            blocks.push(format!("### {}\n{}", field.name, field.value));
+        }
+        let actions: Vec<_> = response
+            .components
+            .iter()
+            .flat_map(|row| match row {
+                ActionRow::Buttons(buttons) => buttons
+                    .iter()
+                    .filter_map(|button| match &button.action {
+                        ButtonAction::Run(custom_id) => {
+                            Some(format!("- {}: `{custom_id}`", button.label))
+                        }
+                        ButtonAction::Link(url) => Some(format!("- [{}]({url})", button.label)),
+                        ButtonAction::Current => None,
+                    })
+                    .collect::<Vec<_>>(),
+                ActionRow::SelectMenu(menu) => menu
+                    .options
+                    .iter()
+                    .map(|option| format!("- {}: `{}`", option.label, option.value))
+                    .collect(),
+            })
+            .collect();
+        if !actions.is_empty() {
+            blocks.push(actions.join("\n"));
+        }
+        if let Some(footer) = &response.footer {
+            blocks.push(format!("<sub>{}</sub>", footer.text));
+        }
+        blocks.join("\n\n")
+    }
+}
+
+impl Renderer for JsonRenderer {
+    type Output = Value;
+
+    fn render(&self, response: &Response) -> Self::Output {
+        serde_json::to_value(response).unwrap_or_default()
+    }
+}
@@ -0,0 +1,228 @@
+use serde::Serialize;
+
+/// What a command answers with, before it is turned into anything a frontend understands
+/// - Commands build this instead of serenity builders, so it can be inspected and compared
+/// - A [`crate::render::Renderer`] turns it into a Discord embed, text, Markdown or JSON
+/// - The builder methods mirror `CreateEmbed`, ex: `.title(..).field(..)`
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Response {
+    pub title: Option<String>,
+    pub url: Option<String>,
+    pub description: Option<String>,
+    pub thumbnail: Option<String>,
+    pub fields: Vec<Field>,
+    /// RGB
+    pub color: Option<(u8, u8, u8)>,
+    pub author: Option<Author>,
+    pub footer: Option<Footer>,
+    pub components: Vec<ActionRow>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Field {
+    pub name: String,
+    pub value: String,
+    pub inline: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Author {
+    pub name: String,
+    pub url: Option<String>,
+    pub icon_url: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Footer {
+    pub text: String,
+    pub icon_url: Option<String>,
+}
+
+impl Response {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn thumbnail(mut self, url: impl Into<String>) -> Self {
+        self.thumbnail = Some(url.into());
+        self
+    }
+
+    pub fn field(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+        inline: bool,
+    ) -> Self {
+        self.fields.push(Field {
+            name: name.into(),
+            value: value.into(),
+            inline,
+        });
+        self
+    }
+
+    pub fn fields<N: Into<String>, V: Into<String>>(
+        self,
+        fields: impl IntoIterator<Item = (N, V, bool)>,
+    ) -> Self {
+        fields
+            .into_iter()
+            .fold(self, |response, (name, value, inline)| {
+                response.field(name, value, inline)
+            })
+    }
+
+    pub fn color(mut self, color: (u8, u8, u8)) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn author(mut self, author: Author) -> Self {
+        self.author = Some(author);
+        self
+    }
+
+    pub fn footer(mut self, footer: Footer) -> Self {
+        self.footer = Some(footer);
+        self
+    }
+
+    /// Replaces the rows, ex: with the components built below the embed
+    pub fn components(mut self, components: Vec<ActionRow>) -> Self {
+        self.components = components;
+        self
+    }
+
+    pub fn row(mut self, row: ActionRow) -> Self {
+        self.components.push(row);
+        self
+    }
+}
+
+/// Discord shows at most 5 of these below a message
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "type", content = "components", rename_all = "snake_case")]
+pub enum ActionRow {
+    /// At most 5
+    Buttons(Vec<Button>),
+    SelectMenu(SelectMenu),
+}
+
+/// What pressing a button does
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", content = "target", rename_all = "snake_case")]
+pub enum ButtonAction {
+    /// Runs the command of a custom id (see [`crate::interaction_command::ToCustomId`])
+    Run(String),
+    /// Opens a url
+    Link(String),
+    /// Marks what is already shown, ex: the `Drop` button on `/item drop`; it is never pressable
+    Current,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ButtonStyle {
+    #[default]
+    Primary,
+    Secondary,
+    Success,
+    Danger,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Button {
+    pub label: String,
+    pub action: ButtonAction,
+    pub style: ButtonStyle,
+    pub disabled: bool,
+}
+
+impl Button {
+    pub fn new(label: impl Into<String>, action: ButtonAction) -> Self {
+        Self {
+            label: label.into(),
+            action,
+            style: ButtonStyle::default(),
+            disabled: false,
+        }
+    }
+
+    pub fn link(label: impl Into<String>, url: impl Into<String>) -> Self {
+        Self::new(label, ButtonAction::Link(url.into()))
+    }
+
+    pub fn style(mut self, style: ButtonStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+/// Choosing an option runs its custom id
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SelectMenu {
+    pub custom_id: String,
+    /// At most 25
+    pub options: Vec<SelectOption>,
+    /// Shown until something is chosen
+    pub placeholder: Option<String>,
+}
+
+impl SelectMenu {
+    pub fn new(custom_id: impl Into<String>, options: Vec<SelectOption>) -> Self {
+        Self {
+            custom_id: custom_id.into(),
+            options,
+            placeholder: None,
+        }
+    }
+
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = Some(placeholder.into());
+        self
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SelectOption {
+    pub label: String,
+    /// The custom id it runs
+    pub value: String,
+    pub description: Option<String>,
+}
+
+impl SelectOption {
+    pub fn new(label: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            value: value.into(),
+            description: None,
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
@@ -0,0 +1,207 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+use serenity::all::{
+    CommandInteraction, ComponentInteraction, Message, ModalInteraction, Permissions,
+};
+
+use crate::bot_config::BotConfig;
+use crate::metrics::Outcome;
+use crate::CONFIG;
+use crate::{data, settings};
+
+/// Who an interaction came from and where
+/// - Handlers only receive their parsed arguments, so this is how the config and locale of the
+///   interaction reach [`crate::Api`]
+#[derive(Clone, Debug)]
+pub struct InteractionScope {
+    pub guild_id: Option<u64>,
+    pub user_id: u64,
+    /// Permissions of the member in the guild (`None` in DMs)
+    pub permissions: Option<Permissions>,
+    /// [`CONFIG`] with the guild's settings applied
+    pub config: Arc<BotConfig>,
+    /// One of [`data::LuData::locales`], picked in this order:
+    /// 1. The user's `/language` preference
+    /// 2. The user's Discord locale
+    /// 3. The guild's locale (see [`Self::config`])
+    pub locale: String,
+    /// Set by the handler through [`set_outcome`]
+    pub outcome: Option<Outcome>,
+    /// Set once nobody waits for the handler anymore (see [`is_cancelled`])
+    pub cancellation: Cancellation,
+}
+
+/// Shared between a handler on the blocking pool and the task waiting for it
+#[derive(Clone, Debug, Default)]
+pub struct Cancellation(Arc<AtomicBool>);
+
+impl Cancellation {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl InteractionScope {
+    pub fn new(
+        guild_id: Option<u64>,
+        user_id: u64,
+        permissions: Option<Permissions>,
+        discord_locale: &str,
+    ) -> Self {
+        let config = match guild_id {
+            Some(guild_id) => Arc::new(settings::guild_settings(guild_id).apply(&CONFIG)),
+            None => BASE_CONFIG.clone(),
+        };
+        let data = data::current();
+        let locale = settings::user_locale(user_id)
+            .and_then(|locale| data.match_locale(&locale))
+            .or_else(|| data.match_locale(discord_locale))
+            .unwrap_or_else(|| config.locale.clone());
+        Self {
+            guild_id,
+            user_id,
+            permissions,
+            config,
+            locale,
+            outcome: None,
+            cancellation: Cancellation::default(),
+        }
+    }
+
+    /// Outside of Discord, ex: in `crab-cli`: no guild, no user and no stored settings
+    pub fn offline(locale: String) -> Self {
+        Self {
+            guild_id: None,
+            user_id: 0,
+            permissions: None,
+            config: BASE_CONFIG.clone(),
+            locale,
+            outcome: None,
+            cancellation: Cancellation::default(),
+        }
+    }
+
+    pub fn with_cancellation(mut self, cancellation: Cancellation) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    pub fn of_command(command: &CommandInteraction) -> Self {
+        Self::new(
+            command.guild_id.map(|id| id.get()),
+            command.user.id.get(),
+            command
+                .member
+                .as_ref()
+                .and_then(|member| member.permissions),
+            &command.locale,
+        )
+    }
+
+    pub fn of_component(interaction: &ComponentInteraction) -> Self {
+        Self::new(
+            interaction.guild_id.map(|id| id.get()),
+            interaction.user.id.get(),
+            interaction
+                .member
+                .as_ref()
+                .and_then(|member| member.permissions),
+            &interaction.locale,
+        )
+    }
+
+    pub fn of_modal(interaction: &ModalInteraction) -> Self {
+        Self::new(
+            interaction.guild_id.map(|id| id.get()),
+            interaction.user.id.get(),
+            interaction
+                .member
+                .as_ref()
+                .and_then(|member| member.permissions),
+            &interaction.locale,
+        )
+    }
+
+    /// - A message has no locale or permissions, so the user's or server's locale is used
+    pub fn of_message(message: &Message) -> Self {
+        Self::new(
+            message.guild_id.map(|id| id.get()),
+            message.author.id.get(),
+            None,
+            "",
+        )
+    }
+
+    pub fn can_manage_guild(&self) -> bool {
+        self.permissions
+            .is_some_and(|permissions| permissions.manage_guild())
+    }
+}
+
+static BASE_CONFIG: Lazy<Arc<BotConfig>> = Lazy::new(|| Arc::new(CONFIG.clone()));
+
+thread_local! {
+    static CURRENT: RefCell<Option<InteractionScope>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` with `scope` as the current scope, restoring the previous one afterwards
+/// - Also returns the scope as `f` left it, ex: with its [`InteractionScope::outcome`]
+pub fn with_scope<R>(scope: InteractionScope, f: impl FnOnce() -> R) -> (R, InteractionScope) {
+    let previous = CURRENT.with(|current| current.replace(Some(scope)));
+    let result = f();
+    let scope = CURRENT.with(|current| current.replace(previous));
+    (result, scope.expect("The scope is only taken here"))
+}
+
+/// Records how the current interaction ended (does nothing outside of an interaction)
+pub fn set_outcome(outcome: Outcome) {
+    CURRENT.with(|current| {
+        if let Some(scope) = current.borrow_mut().as_mut() {
+            scope.outcome = Some(outcome);
+        }
+    })
+}
+
+/// Whether the current interaction timed out, so a long scan can stop early
+/// - Its result is thrown away anyway, so what it returns does not matter
+pub fn is_cancelled() -> bool {
+    CURRENT.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .is_some_and(|scope| scope.cancellation.is_cancelled())
+    })
+}
+
+pub fn current() -> Option<InteractionScope> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// The config of the current interaction's guild, or [`CONFIG`] outside of an interaction
+pub fn config() -> Arc<BotConfig> {
+    CURRENT.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .map(|scope| scope.config.clone())
+            .unwrap_or_else(|| BASE_CONFIG.clone())
+    })
+}
+
+/// The locale of the current interaction, or the configured locale outside of an interaction
+pub fn locale() -> String {
+    CURRENT.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .map(|scope| scope.locale.clone())
+            .unwrap_or_else(|| BASE_CONFIG.locale.clone())
+    })
+}
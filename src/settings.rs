@@ -0,0 +1,439 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::{Mutex, RwLock};
+
+use once_cell::sync::OnceCell;
+use rusqlite::{params, Connection, OptionalExtension};
+use tracing::error;
+
+use crate::bot_config::BotConfig;
+use crate::error::{BotError, MsgResult};
+use crate::CONFIG;
+
+/// A setting that guild admins can override with `/settings`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GuildSetting {
+    Locale,
+    Color,
+    ExplorerUrl,
+    FooterMessage,
+    InlineLookups,
+    InlineChannels,
+}
+
+impl GuildSetting {
+    pub const ALL: [GuildSetting; 6] = [
+        GuildSetting::Locale,
+        GuildSetting::Color,
+        GuildSetting::ExplorerUrl,
+        GuildSetting::FooterMessage,
+        GuildSetting::InlineLookups,
+        GuildSetting::InlineChannels,
+    ];
+
+    /// The name used in the database, in `/settings` and in custom ids
+    pub fn key(&self) -> &'static str {
+        match self {
+            GuildSetting::Locale => "locale",
+            GuildSetting::Color => "color",
+            GuildSetting::ExplorerUrl => "explorer_url",
+            GuildSetting::FooterMessage => "footer_message",
+            GuildSetting::InlineLookups => "inline_lookups",
+            GuildSetting::InlineChannels => "inline_channels",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            GuildSetting::Locale => {
+                "Locale for members whose language is not in the game, ex: `de_DE`"
+            }
+            GuildSetting::Color => "Embed color, ex: `#1a87e8`",
+            GuildSetting::ExplorerUrl => "Explorer linked to in embeds",
+            GuildSetting::FooterMessage => "Footer of every embed",
+            GuildSetting::InlineLookups => {
+                "Reply to `[[Item Name]]` in chat with its card, `on` or `off`"
+            }
+            GuildSetting::InlineChannels => {
+                "Channels for inline lookups, ex: `#trading #help`; every channel when unset"
+            }
+        }
+    }
+
+    /// The value every guild gets unless it overrides it
+    pub fn default_value(&self, config: &BotConfig) -> Option<String> {
+        match self {
+            GuildSetting::Locale => Some(config.locale.clone()),
+            GuildSetting::Color => config.color.clone(),
+            GuildSetting::ExplorerUrl => Some(config.explorer_url.clone()),
+            GuildSetting::FooterMessage => config.footer_message.clone(),
+            GuildSetting::InlineLookups => Some(switch(config.inline_lookups).to_string()),
+            GuildSetting::InlineChannels => None,
+        }
+    }
+
+    /// Checks a value before it is stored, so a bad value can never break a guild's embeds
+    pub fn validate(&self, value: &str) -> MsgResult<()> {
+        let mut config = CONFIG.clone();
+        self.apply(&mut config, value.to_string());
+        match self {
+            GuildSetting::Locale => {
+                let locales = crate::data::current().locale_names();
+                if !locales.contains(&config.locale) {
+                    Err(format!(
+                        "`{value}` is not a locale, expected one of: {}",
+                        locales
+                            .iter()
+                            .map(|l| format!("`{l}`"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ))?
+                }
+            }
+            GuildSetting::Color => {
+                if config.embed_color().is_none() {
+                    Err(format!("`{value}` is not a hex color like `#1a87e8`"))?
+                }
+            }
+            GuildSetting::ExplorerUrl => {
+                if !value.starts_with("https://") && !value.starts_with("http://") {
+                    Err(format!("`{value}` is not an http(s) url"))?
+                }
+            }
+            GuildSetting::FooterMessage => {
+                if value.chars().count() > 2048 {
+                    Err(String::from("The footer can be at most 2048 characters"))?
+                }
+            }
+            GuildSetting::InlineLookups => match parse_switch(value) {
+                None => Err(format!("`{value}` is not `on` or `off`"))?,
+                Some(true) if !CONFIG.message_content_intent => Err(String::from(
+                    "The bot can't read messages, so ask its owner to set `message_content_intent`",
+                ))?,
+                Some(_) => {}
+            },
+            GuildSetting::InlineChannels => {
+                if parse_channels(value).is_none() {
+                    Err(format!(
+                        "`{value}` is not a list of channels like `#trading #help`"
+                    ))?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn apply(&self, config: &mut BotConfig, value: String) {
+        match self {
+            GuildSetting::Locale => config.locale = value,
+            GuildSetting::Color => config.color = Some(value),
+            GuildSetting::ExplorerUrl => config.explorer_url = value,
+            GuildSetting::FooterMessage => config.footer_message = Some(value),
+            GuildSetting::InlineLookups => {
+                config.inline_lookups = parse_switch(&value).unwrap_or(false)
+            }
+            // an empty list is every channel, so a list that does not parse turns lookups off
+            // instead, which holds since guild settings apply in the order of `GuildSetting`
+            GuildSetting::InlineChannels => match parse_channels(&value) {
+                Some(channels) => config.inline_channels = channels,
+                None => config.inline_lookups = false,
+            },
+        }
+    }
+}
+
+fn switch(on: bool) -> &'static str {
+    match on {
+        true => "on",
+        false => "off",
+    }
+}
+
+/// `on` or `off`, or `true` or `false`
+fn parse_switch(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "on" | "true" => Some(true),
+        "off" | "false" => Some(false),
+        _ => None,
+    }
+}
+
+/// Channel mentions or ids split by spaces or commas, ex: `<#123>, 456`
+fn parse_channels(value: &str) -> Option<Vec<u64>> {
+    let channels: Option<Vec<u64>> = value
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|channel| !channel.is_empty())
+        .map(|channel| {
+            let id = channel
+                .strip_prefix("<#")
+                .and_then(|channel| channel.strip_suffix('>'))
+                .unwrap_or(channel);
+            id.parse().ok()
+        })
+        .collect();
+    channels.filter(|channels| !channels.is_empty())
+}
+
+impl FromStr for GuildSetting {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        GuildSetting::ALL
+            .into_iter()
+            .find(|setting| setting.key() == s)
+            .ok_or_else(|| format!("`{s}` is not a setting"))
+    }
+}
+
+impl Display for GuildSetting {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+/// The overrides of one guild; anything missing comes from [`CONFIG`]
+#[derive(Clone, Debug, Default)]
+pub struct GuildSettings {
+    pub values: BTreeMap<GuildSetting, String>,
+}
+
+impl GuildSettings {
+    pub fn get(&self, setting: GuildSetting) -> Option<&str> {
+        self.values.get(&setting).map(String::as_str)
+    }
+
+    /// The config with this guild's overrides applied
+    pub fn apply(&self, config: &BotConfig) -> BotConfig {
+        let mut config = config.clone();
+        for (setting, value) in &self.values {
+            setting.apply(&mut config, value.clone());
+        }
+        config
+    }
+}
+
+/// Guild and user settings live in their own SQLite file (see [`BotConfig::settings_path`])
+/// - The cdclient is read-only game data, so nothing is ever written to it
+/// - Every guild or user is read once and then cached, since settings are read on every interaction
+/// - The caches are only filled or changed while holding `conn`, so a read that misses the cache
+///   can't put back what a write just replaced
+struct SettingsStore {
+    conn: Mutex<Connection>,
+    cache: RwLock<HashMap<u64, GuildSettings>>,
+    user_locales: Mutex<UserLocales>,
+}
+
+/// Users whose locale is cached, at most
+const MAX_CACHED_USERS: usize = 10_000;
+
+/// The locales of the users seen most recently
+/// - Once it holds [`MAX_CACHED_USERS`], the least recently seen half is dropped, so a lookup
+///   never scans it
+#[derive(Default)]
+struct UserLocales {
+    /// The locale of each user, and when it was last looked up
+    entries: HashMap<u64, (Option<String>, u64)>,
+    tick: u64,
+}
+
+impl UserLocales {
+    fn get(&mut self, user_id: u64) -> Option<Option<String>> {
+        self.tick += 1;
+        let tick = self.tick;
+        self.entries.get_mut(&user_id).map(|(locale, used)| {
+            *used = tick;
+            locale.clone()
+        })
+    }
+
+    fn insert(&mut self, user_id: u64, locale: Option<String>) {
+        if self.entries.len() >= MAX_CACHED_USERS {
+            let mut used: Vec<u64> = self.entries.values().map(|(_, used)| *used).collect();
+            let middle = used.len() / 2;
+            let (_, median, _) = used.select_nth_unstable(middle);
+            let median = *median;
+            self.entries.retain(|_, (_, used)| *used > median);
+        }
+        self.tick += 1;
+        self.entries.insert(user_id, (locale, self.tick));
+    }
+}
+
+static STORE: OnceCell<SettingsStore> = OnceCell::new();
+
+fn store() -> MsgResult<&'static SettingsStore> {
+    STORE.get_or_try_init(|| SettingsStore::open(&CONFIG))
+}
+
+impl SettingsStore {
+    fn open(config: &BotConfig) -> MsgResult<Self> {
+        let path = &config.settings_path;
+        let conn = Connection::open(path)
+            .map_err(|e| BotError::internal(format!("Could not open `{}`: {e}", path.display())))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS guild_settings (
+                guild_id INTEGER NOT NULL,
+                setting  TEXT    NOT NULL,
+                value    TEXT    NOT NULL,
+                PRIMARY KEY (guild_id, setting)
+            );
+            CREATE TABLE IF NOT EXISTS user_settings (
+                user_id INTEGER NOT NULL,
+                setting TEXT    NOT NULL,
+                value   TEXT    NOT NULL,
+                PRIMARY KEY (user_id, setting)
+            );",
+        )
+        .map_err(|e| {
+            BotError::internal(format!(
+                "Could not create tables in `{}`: {e}",
+                path.display()
+            ))
+        })?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+            cache: RwLock::new(HashMap::new()),
+            user_locales: Mutex::new(UserLocales::default()),
+        })
+    }
+
+    fn load(conn: &Connection, guild_id: u64) -> MsgResult<GuildSettings> {
+        let mut stmt = conn
+            .prepare_cached("SELECT setting, value FROM guild_settings WHERE guild_id = ?1")
+            .map_err(BotError::internal)?;
+        let rows = stmt
+            .query_map(params![guild_id as i64], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(BotError::internal)?;
+        let mut settings = GuildSettings::default();
+        for row in rows {
+            let (setting, value) = row.map_err(BotError::internal)?;
+            // settings that were removed from the bot are ignored
+            if let Ok(setting) = setting.parse() {
+                settings.values.insert(setting, value);
+            }
+        }
+        Ok(settings)
+    }
+
+    fn load_user_locale(conn: &Connection, user_id: u64) -> MsgResult<Option<String>> {
+        conn.query_row(
+            "SELECT value FROM user_settings WHERE user_id = ?1 AND setting = 'locale'",
+            params![user_id as i64],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(BotError::internal)
+    }
+}
+
+/// Opens the settings database now, so a bad `settings_path` is reported at startup
+pub fn init() -> MsgResult<()> {
+    store().map(|_| ())
+}
+
+/// - Falls back to no overrides if the database cannot be read, so commands keep working
+pub fn guild_settings(guild_id: u64) -> GuildSettings {
+    let store = match store() {
+        Ok(store) => store,
+        Err(e) => {
+            error!("Could not load the settings of guild {guild_id}: {e}");
+            return GuildSettings::default();
+        }
+    };
+    if let Some(settings) = store.cache.read().unwrap().get(&guild_id) {
+        return settings.clone();
+    }
+    let conn = store.conn.lock().unwrap();
+    // filled while waiting for the connection
+    if let Some(settings) = store.cache.read().unwrap().get(&guild_id) {
+        return settings.clone();
+    }
+    let settings = SettingsStore::load(&conn, guild_id).unwrap_or_else(|e| {
+        error!("Could not load the settings of guild {guild_id}: {e}");
+        GuildSettings::default()
+    });
+    store
+        .cache
+        .write()
+        .unwrap()
+        .insert(guild_id, settings.clone());
+    settings
+}
+
+/// Stores an override, or removes it when `value` is `None`
+pub fn set_guild_setting(
+    guild_id: u64,
+    setting: GuildSetting,
+    value: Option<String>,
+) -> MsgResult<()> {
+    if let Some(ref value) = value {
+        setting.validate(value)?;
+    }
+    let store = store()?;
+    {
+        let conn = store.conn.lock().unwrap();
+        let result = match value {
+            Some(ref value) => conn.execute(
+                "INSERT INTO guild_settings (guild_id, setting, value) VALUES (?1, ?2, ?3)
+                 ON CONFLICT (guild_id, setting) DO UPDATE SET value = excluded.value",
+                params![guild_id as i64, setting.key(), value],
+            ),
+            None => conn.execute(
+                "DELETE FROM guild_settings WHERE guild_id = ?1 AND setting = ?2",
+                params![guild_id as i64, setting.key()],
+            ),
+        };
+        result.map_err(|e| BotError::internal(format!("Could not save `{setting}`: {e}")))?;
+        store.cache.write().unwrap().remove(&guild_id);
+    }
+    Ok(())
+}
+
+/// The locale a user picked with `/language`, if any
+pub fn user_locale(user_id: u64) -> Option<String> {
+    let store = store().ok()?;
+    if let Some(locale) = store.user_locales.lock().unwrap().get(user_id) {
+        return locale;
+    }
+    let conn = store.conn.lock().unwrap();
+    // filled while waiting for the connection
+    if let Some(locale) = store.user_locales.lock().unwrap().get(user_id) {
+        return locale;
+    }
+    let locale = SettingsStore::load_user_locale(&conn, user_id).unwrap_or_else(|e| {
+        error!("Could not load the locale of user {user_id}: {e}");
+        None
+    });
+    store
+        .user_locales
+        .lock()
+        .unwrap()
+        .insert(user_id, locale.clone());
+    locale
+}
+
+/// Stores the locale a user prefers, or removes it when `locale` is `None`
+/// - `locale` must already be one of [`crate::data::LuData::locale_names`]
+pub fn set_user_locale(user_id: u64, locale: Option<String>) -> MsgResult<()> {
+    let store = store()?;
+    {
+        let conn = store.conn.lock().unwrap();
+        let result = match locale {
+            Some(ref locale) => conn.execute(
+                "INSERT INTO user_settings (user_id, setting, value) VALUES (?1, 'locale', ?2)
+                 ON CONFLICT (user_id, setting) DO UPDATE SET value = excluded.value",
+                params![user_id as i64, locale],
+            ),
+            None => conn.execute(
+                "DELETE FROM user_settings WHERE user_id = ?1 AND setting = 'locale'",
+                params![user_id as i64],
+            ),
+        };
+        result.map_err(|e| BotError::internal(format!("Could not save your locale: {e}")))?;
+        store.user_locales.lock().unwrap().entries.remove(&user_id);
+    }
+    Ok(())
+}
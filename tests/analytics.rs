@@ -0,0 +1,79 @@
+use std::path::Path;
+
+use lu_discord_bot::analytics::{AnalyticsStore, Lookup};
+use lu_discord_bot::error::BotError;
+use lu_discord_bot::metrics::{InteractionKind, Outcome};
+
+fn store() -> AnalyticsStore {
+    let store = AnalyticsStore::open(Path::new(":memory:")).unwrap();
+    let record = |kind, command, lookup, outcome: &Outcome| {
+        store.record(kind, command, lookup, Some(1), outcome);
+    };
+    let slash = InteractionKind::Slash;
+    let item = |id| Some((Lookup::Object, id));
+    for _ in 0..3 {
+        record(slash, "drop", item(7415), &Outcome::Ok);
+    }
+    record(InteractionKind::Component, "drop", item(7415), &Outcome::Ok);
+    record(slash, "item", item(1727), &Outcome::Ok);
+    record(slash, "item", item(1727), &Outcome::Ok);
+    record(
+        slash,
+        "item",
+        item(3),
+        &Outcome::Error(BotError::NotFound(String::from("no"))),
+    );
+    record(
+        slash,
+        "mission",
+        Some((Lookup::Mission, 1727)),
+        &Outcome::Ok,
+    );
+    // typing is neither a lookup nor a use
+    for _ in 0..10 {
+        record(
+            InteractionKind::Autocomplete,
+            "level",
+            item(9),
+            &Outcome::Ok,
+        );
+    }
+    store
+}
+
+#[test]
+fn lookups_are_counted_by_kind() {
+    let store = store();
+    assert_eq!(
+        store.top_lookups(Lookup::Object, 0, 10).unwrap(),
+        [(7415, 4), (1727, 2)]
+    );
+    assert_eq!(
+        store.top_lookups(Lookup::Object, 0, 1).unwrap(),
+        [(7415, 4)]
+    );
+    assert_eq!(
+        store.top_lookups(Lookup::Mission, 0, 10).unwrap(),
+        [(1727, 1)]
+    );
+    assert!(store.top_lookups(Lookup::Skill, 0, 10).unwrap().is_empty());
+    assert!(store
+        .top_lookups(Lookup::Object, u64::MAX >> 1, 10)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn commands_are_counted_without_autocomplete() {
+    let store = store();
+    assert_eq!(
+        store.busiest_commands(0, 10).unwrap(),
+        [
+            (String::from("drop"), 4),
+            (String::from("item"), 3),
+            (String::from("mission"), 1),
+        ]
+    );
+    // what is still queued is written first
+    assert_eq!(store.pending(), 0);
+}
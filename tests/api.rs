@@ -0,0 +1,122 @@
+#![cfg(feature = "api")]
+
+use lu_discord_bot::api::{self, Endpoint, ObjectRef, Page, Request, DEFAULT_PER_PAGE};
+use lu_discord_bot::error::BotError;
+use serde_json::json;
+
+#[test]
+fn requests_are_routed_to_their_endpoint() {
+    for endpoint in Endpoint::ALL {
+        let line = format!("GET /objects/7415/{} HTTP/1.1", endpoint.path());
+        let request = Request::parse(&line).unwrap();
+        assert_eq!(request.object, 7415);
+        assert_eq!(request.endpoint, endpoint);
+        assert_eq!(request.page, 1);
+        assert_eq!(request.per_page, DEFAULT_PER_PAGE);
+        assert_eq!(request.locale, None);
+    }
+
+    let request =
+        Request::parse("GET /objects/7415/drops/?page=2&per_page=10&locale=de_DE HTTP/1.1")
+            .unwrap();
+    assert_eq!(request.endpoint, Endpoint::Drops);
+    assert_eq!((request.page, request.per_page), (2, 10));
+    assert_eq!(request.locale.as_deref(), Some("de_DE"));
+
+    let request =
+        Request::parse("GET /objects/7415/drops?%70age=%32&locale=de%5FDE HTTP/1.1").unwrap();
+    assert_eq!(request.page, 2);
+    assert_eq!(request.locale.as_deref(), Some("de_DE"));
+    let request =
+        Request::parse("GET /objects/7415/drops?locale=Crab%20Force+Helmet HTTP/1.1").unwrap();
+    assert_eq!(request.locale.as_deref(), Some("Crab Force Helmet"));
+}
+
+#[test]
+fn bad_requests_are_rejected() {
+    let bad_argument = |line: &str| match Request::parse(line) {
+        Err(BotError::BadArgument(message)) => message,
+        other => panic!("`{line}` was not a bad argument: {other:?}"),
+    };
+    assert!(bad_argument("GET /objects/crab/drops HTTP/1.1").contains("`crab` is not an object id"));
+    assert!(bad_argument("GET /objects/99999999999/drops HTTP/1.1").contains("is not an object id"));
+    assert!(bad_argument("POST /objects/7415/drops HTTP/1.1").contains("`GET`"));
+    assert!(bad_argument("GET /objects/7415/drops?page=two HTTP/1.1").contains("`page`"));
+    assert!(bad_argument("GET /objects/7415/drops?per_page=101 HTTP/1.1").contains("`per_page`"));
+    assert!(bad_argument("GET /objects/7415/drops?sort=chance HTTP/1.1").contains("`sort`"));
+    assert!(bad_argument("GET /objects/7415/drops?locale=de%5 HTTP/1.1").contains("`de%5`"));
+    assert!(bad_argument("GET /objects/7415/drops?page=%FF HTTP/1.1").contains("`%FF`"));
+}
+
+#[test]
+fn unknown_paths_are_not_found() {
+    for path in [
+        "/",
+        "/objects",
+        "/objects/7415",
+        "/objects/7415/sells",
+        "/items/7415/drops",
+    ] {
+        let line = format!("GET {path} HTTP/1.1");
+        match Request::parse(&line) {
+            Err(BotError::NotFound(message)) => assert!(message.contains("/objects/{id}/")),
+            other => panic!("`{path}` was found: {other:?}"),
+        }
+    }
+}
+
+/// Errors are caught before anything is looked up, so no data files are needed
+#[tokio::test]
+async fn errors_are_served_as_json() {
+    let (status, body) = api::response("GET /objects/crab/drops HTTP/1.1").await;
+    assert_eq!(status, "400 Bad Request");
+    assert_eq!(
+        body,
+        json!({ "error": "bad_argument", "message": "`crab` is not an object id" })
+    );
+
+    let (status, body) = api::response("GET /favicon.ico HTTP/1.1").await;
+    assert_eq!(status, "404 Not Found");
+    assert_eq!(body["error"], "not_found");
+}
+
+fn vendor(id: i32) -> ObjectRef {
+    ObjectRef {
+        id,
+        name: format!("Vendor {id}"),
+        url: format!("https://explorer.lu/objects/{id}"),
+    }
+}
+
+#[test]
+fn an_object_query_is_one_page_of_results() {
+    let object = ObjectRef {
+        id: 7415,
+        name: String::from("Crab Force Helmet"),
+        url: String::from("https://explorer.lu/objects/7415"),
+    };
+    let results = (1..=5).map(|id| json!(vendor(id))).collect();
+    let page = Page::new(object, results, 2, 2).unwrap();
+    assert_eq!(
+        json!(page),
+        json!({
+            "object": {
+                "id": 7415,
+                "name": "Crab Force Helmet",
+                "url": "https://explorer.lu/objects/7415",
+            },
+            "page": 2,
+            "per_page": 2,
+            "pages": 3,
+            "total": 5,
+            "results": [json!(vendor(3)), json!(vendor(4))],
+        })
+    );
+
+    let results = (1..=5).map(|id| json!(vendor(id))).collect();
+    match Page::new(vendor(7415), results, 4, 2) {
+        Err(BotError::BadArgument(message)) => assert!(message.contains("between 1 and 3")),
+        Err(other) => panic!("{other:?}"),
+        Ok(_) => panic!("page 4 of 3 was served"),
+    }
+}
@@ -0,0 +1,231 @@
+use lu_discord_bot::arguments::{CommandArguments, OptionChoice, OptionValue};
+use lu_discord_bot::commands::drop::{DropArguments, DropCommand, DropSort};
+use lu_discord_bot::commands::help::HelpArguments;
+use lu_discord_bot::commands::level::LevelArguments;
+use lu_discord_bot::commands::skillitems::SkillItemsArguments;
+use lu_discord_bot::commands::stats::{StatsArguments, Window};
+use lu_discord_bot::interaction_command::{
+    CommandResult, CustomIdOptions, InteractionCommand, ToCustomId,
+};
+use lu_discord_bot::response::Response;
+use serde_json::{json, Value};
+
+/// Only here to name the custom ids of [`EchoArguments`]
+struct EchoCommand;
+
+#[derive(CommandArguments)]
+#[arguments(command = EchoCommand, version = 3)]
+struct EchoArguments {
+    /// A mission in LEGO Universe.
+    #[option(required, autocomplete = "mission")]
+    mission: i32,
+    /// How many times to say it.
+    // whole numbers can't have a bound under 0, which fails to compile
+    #[option(min = 1, max = 10)]
+    times: Option<u32>,
+    /// Whether to shout it.
+    #[option]
+    loud: Option<bool>,
+    #[page]
+    page: usize,
+}
+
+impl InteractionCommand for EchoCommand {
+    const NAME: &'static str = "echo";
+    const DESCRIPTION: &'static str = "Say a mission again!";
+
+    type Arguments = EchoArguments;
+
+    fn run(_: Self::Arguments) -> CommandResult {
+        Ok(Response::new())
+    }
+}
+
+fn round_trip<T: ToCustomId + for<'a> TryFrom<&'a CustomIdOptions>>(arguments: &T) -> T {
+    let custom_id = arguments.to_custom_id(false);
+    let options = CustomIdOptions::from_custom_id(&custom_id).unwrap();
+    assert_eq!(options.cmd, T::CMD);
+    assert_eq!(options.version, T::VERSION);
+    T::try_from(&options).unwrap_or_else(|_| panic!("{custom_id} did not parse back"))
+}
+
+#[test]
+fn derived_arguments_round_trip() {
+    let drop = round_trip(&DropArguments {
+        item: 7415,
+        min_chance: 2.5,
+        sort: DropSort::Lowest,
+        page: 3,
+        size: 10,
+    });
+    assert_eq!((drop.item, drop.page, drop.size), (7415, 3, 10));
+    assert_eq!((drop.min_chance, drop.sort), (2.5, DropSort::Lowest));
+
+    let stats = round_trip(&StatsArguments {
+        window: Window::Month,
+    });
+    assert_eq!(stats.window, Window::Month);
+
+    let skill_items = round_trip(&SkillItemsArguments {
+        skill: -1,
+        page: 2,
+        size: 15,
+    });
+    assert_eq!(
+        (skill_items.skill, skill_items.page, skill_items.size),
+        (-1, 2, 15)
+    );
+
+    // text keeps what separates keys and values
+    let help = HelpArguments {
+        command: Some(String::from("a&b=c~d%26 é")),
+        page: 2,
+    };
+    assert_eq!(
+        help.to_custom_id(false),
+        "u/help.1:command=a%26b%3Dc%7Ed%2526 é&page=2"
+    );
+    let help = round_trip(&help);
+    assert_eq!(help.command.as_deref(), Some("a&b=c~d%26 é"));
+
+    let longest = round_trip(&DropArguments::longest());
+    assert_eq!(
+        (longest.item, longest.page, longest.size),
+        (i32::MIN, usize::MAX, usize::MAX)
+    );
+}
+
+#[test]
+fn optional_fields_round_trip() {
+    let echo = EchoArguments {
+        mission: 1727,
+        times: Some(2),
+        loud: Some(true),
+        page: 4,
+    };
+    assert_eq!(
+        echo.to_custom_id(true),
+        "r/echo.3:mission~Rr&times=2&loud=true&page=4"
+    );
+    let echo = round_trip(&echo);
+    assert_eq!(
+        (echo.mission, echo.times, echo.loud, echo.page),
+        (1727, Some(2), Some(true), 4)
+    );
+
+    let quiet = EchoArguments {
+        mission: 1727,
+        times: None,
+        loud: None,
+        page: 1,
+    };
+    assert_eq!(quiet.to_custom_id(false), "u/echo.3:mission~Rr&page=1");
+    let quiet = round_trip(&quiet);
+    assert_eq!((quiet.times, quiet.loud), (None, None));
+}
+
+#[test]
+fn options_with_defaults_can_be_left_out_of_custom_ids() {
+    // from before `/drop` had `min_chance` and `sort`
+    let options = CustomIdOptions::from_custom_id("u/drop.1:item~1vb&page=2&size=5").unwrap();
+    let drop = DropArguments::try_from(&options).unwrap();
+    assert_eq!((drop.min_chance, drop.sort), (0.0, DropSort::Highest));
+
+    let options = CustomIdOptions::from_custom_id("u/stats.1:").unwrap();
+    assert_eq!(
+        StatsArguments::try_from(&options).unwrap().window,
+        Window::Week
+    );
+}
+
+#[test]
+fn choices_are_their_keys() {
+    assert_eq!(DropSort::ALL, &[DropSort::Highest, DropSort::Lowest]);
+    assert_eq!(DropSort::Lowest.to_string(), "lowest");
+    assert_eq!("lowest".parse::<DropSort>(), Ok(DropSort::Lowest));
+    assert!("Lowest chance first".parse::<DropSort>().is_err());
+    assert_eq!(Window::All.label(), "All Time");
+    assert_eq!(<Window as OptionValue>::longest(), Window::Month);
+}
+
+#[test]
+fn bad_custom_id_values_are_rejected() {
+    let options = CustomIdOptions::from_custom_id("u/echo.3:mission=1727&loud=yes&page=1").unwrap();
+    assert!(EchoArguments::try_from(&options).is_err());
+
+    let options = CustomIdOptions::from_custom_id("u/echo.3:times=2&page=1").unwrap();
+    assert!(EchoArguments::try_from(&options).is_err());
+
+    let options =
+        CustomIdOptions::from_custom_id("u/drop.1:item=1&sort=random&page=1&size=5").unwrap();
+    assert!(DropArguments::try_from(&options).is_err());
+
+    assert!(CustomIdOptions::from_custom_id("u/help:command=100%&page=1").is_err());
+    assert!(CustomIdOptions::from_custom_id("u/help:command=%zz&page=1").is_err());
+}
+
+fn options<T: CommandArguments>() -> Vec<Value> {
+    T::options()
+        .into_iter()
+        .map(|option| serde_json::to_value(option).unwrap())
+        .collect()
+}
+
+#[test]
+fn options_come_from_the_fields() {
+    let drop = options::<DropArguments>();
+    let names: Vec<_> = drop.iter().map(|option| &option["name"]).collect();
+    assert_eq!(
+        names,
+        ["item", "min_chance", "sort"],
+        "not `page` or `size`"
+    );
+    assert_eq!(drop[0]["name"], "item");
+    assert_eq!(drop[0]["description"], "An item in LEGO Universe.");
+    assert_eq!(drop[0]["type"], 4);
+    assert_eq!(drop[0]["required"], true);
+    assert_eq!(drop[0]["autocomplete"], true);
+
+    assert_eq!(drop[1]["type"], 10);
+    assert_eq!(drop[1]["required"], false);
+    assert_eq!(
+        (&drop[1]["min_value"], &drop[1]["max_value"]),
+        (&json!(0.0), &json!(100.0))
+    );
+
+    assert_eq!(drop[2]["type"], 3);
+    assert_eq!(
+        drop[2]["choices"],
+        json!([
+            {"name": "Highest chance first", "value": "highest"},
+            {"name": "Lowest chance first", "value": "lowest"},
+        ])
+    );
+
+    let level = options::<LevelArguments>();
+    assert_eq!(level[0]["autocomplete"], false);
+
+    let echo = options::<EchoArguments>();
+    let summary: Vec<_> = echo
+        .iter()
+        .map(|option| json!([option["name"], option["type"], option["required"]]))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            json!(["mission", 4, true]),
+            json!(["times", 4, false]),
+            json!(["loud", 5, false]),
+        ]
+    );
+    assert_eq!(
+        (&echo[1]["min_value"], &echo[1]["max_value"]),
+        (&json!(1), &json!(10))
+    );
+}
+
+#[test]
+fn command_options_are_the_arguments_options() {
+    let command = serde_json::to_value(DropCommand::register()).unwrap();
+    assert_eq!(command["options"], json!(options::<DropArguments>()));
+}
@@ -0,0 +1,16 @@
+use std::process::Command;
+
+#[test]
+fn outdated_custom_ids_are_not_run() {
+    // any file will do for the data files, since an outdated id never gets to load them
+    let output = Command::new(env!("CARGO_BIN_EXE_crab-cli"))
+        .env("CRAB_FORCE_CDCLIENT_PATH", "Cargo.toml")
+        .env("CRAB_FORCE_LOCALE_PATH", "Cargo.toml")
+        .args(["--custom-id", "u/drop.0:item~1vb&page=2&size=5"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("is outdated"), "{stderr}");
+    assert!(output.stdout.is_empty());
+}
@@ -0,0 +1,60 @@
+use std::env;
+use std::fs;
+
+use lu_discord_bot::bot_config::{BotConfig, CONFIG_PATH_VAR, ENV_PREFIX, TOKEN_VAR};
+
+/// Environment variables are shared by the whole process, so every case runs in this one test
+#[test]
+fn config_problems_are_reported_together() {
+    let path = env::temp_dir().join(format!("crab-force-config-{}.toml", std::process::id()));
+    // any file will do for the data files, since loading them is not checked here
+    fs::write(
+        &path,
+        "cdclient_path = \"Cargo.toml\"\nlocale_path = \"Cargo.toml\"\ncolor = \"#1a87e8\"\n",
+    )
+    .unwrap();
+    env::set_var(CONFIG_PATH_VAR, &path);
+    let set = |field: &str, value: &str| env::set_var(format!("{ENV_PREFIX}{field}"), value);
+    let unset = |field: &str| env::remove_var(format!("{ENV_PREFIX}{field}"));
+    env::remove_var(TOKEN_VAR);
+    env::remove_var("GUILD_ID");
+
+    set("LOCALE", "de_DE");
+    set("COLOR", "blue");
+    set("DEFER_AFTER_MS", "soon");
+    set("HANDLER_TIMEOUT_SECS", "901");
+    let errors = BotConfig::load().unwrap_err();
+    assert_eq!(errors.len(), 3, "{errors:?}");
+    assert!(errors.iter().any(|error| error.starts_with("`color`")));
+    assert!(errors
+        .iter()
+        .any(|error| error.starts_with("`defer_after_ms`")));
+    assert!(errors
+        .iter()
+        .any(|error| error.starts_with("`handler_timeout_secs`")));
+
+    // an empty value unsets an optional field, back to no color
+    set("COLOR", "");
+    unset("DEFER_AFTER_MS");
+    set("HANDLER_TIMEOUT_SECS", "60");
+    let config = BotConfig::load().unwrap();
+    assert_eq!(config.locale, "de_DE");
+    assert_eq!(config.color, None);
+    assert_eq!(config.handler_timeout_secs, 60);
+
+    // what only the bot needs comes with the rest
+    set("COLOR", "blue");
+    let errors = BotConfig::load_bot().unwrap_err();
+    assert_eq!(errors.len(), 3, "{errors:?}");
+    assert!(errors.iter().any(|error| error.starts_with("`guild_ids`")));
+    assert!(errors.iter().any(|error| error.contains(TOKEN_VAR)));
+
+    unset("COLOR");
+    set("GUILD_IDS", "1, 2");
+    env::set_var(TOKEN_VAR, "token");
+    let (config, token) = BotConfig::load_bot().unwrap();
+    assert_eq!(config.guild_ids, [1, 2]);
+    assert_eq!(token, "token");
+
+    fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,69 @@
+use lu_discord_bot::commands::drop::DropArguments;
+use lu_discord_bot::commands::COMMANDS;
+use lu_discord_bot::interaction_command::{CustomIdOptions, ToCustomId, CUSTOM_ID_LIMIT};
+
+#[test]
+fn longest_custom_ids_fit_discords_limit() {
+    for command in COMMANDS.iter() {
+        let custom_id = (command.longest_custom_id)();
+        assert!(
+            custom_id.len() <= CUSTOM_ID_LIMIT,
+            "`{}` makes custom ids of {} characters: {custom_id}",
+            command.name,
+            custom_id.len()
+        );
+    }
+}
+
+#[test]
+fn longest_custom_ids_round_trip() {
+    for command in COMMANDS.iter() {
+        let custom_id = (command.longest_custom_id)();
+        let options = CustomIdOptions::from_custom_id(&custom_id).unwrap();
+        assert_eq!(options.cmd, command.name);
+        assert!(COMMANDS.check_custom_id(&options).is_ok(), "{custom_id}");
+        assert_eq!((command.custom_id)(&options).unwrap(), custom_id);
+    }
+}
+
+#[test]
+fn whole_numbers_are_packed() {
+    let arguments = DropArguments::new(7415, 2, 15);
+    let custom_id = arguments.to_custom_id(false);
+    assert_eq!(
+        custom_id,
+        "u/drop.1:item~1vb&min_chance=0&sort=highest&page=2&size~F"
+    );
+
+    let options = CustomIdOptions::from_custom_id(&custom_id).unwrap();
+    assert_eq!(options.version, 1);
+    assert_eq!(options.get("item").unwrap(), "7415");
+    assert_eq!(options.get("page").unwrap(), "2");
+}
+
+#[test]
+fn negative_numbers_are_not_packed() {
+    let arguments = DropArguments::new(-7415, 1, 5);
+    let options = CustomIdOptions::from_custom_id(&arguments.to_custom_id(true)).unwrap();
+    assert_eq!(options.get("item").unwrap(), "-7415");
+}
+
+#[test]
+fn stale_custom_ids_are_outdated() {
+    // from before custom ids had versions
+    let unversioned = CustomIdOptions::from_custom_id("u/drop:item=7415&page=2").unwrap();
+    assert_eq!(unversioned.version, 0);
+    assert!(COMMANDS.check_custom_id(&unversioned).is_err());
+
+    let newer = CustomIdOptions::from_custom_id("u/drop.99:item~1vb&page=2&size=5").unwrap();
+    assert!(COMMANDS.check_custom_id(&newer).is_err());
+
+    let missing_size = CustomIdOptions::from_custom_id("u/drop.1:item~1vb&page=2").unwrap();
+    assert!(COMMANDS.check_custom_id(&missing_size).is_err());
+
+    let unknown = CustomIdOptions::from_custom_id("u/teleport.1:zone=1100").unwrap();
+    assert!(COMMANDS.check_custom_id(&unknown).is_err());
+
+    assert!(CustomIdOptions::from_custom_id("u/drop.1:item~1V!").is_err());
+    assert!(CustomIdOptions::from_custom_id("not a custom id").is_err());
+}
@@ -0,0 +1,46 @@
+use lu_discord_bot::data::Snapshot;
+
+#[test]
+fn one_reload_runs_at_a_time() {
+    let snapshot = Snapshot::new(1);
+    let claim = snapshot.claim().unwrap();
+    assert!(snapshot.is_reloading());
+    assert_eq!(
+        snapshot.claim().err().as_deref(),
+        Some("A reload is already in progress")
+    );
+
+    claim.load(|| Ok(2)).unwrap();
+    assert!(!snapshot.is_reloading());
+    assert_eq!(snapshot.current(), 2);
+    assert!(snapshot.claim().is_ok());
+}
+
+#[test]
+fn failed_reloads_keep_the_current_data() {
+    let snapshot = Snapshot::new(1);
+    let failed = snapshot
+        .claim()
+        .unwrap()
+        .load(|| Err(String::from("Could not load `cdclient.sqlite`")));
+    assert_eq!(
+        failed,
+        Err(String::from("Could not load `cdclient.sqlite`"))
+    );
+    assert_eq!(snapshot.current(), 1);
+    assert!(!snapshot.is_reloading());
+
+    // a loader that panics fails the reload too, and the next one may still start
+    let panicked = snapshot
+        .claim()
+        .unwrap()
+        .load(|| panic!("locale.xml is not xml"));
+    assert_eq!(
+        panicked,
+        Err(String::from("Loading panicked: locale.xml is not xml"))
+    );
+    assert_eq!(snapshot.current(), 1);
+    assert!(!snapshot.is_reloading());
+    assert_eq!(snapshot.claim().unwrap().load(|| Ok(3)).map(|_| ()), Ok(()));
+    assert_eq!(snapshot.current(), 3);
+}
@@ -0,0 +1,52 @@
+use lu_discord_bot::commands::help;
+use lu_discord_bot::commands::COMMANDS;
+use lu_discord_bot::interaction_command::{CustomIdOptions, CUSTOM_ID_LIMIT};
+
+#[test]
+fn invocations_follow_the_subcommands() {
+    let invocations: Vec<_> = COMMANDS
+        .invocations()
+        .into_iter()
+        .map(|(invocation, command)| (invocation, command.name))
+        .collect();
+    assert!(invocations.contains(&(String::from("item drop"), "drop")));
+    assert!(invocations.contains(&(String::from("skill items"), "skill_items")));
+    assert!(invocations.contains(&(String::from("help"), "help")));
+    assert!(!invocations
+        .iter()
+        .any(|(invocation, _)| invocation == "drop"));
+    assert_eq!(invocations.len(), COMMANDS.iter().count());
+
+    // as cooldowns and `/stats` show them
+    assert_eq!(COMMANDS.invocation_of("drop"), "/item drop");
+    assert_eq!(COMMANDS.invocation_of("help"), "/help");
+    assert_eq!(COMMANDS.invocation_of("lookup"), "Look up in Crab Force");
+}
+
+#[test]
+fn examples_are_labeled_with_their_options() {
+    let examples = help::examples("drop");
+    let labels: Vec<_> = examples.iter().map(|(label, _)| label.as_str()).collect();
+    assert_eq!(
+        labels,
+        [
+            "/item drop item:7415",
+            "/item drop item:7415 min_chance:10 sort:lowest"
+        ]
+    );
+    assert_eq!(help::examples("/item drop"), examples);
+    assert_eq!(help::examples("help")[0].0, "/help command:drop");
+    assert!(help::examples("not a command").is_empty());
+}
+
+#[test]
+fn examples_run_their_command() {
+    for command in COMMANDS.iter() {
+        for custom_id in (command.examples)() {
+            assert!(custom_id.len() <= CUSTOM_ID_LIMIT, "{custom_id}");
+            let options = CustomIdOptions::from_custom_id(&custom_id).unwrap();
+            assert_eq!(options.cmd, command.name);
+            assert!(COMMANDS.check_custom_id(&options).is_ok(), "{custom_id}");
+        }
+    }
+}
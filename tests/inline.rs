@@ -0,0 +1,60 @@
+use lu_discord_bot::bot_config::BotConfig;
+use lu_discord_bot::references::{scan_names, Reference};
+use lu_discord_bot::settings::{GuildSetting, GuildSettings};
+
+#[test]
+fn only_names_are_looked_up_inline() {
+    assert_eq!(
+        scan_names(
+            "is [[Imagination Booster]] better than LOT 7415 or [[7415]]? [[imagination booster]]"
+        ),
+        [
+            Reference::Name(String::from("Imagination Booster")),
+            Reference::Name(String::from("imagination booster")),
+        ]
+    );
+    assert!(scan_names("https://explorer.lu/objects/7415 [Imagination Booster]").is_empty());
+}
+
+#[test]
+fn inline_lookups_are_opt_in_per_channel() {
+    let mut config = BotConfig {
+        message_content_intent: true,
+        ..BotConfig::default()
+    };
+    assert!(!config.looks_up_inline_in(1));
+
+    GuildSetting::InlineLookups.apply(&mut config, String::from("on"));
+    assert!(config.looks_up_inline_in(1));
+
+    GuildSetting::InlineChannels.apply(&mut config, String::from("<#10>, 20"));
+    assert_eq!(config.inline_channels, [10, 20]);
+    assert!(config.looks_up_inline_in(20));
+    assert!(!config.looks_up_inline_in(1));
+
+    // not a channel, so no channel rather than every channel
+    GuildSetting::InlineChannels.apply(&mut config, String::from("#trading"));
+    assert!(!config.looks_up_inline_in(1));
+    assert!(!config.looks_up_inline_in(20));
+    GuildSetting::InlineChannels.apply(&mut config, String::from("<#10> #trading"));
+    assert!(!config.looks_up_inline_in(10));
+
+    // every override of a guild applies, with the switch before the channels
+    let mut settings = GuildSettings::default();
+    settings
+        .values
+        .insert(GuildSetting::InlineChannels, String::from("#trading"));
+    settings
+        .values
+        .insert(GuildSetting::InlineLookups, String::from("on"));
+    assert!(!settings.apply(&config).looks_up_inline_in(1));
+    settings
+        .values
+        .insert(GuildSetting::InlineChannels, String::from("20"));
+    let mut config = settings.apply(&config);
+    assert!(config.looks_up_inline_in(20));
+    assert!(!config.looks_up_inline_in(1));
+
+    config.message_content_intent = false;
+    assert!(!config.looks_up_inline_in(20));
+}
@@ -0,0 +1,105 @@
+use lu_discord_bot::limits::{self, fit, total_len, truncate_chars, truncate_lines};
+use lu_discord_bot::response::{Footer, Response};
+
+fn chars(text: &str) -> usize {
+    text.chars().count()
+}
+
+#[test]
+fn lines_are_kept_whole() {
+    let text = (1..=100)
+        .map(|n| format!("**{n}.** Crab Force Helmet"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert_eq!(truncate_lines(&text, text.len()), text);
+
+    let cut = truncate_lines(&text, 100);
+    assert!(chars(&cut) <= 100, "{cut}");
+    let lines: Vec<_> = cut.lines().collect();
+    let (notice, kept) = lines.split_last().unwrap();
+    assert!(text.starts_with(&kept.join("\n")));
+    assert_eq!(*notice, format!("\u{2026}and {} more", 100 - kept.len()));
+
+    // a single line too long for the limit is cut inside it instead
+    let line = "x".repeat(50);
+    assert_eq!(
+        truncate_lines(&line, 10),
+        format!("{}\u{2026}", "x".repeat(9))
+    );
+}
+
+#[test]
+fn multi_byte_characters_are_never_split() {
+    // `é` and `🦀` are 2 and 4 bytes, and land right at the cut
+    let text = "é🦀".repeat(10);
+    let cut = truncate_chars(&text, 5);
+    assert_eq!(cut, "é🦀é🦀\u{2026}");
+    let cut = truncate_lines(&format!("{text}\n{text}"), 32);
+    assert_eq!(cut, format!("{text}\n\u{2026}and 1 more"));
+
+    let response = fit(Response::new().field("🦀", "🦀".repeat(2000), false));
+    let value = &response.fields[0].value;
+    assert_eq!(chars(value), limits::FIELD_VALUE);
+    assert!(value.ends_with("🦀\u{2026}"));
+}
+
+#[test]
+fn field_values_are_cut_to_1024_characters() {
+    let value = (1..=100)
+        .map(|n| format!("Loot matrix {n}: 12.5%"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let response = fit(Response::new()
+        .field("Drops", &value, false)
+        .field("", " ", true));
+    let cut = &response.fields[0].value;
+    assert!(chars(cut) <= limits::FIELD_VALUE);
+    assert!(cut.ends_with(" more"), "{cut}");
+    // Discord rejects blank names and values too
+    assert_eq!(response.fields[1].name, "\u{200b}");
+    assert_eq!(response.fields[1].value, "\u{200b}");
+}
+
+#[test]
+fn at_most_25_fields_are_kept() {
+    let mut response = Response::new().title("Vendors");
+    for n in 1..=30 {
+        response = response.field(format!("Vendor {n}"), "Sells it", true);
+    }
+    let fitted = fit(response.clone());
+    assert_eq!(fitted.fields.len(), limits::FIELDS);
+    assert_eq!(fitted.fields[..24], response.fields[..24]);
+    assert_eq!(fitted.fields[24].value, "\u{2026}and 6 more");
+
+    // exactly 25 fit as they are
+    response.fields.truncate(limits::FIELDS);
+    assert_eq!(fit(response.clone()), response);
+}
+
+#[test]
+fn the_embed_stays_within_6000_characters() {
+    let mut response = Response::new()
+        .title("Item Drops")
+        .description("d".repeat(2000));
+    for n in 1..=10 {
+        response = response.field(format!("Smashable {n}"), "v".repeat(1000), false);
+    }
+    assert!(total_len(&response) > limits::TOTAL);
+    let fitted = fit(response.clone());
+    assert!(total_len(&fitted) <= limits::TOTAL);
+    assert_eq!(fitted.description, response.description);
+    let hidden = 10 - (fitted.fields.len() - 1);
+    assert_eq!(
+        fitted.fields.last().unwrap().value,
+        format!("\u{2026}and {hidden} more")
+    );
+
+    // with no fields left to drop, the description is cut as well
+    let fitted = fit(Response::new()
+        .description("d".repeat(4000))
+        .footer(Footer {
+            text: "f".repeat(2048),
+            icon_url: None,
+        }));
+    assert!(total_len(&fitted) <= limits::TOTAL);
+}
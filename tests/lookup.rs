@@ -0,0 +1,68 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use lu_discord_bot::analytics::Lookup;
+use lu_discord_bot::cdclient::CdClient;
+use lu_discord_bot::commands::lookup::LookupCommand;
+use lu_discord_bot::inline;
+use rusqlite::Connection;
+
+/// A `cdclient.sqlite` with one achievement and every other table empty, and its name
+fn data_files() -> (PathBuf, PathBuf) {
+    let dir = env::temp_dir().join(format!("crab-force-lookup-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let cdclient = dir.join("cdclient.sqlite");
+    _ = fs::remove_file(&cdclient);
+    let conn = Connection::open(&cdclient).unwrap();
+    // NULL for what may be missing and 0 for the other numbers
+    let mut row = vec!["NULL"; 52];
+    for idx in [7, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 22, 23, 24, 28]
+        .into_iter()
+        .chain(30..=37)
+        .chain([39, 42, 43, 45, 50])
+    {
+        row[idx] = "0";
+    }
+    (row[0], row[1]) = ("1727", "'Achievement'");
+    let columns: Vec<_> = (0..row.len()).map(|idx| format!("c{idx}")).collect();
+    conn.execute_batch(&format!(
+        "CREATE TABLE Missions ({}); INSERT INTO Missions VALUES ({});",
+        columns.join(", "),
+        row.join(", ")
+    ))
+    .unwrap();
+    // empty tables are never read, so any column will do
+    while let Err(e) = CdClient::load_sqlite(&cdclient) {
+        let message = e.to_string();
+        let table = message
+            .strip_prefix("no such table: ")
+            .unwrap_or_else(|| panic!("{message}"));
+        conn.execute_batch(&format!("CREATE TABLE {table} (id)"))
+            .unwrap();
+    }
+
+    let locale = dir.join("locale.xml");
+    fs::write(
+        &locale,
+        r#"<localization>
+<phrase id="Missions_1727_name"><translation locale="en_US">Crab Force Recruit</translation></phrase>
+</localization>"#,
+    )
+    .unwrap();
+    (cdclient, locale)
+}
+
+#[test]
+fn inline_lookups_show_the_card_the_message_command_does() {
+    let (cdclient, locale) = data_files();
+    env::set_var("CRAB_FORCE_CDCLIENT_PATH", &cdclient);
+    env::set_var("CRAB_FORCE_LOCALE_PATH", &locale);
+
+    let card = LookupCommand::card(Lookup::Mission, 1727).unwrap();
+    assert!(card.title.as_deref().unwrap().contains("1727"), "{card:?}");
+    assert_eq!(
+        inline::card(Lookup::Mission, 1727).unwrap(),
+        card.components(vec![])
+    );
+}
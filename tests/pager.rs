@@ -0,0 +1,176 @@
+use lu_discord_bot::commands::help::HelpArguments;
+use lu_discord_bot::interaction_command::ToCustomId;
+use lu_discord_bot::pager::Pager;
+use lu_discord_bot::response::{ActionRow, ButtonAction, SelectOption};
+
+fn pager(count: usize, page: usize, size: usize) -> Pager<usize> {
+    Pager::new((1..=count).collect(), page, size)
+}
+
+fn to_page(page: usize) -> HelpArguments {
+    HelpArguments {
+        command: None,
+        page,
+    }
+}
+
+/// The label, target page and whether it is disabled of every button in [`Pager::row`]
+fn buttons(pager: &Pager<usize>) -> Vec<(String, Option<usize>, bool)> {
+    let Some(ActionRow::Buttons(buttons)) = pager.row(to_page) else {
+        panic!("no buttons");
+    };
+    buttons
+        .into_iter()
+        .map(|button| {
+            let page = match &button.action {
+                ButtonAction::Run(custom_id) => (1..=pager.page_count())
+                    .find(|page| *custom_id == to_page(*page).to_custom_id(false)),
+                _ => None,
+            };
+            (button.label, page, button.disabled)
+        })
+        .collect()
+}
+
+#[test]
+fn pages_are_clamped() {
+    assert_eq!(pager(12, 0, 5).current(), 1);
+    assert_eq!(pager(12, 99, 5).current(), 3);
+    assert_eq!(pager(12, 99, 5).this_page(), vec![(11, 11), (12, 12)]);
+
+    let empty = pager(0, 4, 5);
+    assert_eq!(empty.current(), 1);
+    assert_eq!(empty.page_count(), 1);
+    assert!(empty.this_page().is_empty());
+    assert!(empty.row(to_page).is_none());
+
+    // a page size of 0 would never end
+    assert_eq!(pager(3, 1, 0).page_size(), 1);
+    assert_eq!(pager(3, 1, 0).page_count(), 3);
+}
+
+#[test]
+fn the_last_page_holds_what_is_left() {
+    let last = pager(12, 3, 5);
+    assert_eq!(last.page_count(), 3);
+    assert_eq!(last.total(), 12);
+    assert!(last.is_last_page());
+    assert_eq!(
+        last.lines(|num, entry| format!("{num}: {entry}")),
+        "11: 11\n12: 12"
+    );
+
+    assert_eq!(pager(10, 2, 5).page_count(), 2);
+    assert!(pager(10, 2, 5).has_page(2));
+    assert!(!pager(10, 2, 5).has_page(3));
+}
+
+#[test]
+fn entries_are_numbered_across_pages() {
+    let pager: Pager<char> = Pager::new(('a'..='l').collect(), 2, 5);
+    assert_eq!(
+        pager.this_page(),
+        vec![(6, 'f'), (7, 'g'), (8, 'h'), (9, 'i'), (10, 'j')]
+    );
+    assert_eq!(
+        pager.lines(|num, entry| format!("{num}. {entry}")),
+        "6. f\n7. g\n8. h\n9. i\n10. j"
+    );
+    assert_eq!(
+        pager.columns(2, |num, entry| format!("{num}. {entry}")),
+        vec!["6. f\n7. g\n8. h", "9. i\n10. j"]
+    );
+    // empty columns are left out
+    assert_eq!(pager.columns(9, |num, _| num.to_string()).len(), 5);
+}
+
+#[test]
+fn buttons_are_disabled_at_each_edge() {
+    let go_to = |current| format!("Go to page… ({current}/4)");
+
+    assert_eq!(
+        buttons(&pager(20, 1, 5)),
+        vec![
+            ("Page 1".into(), Some(1), true),
+            (go_to(1), None, false),
+            ("Page 2".into(), Some(2), false),
+            ("Last".into(), Some(4), false),
+        ]
+    );
+    // First would be the same as the previous page
+    assert_eq!(
+        buttons(&pager(20, 2, 5)),
+        vec![
+            ("Page 1".into(), Some(1), false),
+            (go_to(2), None, false),
+            ("Page 3".into(), Some(3), false),
+            ("Last".into(), Some(4), false),
+        ]
+    );
+    // Last would be the same as the next page
+    assert_eq!(
+        buttons(&pager(20, 3, 5)),
+        vec![
+            ("First".into(), Some(1), false),
+            ("Page 2".into(), Some(2), false),
+            (go_to(3), None, false),
+            ("Page 4".into(), Some(4), false),
+        ]
+    );
+    assert_eq!(
+        buttons(&pager(20, 4, 5)),
+        vec![
+            ("First".into(), Some(1), false),
+            ("Page 3".into(), Some(3), false),
+            (go_to(4), None, false),
+            ("Page 4".into(), Some(4), true),
+        ]
+    );
+    assert!(pager(5, 1, 5).row(to_page).is_none());
+}
+
+#[test]
+fn page_sizes_keep_the_first_entry_shown() {
+    assert!(pager(5, 1, 5).size_menu(|page, _| to_page(page)).is_none());
+
+    // entries 11 to 15 are shown
+    let Some(ActionRow::SelectMenu(menu)) =
+        pager(20, 3, 5).size_menu(|page, size| to_page(page * 100 + size))
+    else {
+        panic!("no size menu");
+    };
+    assert_eq!(menu.placeholder.as_deref(), Some("5 per page"));
+    let options: Vec<_> = menu
+        .options
+        .into_iter()
+        .map(|option| (option.label, option.value))
+        .collect();
+    assert_eq!(
+        options,
+        vec![
+            ("5 per page".into(), to_page(305).to_custom_id(false)),
+            ("10 per page".into(), to_page(210).to_custom_id(false)),
+            ("15 per page".into(), to_page(115).to_custom_id(false)),
+        ]
+    );
+}
+
+#[test]
+fn select_menus_hold_at_most_25_options() {
+    let to_option = |entry: usize| SelectOption::new(entry.to_string(), entry.to_string());
+
+    let Some(ActionRow::SelectMenu(menu)) = pager(40, 1, 30).select_menu("pick", to_option) else {
+        panic!("no select menu");
+    };
+    assert_eq!(menu.custom_id, "pick");
+    assert_eq!(menu.options.len(), 25);
+    assert_eq!(menu.options[24].value, "25");
+
+    let Some(ActionRow::SelectMenu(menu)) = pager(40, 2, 30).select_menu("pick", to_option) else {
+        panic!("no select menu");
+    };
+    assert_eq!(menu.options.len(), 10);
+    assert_eq!(menu.options[0].value, "31");
+
+    assert!(pager(0, 1, 30).select_menu("pick", to_option).is_none());
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "prometheus")]
+
+use std::time::Duration;
+
+use lu_discord_bot::metrics::{self, Histogram, InteractionKind, Outcome};
+use lu_discord_bot::prometheus;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+async fn get(port: u16, path: &str) -> String {
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+    let request = format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n");
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    response
+}
+
+#[tokio::test]
+async fn metrics_are_scraped_over_http() {
+    metrics::record(
+        "drop",
+        InteractionKind::Slash,
+        Duration::from_millis(20),
+        &Outcome::Ok,
+    );
+    metrics::record(
+        "we\"ird\\na\nme",
+        InteractionKind::Component,
+        Duration::from_millis(20),
+        &Outcome::Ok,
+    );
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(prometheus::serve_on(listener));
+
+    let response = get(port, "/metrics").await;
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    assert!(head.starts_with("HTTP/1.1 200 OK"), "{head}");
+    assert!(head.contains("Content-Type: text/plain; version=0.0.4"));
+    assert!(head.contains(&format!("Content-Length: {}", body.len())));
+    assert!(body.contains(
+        "crab_force_interactions_total{command=\"drop\",kind=\"slash\",outcome=\"ok\"} 1"
+    ));
+    assert!(body.contains(
+        "crab_force_interactions_total{command=\"we\\\"ird\\\\na\\nme\",kind=\"component\",outcome=\"ok\"} 1"
+    ));
+    // every sample is on a line of its own, even with a newline in a label
+    for line in body.lines().filter(|line| !line.starts_with('#')) {
+        let value = line.rsplit_once(' ').unwrap().1;
+        assert!(value.parse::<f64>().is_ok(), "{line}");
+    }
+
+    let response = get(port, "/").await;
+    assert!(response.starts_with("HTTP/1.1 404 Not Found"));
+}
+
+#[test]
+fn histograms_without_labels_have_no_braces() {
+    let mut histogram = Histogram::default();
+    histogram.observe(Duration::from_millis(250));
+
+    let samples = histogram.render_prometheus("load_seconds", "");
+    assert!(samples.contains("load_seconds_bucket{le=\"0.1\"} 0\n"));
+    assert!(samples.contains("load_seconds_bucket{le=\"0.25\"} 1\n"));
+    assert!(samples.contains("load_seconds_bucket{le=\"+Inf\"} 1\n"));
+    assert!(samples.ends_with("load_seconds_sum 0.25\nload_seconds_count 1\n"));
+
+    let samples = histogram.render_prometheus("load_seconds", "file=\"locale.xml\"");
+    assert!(samples.contains("load_seconds_bucket{file=\"locale.xml\",le=\"+Inf\"} 1\n"));
+    assert!(samples.ends_with(
+        "load_seconds_sum{file=\"locale.xml\"} 0.25\nload_seconds_count{file=\"locale.xml\"} 1\n"
+    ));
+}
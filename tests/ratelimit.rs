@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use lu_discord_bot::metrics::InteractionKind;
+use lu_discord_bot::ratelimit::{Limit, LimitScope, RateLimiter, RateLimits, PRUNE_EVERY};
+
+fn limits() -> RateLimits {
+    RateLimits {
+        enabled: true,
+        exempt_owners: true,
+        user: Limit::new(10, 20),
+        guild: Limit::new(100, 100),
+        command: Limit::new(3, 6),
+        commands: BTreeMap::from([(String::from("drop"), Limit::new(1, 10))]),
+        autocomplete: Limit::new(2, 1),
+    }
+}
+
+#[test]
+fn a_burst_is_allowed_up_to_capacity() {
+    let limits = limits();
+    let now = Instant::now();
+    let mut limiter = RateLimiter::new(now);
+    let slash = |limiter: &mut RateLimiter, command: &str, user: u64| {
+        limiter.check(&limits, InteractionKind::Slash, command, user, Some(1), now)
+    };
+    for _ in 0..3 {
+        assert!(slash(&mut limiter, "item", 1).is_ok());
+    }
+    let cooldown = slash(&mut limiter, "item", 1).unwrap_err();
+    assert_eq!(cooldown.scope, LimitScope::Command);
+    // a token comes back every 2 seconds
+    assert_eq!(cooldown.retry_after, Duration::from_secs(2));
+
+    // other commands and users have buckets of their own
+    assert!(slash(&mut limiter, "level", 1).is_ok());
+    assert!(slash(&mut limiter, "item", 2).is_ok());
+    // and some commands have their own limit
+    assert!(slash(&mut limiter, "drop", 1).is_ok());
+    let cooldown = slash(&mut limiter, "drop", 1).unwrap_err();
+    assert_eq!(cooldown.retry_after, Duration::from_secs(10));
+}
+
+#[test]
+fn buckets_refill_evenly() {
+    let limits = limits();
+    let start = Instant::now();
+    let mut limiter = RateLimiter::new(start);
+    let mut slash =
+        |at: Duration| limiter.check(&limits, InteractionKind::Slash, "item", 1, None, start + at);
+    for _ in 0..3 {
+        assert!(slash(Duration::ZERO).is_ok());
+    }
+    // half a token back, so half the wait is left
+    let cooldown = slash(Duration::from_secs(1)).unwrap_err();
+    assert_eq!(cooldown.retry_after, Duration::from_secs(1));
+    assert!(slash(Duration::from_secs(2)).is_ok());
+    assert!(slash(Duration::from_secs(2)).is_err());
+    // never more than capacity, however long it has been
+    for _ in 0..3 {
+        assert!(slash(Duration::from_secs(60)).is_ok());
+    }
+    assert!(slash(Duration::from_secs(60)).is_err());
+}
+
+#[test]
+fn the_slowest_bucket_is_reported() {
+    let limits = RateLimits {
+        user: Limit::new(2, 20),
+        ..limits()
+    };
+    let now = Instant::now();
+    let mut limiter = RateLimiter::new(now);
+    for command in ["item", "level"] {
+        assert!(limiter
+            .check(&limits, InteractionKind::Slash, command, 1, None, now)
+            .is_ok());
+    }
+    let cooldown = limiter
+        .check(&limits, InteractionKind::Slash, "mission", 1, None, now)
+        .unwrap_err();
+    assert_eq!(cooldown.scope, LimitScope::User);
+    assert_eq!(cooldown.retry_after, Duration::from_secs(10));
+
+    // autocomplete only has a bucket per user
+    for _ in 0..2 {
+        assert!(limiter
+            .check(&limits, InteractionKind::Autocomplete, "item", 1, None, now)
+            .is_ok());
+    }
+    let cooldown = limiter
+        .check(&limits, InteractionKind::Autocomplete, "item", 1, None, now)
+        .unwrap_err();
+    assert_eq!(cooldown.scope, LimitScope::User);
+    assert_eq!(cooldown.retry_after, Duration::from_millis(500));
+}
+
+#[test]
+fn full_buckets_are_pruned_on_an_interval() {
+    let limits = limits();
+    let start = Instant::now();
+    let mut limiter = RateLimiter::new(start);
+    for user in 0..100 {
+        assert!(limiter
+            .check(
+                &limits,
+                InteractionKind::Slash,
+                "item",
+                user,
+                Some(1),
+                start
+            )
+            .is_ok());
+    }
+    // a user bucket, a command bucket for each user, and one guild bucket
+    assert_eq!(limiter.len(), 201);
+
+    // the guild bucket takes 100 seconds to refill, the rest are full again
+    let later = start + PRUNE_EVERY;
+    assert!(limiter
+        .check(
+            &limits,
+            InteractionKind::Autocomplete,
+            "item",
+            0,
+            None,
+            later
+        )
+        .is_ok());
+    assert_eq!(limiter.len(), 2);
+}
@@ -0,0 +1,122 @@
+use lu_discord_bot::analytics::Lookup;
+use lu_discord_bot::commands::COMMANDS;
+use lu_discord_bot::references::{scan, ExplorerUrls, Reference};
+use serde_json::json;
+use serenity::all::CommandData;
+
+fn explorer() -> ExplorerUrls {
+    ExplorerUrls {
+        objects: String::from("https://explorer.lu/objects/"),
+        missions: String::from("https://explorer.lu/missions/"),
+        skills: String::from("https://explorer.lu/skills/"),
+    }
+}
+
+#[test]
+fn explorer_links_are_found_in_order() {
+    let text = "found it on https://explorer.lu/missions/1727, it gives \
+                explorer.lu/objects/7415 and HTTP://EXPLORER.LU/skills/148/ too";
+    assert_eq!(
+        scan(text, &explorer()),
+        [
+            Reference::Id(Lookup::Mission, 1727),
+            Reference::Id(Lookup::Object, 7415),
+            Reference::Id(Lookup::Skill, 148),
+        ]
+    );
+    let elsewhere = "https://example.com/objects/7415";
+    assert!(scan(elsewhere, &explorer()).is_empty());
+}
+
+#[test]
+fn lot_numbers_and_names_are_found() {
+    assert_eq!(
+        scan(
+            "does LOT 7415 beat [[Flaming Sword]] or lot:1727? [[ ]]",
+            &explorer()
+        ),
+        [
+            Reference::Id(Lookup::Object, 7415),
+            Reference::Name(String::from("Flaming Sword")),
+            Reference::Id(Lookup::Object, 1727),
+        ]
+    );
+    assert_eq!(
+        scan(" 7415\n", &explorer()),
+        [Reference::Id(Lookup::Object, 7415)]
+    );
+    assert!(scan("I have 7415 coins", &explorer()).is_empty());
+}
+
+#[test]
+fn each_reference_is_found_once() {
+    // how the bot links an object itself
+    let text = "Flaming Sword [`[7415]`](https://explorer.lu/objects/7415) and [[7415]]";
+    assert_eq!(
+        scan(text, &explorer()),
+        [Reference::Id(Lookup::Object, 7415)]
+    );
+}
+
+#[test]
+fn ids_in_cards_take_the_kind_of_their_link() {
+    // the url, title and fields of a mission card, as the message command reads them
+    let mission = "https://explorer.lu/missions/1727\n\
+                   Crab Force Recruit [1727]\n\
+                   Prerequisites\n\
+                   Join Crab Force [`[1726]`](https://explorer.lu/missions/1726)\n\
+                   Rewards\n\
+                   Crab Force Helmet [`[7415]`](https://explorer.lu/objects/7415)";
+    assert_eq!(
+        scan(mission, &explorer()),
+        [
+            Reference::Id(Lookup::Mission, 1727),
+            Reference::Id(Lookup::Mission, 1726),
+            Reference::Id(Lookup::Object, 7415),
+        ]
+    );
+
+    let skill = "https://explorer.lu/skills/148\n\
+                 Speed Boost [148]\n\
+                 Items\n\
+                 Speed Boots [`[148]`](https://explorer.lu/objects/148)";
+    assert_eq!(
+        scan(skill, &explorer()),
+        [
+            Reference::Id(Lookup::Skill, 148),
+            Reference::Id(Lookup::Object, 148),
+        ]
+    );
+    // a title before its url still takes its kind
+    assert_eq!(
+        scan(
+            "Speed Boost [148]\nhttps://explorer.lu/skills/148",
+            &explorer()
+        ),
+        [Reference::Id(Lookup::Skill, 148)]
+    );
+}
+
+#[test]
+fn message_commands_are_registered_and_named() {
+    let registered: Vec<_> = COMMANDS
+        .register_all()
+        .into_iter()
+        .map(|command| serde_json::to_value(command).unwrap())
+        .collect();
+    let lookup = registered
+        .iter()
+        .find(|command| command["name"] == "Look up in Crab Force")
+        .unwrap();
+    assert_eq!(lookup["type"], 3);
+
+    let data: CommandData = serde_json::from_value(json!({
+        "id": "1",
+        "name": "Look up in Crab Force",
+        "type": 3,
+        "target_id": "2",
+    }))
+    .unwrap();
+    assert_eq!(COMMANDS.command_name(&data), "lookup");
+    assert!(COMMANDS.resolve(&data.name, &data.options()).is_none());
+}
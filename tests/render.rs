@@ -0,0 +1,179 @@
+use lu_discord_bot::render::{
+    DiscordRenderer, JsonRenderer, MarkdownRenderer, Renderer, TextRenderer,
+};
+use lu_discord_bot::response::{
+    ActionRow, Button, ButtonAction, ButtonStyle, Footer, Response, SelectMenu, SelectOption,
+};
+use serde_json::json;
+
+const EXPLORER: &str = "https://explorer.lu/objects/7415";
+
+/// A card like `/item buy` replies with
+fn card() -> Response {
+    Response::new()
+        .title("Crab Force Helmet [7415]")
+        .url(EXPLORER)
+        .description("**Vendors**\n1. [Crab Vendor](https://explorer.lu/objects/100)")
+        .field("Coins", "**250** coins", true)
+        .color((26, 135, 232))
+        .footer(Footer {
+            text: String::from("Crab Force"),
+            icon_url: None,
+        })
+        .row(ActionRow::Buttons(vec![
+            Button::new("Buy", ButtonAction::Current),
+            Button::new(
+                "Earn",
+                ButtonAction::Run(String::from("u/earn.1:item=7415")),
+            )
+            .style(ButtonStyle::Secondary),
+            Button::link("Explorer", EXPLORER).disabled(true),
+        ]))
+        .row(ActionRow::SelectMenu(
+            SelectMenu::new(
+                "buy",
+                vec![
+                    SelectOption::new("Crab Vendor [100]", "r/vendor.1:vendor=100")
+                        .description("Sells it"),
+                ],
+            )
+            .placeholder("View a vendor"),
+        ))
+}
+
+#[test]
+fn text_has_no_markdown() {
+    assert_eq!(
+        TextRenderer.render(&card()),
+        "Crab Force Helmet [7415]
+https://explorer.lu/objects/7415
+
+Vendors
+1. Crab Vendor
+
+Coins:
+250 coins
+
+Row 1:
+    [Buy] (shown)
+    [Earn] u/earn.1:item=7415
+    [Explorer] https://explorer.lu/objects/7415 (disabled)
+
+Row 2:
+    <Crab Vendor [100]> r/vendor.1:vendor=100"
+    );
+}
+
+#[test]
+fn markdown_links_the_title_and_lists_what_can_be_run() {
+    assert_eq!(
+        MarkdownRenderer.render(&card()),
+        "## [Crab Force Helmet [7415]](https://explorer.lu/objects/7415)
+
+**Vendors**
+1. [Crab Vendor](https://explorer.lu/objects/100)
+
+### Coins
+**250** coins
+
+- Earn: `u/earn.1:item=7415`
+- [Explorer](https://explorer.lu/objects/7415)
+- Crab Vendor [100]: `r/vendor.1:vendor=100`
+
+<sub>Crab Force</sub>"
+    );
+}
+
+#[test]
+fn json_is_the_response_as_is() {
+    let json = JsonRenderer.render(&card());
+    assert_eq!(json["title"], "Crab Force Helmet [7415]");
+    assert_eq!(json["color"], json!([26, 135, 232]));
+    assert_eq!(
+        json["fields"],
+        json!([{ "name": "Coins", "value": "**250** coins", "inline": true }])
+    );
+    assert_eq!(
+        json["footer"],
+        json!({ "text": "Crab Force", "icon_url": null })
+    );
+    assert_eq!(json["components"][0]["type"], "buttons");
+    assert_eq!(
+        json["components"][0]["components"][1],
+        json!({
+            "label": "Earn",
+            "action": { "type": "run", "target": "u/earn.1:item=7415" },
+            "style": "secondary",
+            "disabled": false,
+        })
+    );
+    assert_eq!(json["components"][1]["type"], "select_menu");
+    assert_eq!(json["components"][1]["components"]["custom_id"], "buy");
+}
+
+#[test]
+fn discord_gets_an_embed_and_its_rows() {
+    let (embed, rows) = DiscordRenderer.render(&card());
+    assert_eq!(
+        serde_json::to_value(embed).unwrap(),
+        json!({
+            "type": "rich",
+            "title": "Crab Force Helmet [7415]",
+            "url": EXPLORER,
+            "description": "**Vendors**\n1. [Crab Vendor](https://explorer.lu/objects/100)",
+            "fields": [{ "name": "Coins", "value": "**250** coins", "inline": true }],
+            "color": 0x1a87e8,
+            "footer": { "text": "Crab Force" },
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(rows).unwrap(),
+        json!([
+            {
+                "type": 1,
+                "components": [
+                    // what is shown can't be pressed, and its custom id is never used
+                    { "type": 2, "style": 3, "label": "Buy", "custom_id": "----", "disabled": true },
+                    {
+                        "type": 2,
+                        "style": 2,
+                        "label": "Earn",
+                        "custom_id": "u/earn.1:item=7415",
+                        "disabled": false,
+                    },
+                    { "type": 2, "style": 5, "label": "Explorer", "url": EXPLORER, "disabled": true },
+                ],
+            },
+            {
+                "type": 1,
+                "components": [{
+                    "type": 3,
+                    "custom_id": "buy",
+                    "placeholder": "View a vendor",
+                    "options": [{
+                        "label": "Crab Vendor [100]",
+                        "value": "r/vendor.1:vendor=100",
+                        "description": "Sells it",
+                    }],
+                }],
+            },
+        ])
+    );
+}
+
+#[test]
+fn discord_gets_what_fits() {
+    let buttons = (1..=7)
+        .map(|page| Button::new(format!("Page {page}"), ButtonAction::Current))
+        .collect();
+    let response = card().components(vec![ActionRow::Buttons(buttons)]);
+    let (_, rows) = DiscordRenderer.render(&response);
+    let rows = serde_json::to_value(rows).unwrap();
+    let lengths: Vec<_> = rows
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|row| row["components"].as_array().unwrap().len())
+        .collect();
+    assert_eq!(lengths, [5, 2]);
+}
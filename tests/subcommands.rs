@@ -0,0 +1,151 @@
+use lu_discord_bot::commands::drop::DropCommand;
+use lu_discord_bot::commands::mission::MissionCommand;
+use lu_discord_bot::commands::skillitems::SkillItemsCommand;
+use lu_discord_bot::commands::COMMANDS;
+use lu_discord_bot::registry::{CommandGroup, CommandRegistry};
+use serde_json::{json, Value};
+use serenity::all::{CommandData, ResolvedValue};
+
+/// What Discord sends for a slash command or autocomplete, ex: `/item drop item:7415`
+fn command_data(name: &str, options: Value) -> CommandData {
+    serde_json::from_value(json!({
+        "id": "1",
+        "name": name,
+        "type": 1,
+        "options": options,
+    }))
+    .unwrap()
+}
+
+fn subcommand(name: &str, options: Value) -> Value {
+    json!({"name": name, "type": 1, "options": options})
+}
+
+fn item(value: i64) -> Value {
+    json!([{"name": "item", "type": 4, "value": value}])
+}
+
+#[test]
+fn subcommands_run_their_command() {
+    let data = command_data("item", json!([subcommand("drop", item(7415))]));
+    let options = data.options();
+    let (command, options) = COMMANDS.resolve(&data.name, &options).unwrap();
+    assert_eq!(command.name, "drop");
+    assert_eq!(options.len(), 1);
+    assert_eq!(options[0].name, "item");
+    assert!(matches!(options[0].value, ResolvedValue::Integer(7415)));
+    assert_eq!(COMMANDS.command_name(&data), "drop");
+
+    let info = command_data("item", json!([subcommand("info", item(7415))]));
+    assert_eq!(COMMANDS.command_name(&info), "item");
+}
+
+#[test]
+fn top_level_commands_still_run() {
+    let data = command_data(
+        "mission",
+        json!([{"name": "mission", "type": 4, "value": 1727}]),
+    );
+    let options = data.options();
+    let (command, options) = COMMANDS.resolve(&data.name, &options).unwrap();
+    assert_eq!(command.name, "mission");
+    assert_eq!(options[0].name, "mission");
+}
+
+#[test]
+fn grouped_commands_are_not_top_level() {
+    let data = command_data("drop", item(7415));
+    assert!(COMMANDS.resolve(&data.name, &data.options()).is_none());
+    assert_eq!(COMMANDS.command_name(&data), "drop");
+
+    let elsewhere = command_data("skill", json!([subcommand("drop", item(7415))]));
+    assert!(COMMANDS
+        .resolve(&elsewhere.name, &elsewhere.options())
+        .is_none());
+}
+
+#[test]
+fn autocomplete_finds_the_focused_option_of_a_subcommand() {
+    let data = command_data(
+        "skill",
+        json!([subcommand(
+            "items",
+            json!([{"name": "skill", "type": 4, "value": "fire", "focused": true}])
+        )]),
+    );
+    let options = data.options();
+    let (command, _) = COMMANDS.resolve(&data.name, &options).unwrap();
+    assert_eq!(command.name, "skill_items");
+    assert_eq!(data.autocomplete().unwrap().value, "fire");
+}
+
+#[test]
+fn subcommand_groups_are_one_level_deeper() {
+    let registry = CommandRegistry::new().with::<MissionCommand>().group(
+        CommandGroup::new("lookup", "Look something up!")
+            .command::<SkillItemsCommand>("skill")
+            .group(CommandGroup::new("item", "Look up an item!").command::<DropCommand>("drop")),
+    );
+    let data = command_data(
+        "lookup",
+        json!([{"name": "item", "type": 2, "options": [subcommand("drop", item(7415))]}]),
+    );
+    let options = data.options();
+    let (command, options) = registry.resolve(&data.name, &options).unwrap();
+    assert_eq!(command.name, "drop");
+    assert_eq!(options[0].name, "item");
+
+    let registered: Vec<_> = registry
+        .register_all()
+        .into_iter()
+        .map(|command| serde_json::to_value(command).unwrap())
+        .collect();
+    let lookup = registered
+        .iter()
+        .find(|command| command["name"] == "lookup")
+        .unwrap();
+    assert_eq!(lookup["options"][0]["name"], "skill");
+    assert_eq!(lookup["options"][0]["type"], 1);
+    assert_eq!(lookup["options"][1]["name"], "item");
+    assert_eq!(lookup["options"][1]["type"], 2);
+    assert_eq!(lookup["options"][1]["options"][0]["name"], "drop");
+    assert_eq!(
+        lookup["options"][1]["options"][0]["options"][0]["name"],
+        "item"
+    );
+}
+
+#[test]
+fn every_command_is_registered_once() {
+    let registered: Vec<_> = COMMANDS
+        .register_all()
+        .into_iter()
+        .map(|command| serde_json::to_value(command).unwrap())
+        .collect();
+    let names: Vec<_> = registered
+        .iter()
+        .map(|command| command["name"].as_str().unwrap())
+        .collect();
+    assert!(names.contains(&"item") && names.contains(&"skill"));
+    assert!(!names.contains(&"drop") && !names.contains(&"skill_items"));
+
+    // every command is either top-level or a subcommand, so each can still be run
+    let subcommands: usize = registered
+        .iter()
+        // message commands are not run by name
+        .filter(|command| command["type"] != 3)
+        .map(|command| match command["options"][0]["type"] == 1 {
+            true => command["options"].as_array().unwrap().len(),
+            false => 1,
+        })
+        .sum();
+    assert_eq!(subcommands, COMMANDS.iter().count());
+}
+
+#[test]
+#[should_panic(expected = "registered twice")]
+fn subcommand_names_are_unique() {
+    CommandGroup::new("item", "An item!")
+        .command::<DropCommand>("drop")
+        .command::<SkillItemsCommand>("drop");
+}